@@ -1,6 +1,6 @@
 # CLI help text
 cli-about = Morphological test runner (surface/analyze and lexical/generate)
-cli-backend = Select FST format/section (hfst or foma) [alias: -S/--section]
+cli-backend = Select FST format/section (hfst, foma, apertium, http or command) [alias: -S/--section]
 cli-generator = Override generator FST (.hfstol for HFST, .foma for Foma) [alias: --gen]
 cli-analyser = Override analyser FST (.hfstol for HFST, .foma for Foma) [alias: --morph, --analyzer]
 cli-silent = Silent mode: no output, and suppress stderr from lookup
@@ -16,6 +16,14 @@ cli-hide-passes = Hide passed (PASS), show only failures (FAIL)
 cli-test = Run only specified test: number 1..N, title "Group (Lexical/Generation|Surface/Analysis)" or just the group name from YAML. Special: 0, 'null' or 'list' lists all available tests and exits.
 cli-output = Report format: normal | compact | terse | final (default: normal)
 cli-serial = Use serial execution instead of parallel processing (default is parallel)
+cli-native = Use the built-in reader for transducers (.hfstol, .foma, .att) instead of an external lookup tool
+cli-timeout = Seconds to wait for the lookup of a single input before reporting it as timed out [default: 30, or Timeout in the YAML Config]
+cli-cache = Keep lookup results on disk and reuse them while the transducer and lookup tool are unchanged
+cli-cache-dir = Directory for cached lookup results (implies --cache) [default: the user cache directory]
+cli-endpoint = Run all suites against a morphology service at URL instead of their transducers
+cli-record = Record every lookup to a fixture FILE, for replaying without transducers
+cli-replay = Answer lookups from a fixture FILE recorded with --record
+cli-normalize = Strip flag diacritics such as @P.x.y@ from the outputs before comparing them, as Flags: true under Normalize in the YAML Config does
 
 # Directions and modes
 direction-generate = Lexical/Generation
@@ -40,6 +48,7 @@ info-starting-tests = Starting testing ({$count} tests, mode: {$mode}) (batch pr
 info-starting-parallel = Suite: {$name} (parallel processing)...
 info-finished = Finished: passed {$passed}, failed {$failed}. Writing report...
 info-all-finished = All test runs finished. Total: {$total}, Passed: {$passed}, Failed: {$failed}
+info-native-lookup = built-in reader
 
 # Test listing
 available-tests = Available tests:
@@ -59,19 +68,42 @@ report-test-header = Test {$index}: {$group} ({$direction})
 report-test-summary = Test {$index} - Passes: {$passes}, Fails: {$fails}, Total: {$total}
 report-total-summary = Total passes: {$passes}, Total fails: {$fails}, Total: {$total}
 report-final-counts = {$passes}/{$fails}/{$total}
+report-weight-best = {$output} (best weighted)
+report-weight-below = {$output} (weight below {$limit})
+report-raw = Raw
 
 # Backend error messages
 backend-failed-to-start = Failed to start '{$cmd}'
 backend-missing-stdin = Missing stdin
-backend-process-failed = Lookup process failed with status {$status}
-backend-timeout = Lookup timeout after {$seconds} s
-backend-process-failed-stderr = Lookup process failed with status {$status}
+backend-process-failed = Lookup process exited with status {$status} after answering every input
 Stderr: {$stderr}
 backend-analyzer-not-set = Analyzer FST not set
 backend-generator-not-set = Generator FST not set
 backend-command-not-executable = Lookup command '{$cmd}' could not be executed: {$error}
 backend-command-not-found = Lookup command '{$cmd}' not found or cannot be executed. Check that it is installed and in PATH.
 backend-command-error = Cannot execute lookup command '{$cmd}': {$error}
+backend-missing-stdout = Missing stdout
+backend-input-timeout = No answer within {$seconds} s for input '{$input}'
+backend-process-ended = Lookup process ended while answering input '{$input}' (it was restarted for the remaining inputs)
+http-request-failed = Request to {$endpoint} failed: {$error}
+http-wrong-result-count = Service answered {$got} results for {$expected} inputs
+http-unreachable = Morphology service at {$endpoint} is not usable: {$error}
+command-invalid-pattern = Invalid output pattern: {$pattern}
+command-pattern-without-output = Output pattern has no 'output' group: {$pattern}
+command-missing-pattern = The regex output parser needs a Pattern
+command-unknown-parser = Unknown output parser '{$name}' (hfst, apertium, lines or regex)
+format-fst-not-found = Transducer not found: {$file}
+format-fst-unreadable = Cannot read transducer: {$file}
+format-fst-wrong-format = {$file} is {$format}, which '{$cmd}' cannot read
+format-hfstol = an HFST optimized-lookup transducer
+format-hfst = an HFST transducer of type {$kind}
+format-foma = a foma network
+format-att = an AT&T text transducer
+format-lttoolbox = an lttoolbox transducer
+format-unknown = of unknown format
+format-fst-unknown = The format of {$file} is not known: it has no HFST header and is neither a foma network nor AT&T text
+backend-gave-up = The lookup process failed before answering any input, so it was not restarted: {$error}
+backend-not-looked-up = Not looked up, as a fresh lookup process failed before answering any input: {$error}
 
 # Pool error messages
 pool-io-error = IO error reading from process: {$error}
@@ -85,28 +117,94 @@ pool-get-analyze-failed = Failed to get process from analyze pool: {$error}
 pool-get-generate-failed = Failed to get process from generate pool: {$error}
 pool-validate-analyze-failed = Failed to get process from analyze pool for validation: {$error}
 pool-validate-generate-failed = Failed to get process from generate pool for validation: {$error}
+pool-timeout = Lookup process gave no answer within {$seconds} seconds for input '{$input}'
+pool-process-ended = Lookup process ended before answering input '{$input}'
+
+# Native backend messages
+native-failed-to-read = Failed to read FST file: {$file}
+native-failed-to-load = Failed to load transducer: {$file}
+native-hfstol-truncated = Transducer file is truncated or corrupt
+native-hfstol-bad-header = Invalid HFST header
+native-hfstol-wrong-type = Unsupported HFST transducer type '{$kind}' (expected an optimized-lookup transducer, HFST_OL or HFST_OLW)
+native-foma-bad-gzip = Could not decompress foma file: {$error}
+native-foma-bad-header = Not a foma network (missing ##foma-net header)
+native-foma-bad-line = Invalid line {$line} in foma network: {$text}
+native-att-bad-line = Invalid line {$line} in AT&T file: {$text}
+
+# Cache messages
+cache-no-dir = No cache directory found for this user, lookups will not be cached (use --cache-dir)
+cache-failed-to-hash = Failed to read FST file for the lookup cache: {$file}
+cache-failed-to-read = Ignoring unreadable lookup cache {$file}: {$error}
+cache-failed-to-write = Failed to write lookup cache {$file}: {$error}
+cache-missing-results = The backend returned fewer results than inputs
+fixture-failed-to-read = Failed to read lookup fixture: {$file}
+fixture-failed-to-write = Failed to write lookup fixture: {$file}
+fixture-missing-inputs = {$count} inputs are missing from the fixture {$file}, record it again: {$inputs}
 
 # Spec error messages
 spec-failed-to-read = Failed to read: {$file}
 spec-yaml-error = YAML error in: {$file}
 spec-incomplete-config = Incomplete or unclear Config in {$file}
 spec-missing-config = Config missing
-spec-missing-gen = Found neither HFST.Gen nor Foma.Gen in Config
+spec-missing-gen = Found none of HFST.Gen, Foma.Gen or Apertium.Gen in Config
 spec-missing-hfst = Config.hfst missing
 spec-missing-hfst-gen = Config.hfst.Gen missing
 spec-missing-foma = Config.foma missing
 spec-missing-foma-gen = Config.foma.Gen missing
+spec-invalid-timeout = Timeout must be a positive number of seconds, got {$value}
+spec-missing-apertium = Config.apertium missing
+spec-missing-apertium-gen = Config.apertium.Gen missing
+spec-missing-http = Config.http missing
+spec-missing-http-url = Config.http.Url missing
+spec-missing-command = Config.command missing
+spec-missing-command-gen = Config.command.Gen missing
+spec-missing-command-run = Config.command.Run missing
+spec-missing-fst-gen = The transducer {$fst} in Fsts has no Gen
+spec-fsts-http = Fsts cannot be used with a morphology service (Http)
+spec-fsts-missing = Tests for particular transducers and Relations need transducers named in Fsts
+spec-unknown-fst = Unknown transducer {$fst}, Fsts names {$known}
+spec-invalid-relation = Invalid relation {$relation}, write it as e.g. `desc ⊇ norm` or `desc >= norm`
+project-failed-to-read = Failed to read project config: {$file}
+project-yaml-error = YAML error in project config: {$file}
+project-invalid-value = Invalid value for {$option} in project config: {$value}
+spec-unknown-variable = Unknown variable ${{$name}}: it is not yamldir, builddir, srcdir or fsttype, nor set in the environment
+spec-unclosed-variable = A variable is not closed with } in {$value}
+spec-no-fsttype = ${fsttype} is used, but the file name gives no transducer type (as in nouns_gt-norm.yaml)
+spec-json-error = JSON error in: {$file}
+spec-toml-error = TOML error in: {$file}
+spec-table-error = Error in the test table: {$file}
+table-missing-column = The header has no {$column} column; name it in Table in the Config
+table-incomplete-row = Row on line {$line} has no {$column}
+table-wide-column = The {$column} column only belongs in the long layout, where each row gives one lexical key
+spec-paradigm-error = Error in Paradigms in: {$file}
+spec-paradigm-lemma-forms = Paradigm {$name} needs Lemma with Forms, or Lemmas
+spec-paradigm-length = Paradigm {$name}: {$lemma} has {$forms} forms for {$tags} tag strings
+spec-paradigm-unknown-tag = Paradigm {$name}: {$lemma} has a form for {$tags}, which is not in its Tags
+warn-lexc-duplicate = Duplicate lexc test on line {$line} of {$file}, tested once: {$test}
+spec-lexc-contradiction = The lexc test on line {$line} of {$file} contradicts line {$first}, which gives the same pair the other way, with !!€ or !!$: {$test}
+spec-hfst-tool-guessed = {$file} is {$format}, not an HFST transducer, so which HFST lookup tool reads it is only a guess
 
 # Debug messages
 debug-batch-lookup = Running batch lookup with {$count} inputs using FST: {$fst}
 debug-batch-completed = Batch lookup completed: {$inputs} inputs processed, {$results} total results
 debug-pool-batch = Pool process batch: processing {$count} inputs
 debug-pool-completed = Pool batch completed: {$inputs} inputs processed, {$results} total results
+debug-native-load = Loading transducer into memory: {$fst}
+debug-pool-retry = Lookup process failed on a chunk of {$count} inputs, retrying with a new process: {$error}
+debug-pool-bisect = Splitting a failing chunk of {$count} inputs to find the input that breaks the lookup process
+debug-cache-open = Lookup cache {$file} has {$count} entries
+debug-cache-hits = Lookup cache answered {$hits} of {$total} inputs
+debug-http-request = Sending {$count} inputs to {$endpoint}
+debug-fixture-open = Replaying {$count} lookups from {$file}
+debug-project-config = Using project config {$file}
 
 # Engine messages
 engine-not-processed = Not processed
 engine-batch-analyze-error = Batch analyze error: {$error}
 engine-batch-generate-error = Batch generate error: {$error}
+engine-lookup-error = Lookup failed for this input: {$error}
+warn-fsts-swapped = Generator and analyser look swapped: the analyser {$analyser} answers the lexical form '{$input}', the generator {$generator} answers none of the tested ones
+engine-wrong-result-count = The backend answered {$got} of {$expected} inputs
 
 # I18N system messages
 i18n-init-failed = Failed to initialize localizer
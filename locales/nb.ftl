@@ -1,6 +1,6 @@
 # CLI help text
 cli-about = Morfologisk testkjører (overflate/analyse og leksikalsk/generering)
-cli-backend = Velg fst-format/seksjon (hfst eller foma) [alias: -S/--section]
+cli-backend = Velg fst-format/seksjon (hfst, foma, apertium, http eller command) [alias: -S/--section]
 cli-generator = Overstyr generator-FST (.hfstol for HFST, .foma for Foma) [alias: --gen]
 cli-analyser = Overstyr analysator-FST (.hfstol for HFST, .foma for Foma) [alias: --morph, --analyzer]
 cli-silent = Stille modus: ingen utskrift, og demp stderr fra lookup
@@ -16,6 +16,14 @@ cli-hide-passes = Skjul godkjente (PASS), vis bare feil (FAIL)
 cli-test = Kjør bare angitt test: nummer 1..N, tittel „Gruppe (Lexical/Generation|Surface/Analysis)" eller bare gruppenavnet fra YAML. Spesialtestnavn: 0, 'null' eller 'liste' lister alle tilgjengelige tester.
 cli-output = Rapportformat: normal | compact | terse | final (standard: normal)
 cli-serial = Bruk seriell kjøring i stedet for parallell prosessering (parallell er standard)
+cli-native = Bruk den innebygde leseren for transdusere (.hfstol, .foma, .att) i stedet for et eksternt lookup-verktøy
+cli-timeout = Sekunder å vente på oppslaget av én input før den rapporteres som tidsavbrutt [standard: 30, eller Timeout i YAML-Config]
+cli-cache = Ta vare på oppslagsresultater på disk og bruk dem igjen så lenge transduseren og lookup-verktøyet er uendret
+cli-cache-dir = Katalog for mellomlagrede oppslagsresultater (gir --cache) [standard: brukerens cache-katalog]
+cli-endpoint = Kjør alle testsett mot en morfologitjeneste på URL i stedet for transduserne deres
+cli-record = Ta opp alle oppslag i en fixture-FILE, for avspilling uten transdusere
+cli-replay = Svar på oppslag fra en fixture-FILE tatt opp med --record
+cli-normalize = Fjern flaggdiakritika som @P.x.y@ fra utdataene før de sammenlignes, slik Flags: true under Normalize i YAML-Config gjør
 
 # Directions and modes  
 direction-generate = Leksikalsk/Generering
@@ -40,6 +48,7 @@ info-starting-tests = Begynner å teste ({$count} tester, modus: {$mode}) (batch
 info-starting-parallel = Suite: {$name} (parallellprosessering)...
 info-finished = Ferdig: godkjente {$passed}, feila {$failed}. Skriver rapport...
 info-all-finished = Alle testkjøringer ferdige. Totalt: {$total}, Godkjente: {$passed}, Feila: {$failed}
+info-native-lookup = innebygd leser
 
 # Test listing
 available-tests = Tilgjengelige tester:
@@ -59,19 +68,42 @@ report-test-header = Test {$index}: {$group} ({$direction})
 report-test-summary = Test {$index} - Bestått: {$passes}, Feila: {$fails}, Totalt: {$total}
 report-total-summary = Totalt bestått: {$passes}, Totalt feila: {$fails}, Totalt: {$total}
 report-final-counts = {$passes}/{$fails}/{$total}
+report-weight-best = {$output} (lavest vekt)
+report-weight-below = {$output} (vekt under {$limit})
+report-raw = Rå
 
 # Backend error messages
 backend-failed-to-start = Klarte ikke å starte '{$cmd}'
 backend-missing-stdin = Mangler stdin
-backend-process-failed = Lookup-prosess feilet med status {$status}
-backend-timeout = Lookup tidsavbrudd etter {$seconds} s
-backend-process-failed-stderr = Lookup-prosess feilet med status {$status}
+backend-process-failed = Lookup-prosessen avsluttet med status {$status} etter å ha svart på all input
 Stderr: {$stderr}
 backend-analyzer-not-set = Analysator-FST ikke satt
 backend-generator-not-set = Generator-FST ikke satt
 backend-command-not-executable = Lookup-kommando '{$cmd}' kunne ikke kjøres: {$error}
 backend-command-not-found = Lookup-kommando '{$cmd}' finnes ikke eller kan ikke kjøres. Sjekk at den er installert og i PATH.
 backend-command-error = Kan ikke kjøre lookup-kommando '{$cmd}': {$error}
+backend-missing-stdout = Mangler stdout
+backend-input-timeout = Intet svar innen {$seconds} s for input '{$input}'
+backend-process-ended = Oppslagsprosessen avsluttet mens den svarte på input '{$input}' (den ble startet på nytt for resten)
+http-request-failed = Forespørsel til {$endpoint} feilet: {$error}
+http-wrong-result-count = Tjenesten svarte med {$got} resultater for {$expected} inndata
+http-unreachable = Morfologitjenesten på {$endpoint} kan ikke brukes: {$error}
+command-invalid-pattern = Ugyldig utdatamønster: {$pattern}
+command-pattern-without-output = Utdatamønsteret har ingen 'output'-gruppe: {$pattern}
+command-missing-pattern = Regex-tolkeren for utdata trenger et Pattern
+command-unknown-parser = Ukjent utdatatolker '{$name}' (hfst, apertium, lines eller regex)
+format-fst-not-found = Fant ikke transduseren: {$file}
+format-fst-unreadable = Kan ikke lese transduseren: {$file}
+format-fst-wrong-format = {$file} er {$format}, som '{$cmd}' ikke kan lese
+format-hfstol = en HFST optimized-lookup-transduser
+format-hfst = en HFST-transduser av typen {$kind}
+format-foma = et foma-nettverk
+format-att = en AT&T-teksttransduser
+format-lttoolbox = en lttoolbox-transduser
+format-unknown = av ukjent format
+format-fst-unknown = Formatet til {$file} er ukjent: fila har ingen HFST-header og er verken et foma-nettverk eller AT&T-tekst
+backend-gave-up = Oppslagsprosessen feilet før den svarte på noen input, så den ble ikke startet på nytt: {$error}
+backend-not-looked-up = Ikke slått opp, fordi en ny oppslagsprosess feilet før den svarte på noen input: {$error}
 
 # Pool error messages
 pool-io-error = IO-feil ved lesing fra prosess: {$error}
@@ -85,28 +117,94 @@ pool-get-analyze-failed = Klarte ikke å hente prosess fra analysepulje: {$error
 pool-get-generate-failed = Klarte ikke å hente prosess fra genereringspulje: {$error}
 pool-validate-analyze-failed = Klarte ikke å hente prosess fra analysepulje for validering: {$error}
 pool-validate-generate-failed = Klarte ikke å hente prosess fra genereringspulje for validering: {$error}
+pool-timeout = Oppslagsprosessen svarte ikke innen {$seconds} sekunder for input '{$input}'
+pool-process-ended = Oppslagsprosessen avsluttet før den svarte på input '{$input}'
+
+# Native backend messages
+native-failed-to-read = Klarte ikke å lese FST-fil: {$file}
+native-failed-to-load = Klarte ikke å laste transduser: {$file}
+native-hfstol-truncated = Transduserfila er avkortet eller skadet
+native-hfstol-bad-header = Ugyldig HFST-hode
+native-hfstol-wrong-type = HFST-transdusertypen '{$kind}' støttes ikke (forventet en optimized-lookup-transduser, HFST_OL eller HFST_OLW)
+native-foma-bad-gzip = Kunne ikke pakke ut foma-fil: {$error}
+native-foma-bad-header = Ikke et foma-nettverk (mangler ##foma-net-hode)
+native-foma-bad-line = Ugyldig linje {$line} i foma-nettverk: {$text}
+native-att-bad-line = Ugyldig linje {$line} i AT&T-fil: {$text}
+
+# Cache messages
+cache-no-dir = Fant ingen cache-katalog for denne brukeren, oppslag blir ikke mellomlagret (bruk --cache-dir)
+cache-failed-to-hash = Klarte ikke å lese FST-fil for oppslagscachen: {$file}
+cache-failed-to-read = Ser bort fra uleselig oppslagscache {$file}: {$error}
+cache-failed-to-write = Klarte ikke å skrive oppslagscache {$file}: {$error}
+cache-missing-results = Backenden returnerte færre resultater enn input
+fixture-failed-to-read = Kunne ikke lese oppslagsfixture: {$file}
+fixture-failed-to-write = Kunne ikke skrive oppslagsfixture: {$file}
+fixture-missing-inputs = {$count} inndata mangler i fixturen {$file}, ta den opp på nytt: {$inputs}
 
 # Spec error messages
 spec-failed-to-read = Klarte ikke å lese: {$file}
 spec-yaml-error = YAML-feil i: {$file}
 spec-incomplete-config = Mangelfull eller utydelig Config i {$file}
 spec-missing-config = Config mangler
-spec-missing-gen = Fant verken HFST.Gen eller Foma.Gen i Config
+spec-missing-gen = Fant verken HFST.Gen, Foma.Gen eller Apertium.Gen i Config
 spec-missing-hfst = Config.hfst mangler
 spec-missing-hfst-gen = Config.hfst.Gen mangler
 spec-missing-foma = Config.foma mangler
 spec-missing-foma-gen = Config.foma.Gen mangler
+spec-invalid-timeout = Timeout må være et positivt antall sekunder, fikk {$value}
+spec-missing-apertium = Config.apertium mangler
+spec-missing-apertium-gen = Config.apertium.Gen mangler
+spec-missing-http = Config.http mangler
+spec-missing-http-url = Config.http.Url mangler
+spec-missing-command = Config.command mangler
+spec-missing-command-gen = Config.command.Gen mangler
+spec-missing-command-run = Config.command.Run mangler
+spec-missing-fst-gen = Transduseren {$fst} i Fsts mangler Gen
+spec-fsts-http = Fsts kan ikke brukes med en morfologitjeneste (Http)
+spec-fsts-missing = Tester for enkelte transdusere og Relations krever transdusere navngitt i Fsts
+spec-unknown-fst = Ukjent transduser {$fst}, Fsts navngir {$known}
+spec-invalid-relation = Ugyldig relasjon {$relation}, skriv den f.eks. som `desc ⊇ norm` eller `desc >= norm`
+project-failed-to-read = Kunne ikke lese prosjektoppsett: {$file}
+project-yaml-error = YAML-feil i prosjektoppsett: {$file}
+project-invalid-value = Ugyldig verdi for {$option} i prosjektoppsett: {$value}
+spec-unknown-variable = Ukjent variabel ${{$name}}: den er verken yamldir, builddir, srcdir eller fsttype, eller satt i miljøet
+spec-unclosed-variable = En variabel er ikke lukket med } i {$value}
+spec-no-fsttype = ${fsttype} er brukt, men filnavnet gir ingen transdusertype (som i nouns_gt-norm.yaml)
+spec-json-error = JSON-feil i: {$file}
+spec-toml-error = TOML-feil i: {$file}
+spec-table-error = Feil i testtabellen: {$file}
+table-missing-column = Overskriftsraden har ingen {$column}-kolonne; gi navnet i Table i Config
+table-incomplete-row = Raden på linje {$line} mangler {$column}
+table-wide-column = Kolonnen {$column} hører bare hjemme i langt format, der hver rad gir én leksikalsk nøkkel
+spec-paradigm-error = Feil i Paradigms i: {$file}
+spec-paradigm-lemma-forms = Paradigmet {$name} trenger Lemma med Forms, eller Lemmas
+spec-paradigm-length = Paradigmet {$name}: {$lemma} har {$forms} former for {$tags} taggstrenger
+spec-paradigm-unknown-tag = Paradigmet {$name}: {$lemma} har en form for {$tags}, som ikke er blant Tags
+warn-lexc-duplicate = Duplisert lexc-test på linje {$line} i {$file}, testes én gang: {$test}
+spec-lexc-contradiction = Lexc-testen på linje {$line} i {$file} motsier linje {$first}, som gir det samme paret omvendt, med !!€ eller !!$: {$test}
+spec-hfst-tool-guessed = {$file} er {$format}, ikke en HFST-transduser, så hvilket HFST-oppslagsverktøy som leser den, er bare gjettet
 
 # Debug messages
 debug-batch-lookup = Kjører batch-oppslag med {$count} inndata med FST: {$fst}
 debug-batch-completed = Batch-oppslag fullført: {$inputs} inndata prosessert, {$results} totale resultater
 debug-pool-batch = Pulje-prosess batch: prosesserer {$count} inndata
 debug-pool-completed = Pulje-batch fullført: {$inputs} inndata prosessert, {$results} totale resultater
+debug-native-load = Laster transduser inn i minnet: {$fst}
+debug-pool-retry = Oppslagsprosessen feilet på en bit med {$count} input, prøver igjen med en ny prosess: {$error}
+debug-pool-bisect = Deler en feilende bit med {$count} input for å finne inputen som får oppslagsprosessen til å feile
+debug-cache-open = Oppslagscachen {$file} har {$count} oppføringer
+debug-cache-hits = Oppslagscachen svarte på {$hits} av {$total} input
+debug-http-request = Sender {$count} inndata til {$endpoint}
+debug-fixture-open = Spiller av {$count} oppslag fra {$file}
+debug-project-config = Bruker prosjektoppsett {$file}
 
 # Engine messages
 engine-not-processed = Ikke prosessert
 engine-batch-analyze-error = Batch-analysefeil: {$error}
 engine-batch-generate-error = Batch-genereringsfeil: {$error}
+engine-lookup-error = Oppslaget feilet for denne inputen: {$error}
+warn-fsts-swapped = Generator og analysator ser ut til å være byttet om: analysatoren {$analyser} svarer på den leksikalske formen '{$input}', generatoren {$generator} svarer ikke på noen av de testede
+engine-wrong-result-count = Backenden svarte på {$got} av {$expected} input
 
 # I18N system messages
 i18n-init-failed = Klarte ikke å initialisere lokaliseringssystemet
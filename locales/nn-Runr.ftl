@@ -1,6 +1,6 @@
 # CLI help text
 cli-about = ᛘᚮᚱᚠᚮᛚᚮᚵᛁᛌᚴ ᛐᛂᛌᛐᚴᚯᛦᚱᛆᚱ (ᚮᚠᛂᚱᚠᛚᛆᛐᛂ / ᛆᚿᛆᛚᛦᛌᛂ ᚮᚵ ᛚᛂᚴᛌᛁᚴᛆᛚᛌᚴ / ᚵᛂᚿᛂᚱᛂᚱᛁᚿᚵ)
-cli-backend = ᚠᛂᛚ ᚠᛌᛐ-ᚠᚮᚱᛘᛆᛐ/ᛌᛂᚴᛌᛁᚮᚿ (ᚼᚠᛌᛐ ᛂᛚᛚᛂᚱ ᚠᚮᛘᛆ) [alias: -S/--section]
+cli-backend = ᚠᛂᛚ ᚠᛌᛐ-ᚠᚮᚱᛘᛆᛐ/ᛌᛂᚴᛌᛁᚮᚿ (ᚼᚠᛌᛐ, ᚠᚮᛘᛆ, apertium, http ᛂᛚᛚᛂᚱ command) [alias: -S/--section]
 cli-generator = ᚮᚠᛁᚱᛋᛏᛦᚱ ᚵᛁᚿᛁᚱᛆᛏᚮᚱ-ᚠᛌᛐ (.hfstol ᚠᚮᚱ HFST, .foma ᚠᚮᚱ Foma) [alias: --gen]
 cli-analyser = ᚮᚠᛁᚱᛋᛏᛦᚱ ᛆᚿᛆᛚᛦᛌᛂ-ᚠᛌᛐ (.hfstol ᚠᚮᚱ ᚼᚠᛌᛐ, .foma ᚠᚮᚱ ᚠᚮᛘᛆ) [alias: --morph, --analyzer]
 cli-silent = ᛌᛐᛁᛚᛚᛂ ᛘᚮᛑᚢᛌ: ᛁᚿᚵᛆ ᚢᛐᛌᚴᚱᛁᚠᛐ, ᚮᚵ ᛑᛂᛘᛔ stderr ᚠᚱᚭ lookup
@@ -16,6 +16,14 @@ cli-hide-passes = ᛋᚴᛌᚢᛚ ᚵᛌᛁᚿᚿᚮᛘᚵᚮᛏᛏᛁ (PASS), 
 cli-test = ᚴᚯᛦᚱ ᛒᛁᚱᚱᛁ ᛆᚿᚵᛁᛏᛏ ᛏᛁᛋᛏ: ᚿᚢᛘᛁᚱ 1..N, ᛏᛁᛏᛁᛚ „ᚵᚱᚢᛒᛒᛁ (ᛚᛁᚴᛋᛁᚴᛆᛚ/ᚵᛁᚿᛁᚱᛆᛏᛁᚮᚿ|ᚮᚠᛁᚱᚠᛚᛆᛏᛁ/ᛆᚿᛆᛚᛦᛋᛁ)" ᛁᛚᛚᛁᚱ ᛒᛁᚱᚱᛁ ᚵᚱᚢᛒᛒᛁᚿᛆᛘᚿᛁᛏ ᚠᚱᚮ YAML. ᛋᛒᛁᛋᛁᛆᛚ: 0, 'null' ᛁᛚᛚᛁᚱ 'ᛚᛁᛋᛏᛁ' ᛚᛁᛋᛏᛆᚱ ᛆᛚᛚᛁ ᛏᛁᛚᚵᛌᛁᚿᚵᛁᛚᛁᚵᛁ ᛏᛁᛋᛏᛆᚱ ᚮᚵ ᛆᚠᛋᛚᚢᛏᛏᛆᚱ.
 cli-output = ᚱᛆᛒᛒᚮᚱᛏᚠᚮᚱᛘᛆᛏ: compact | terse | final | normal (ᛋᛏᛆᚿᛏᛆᚱᛏ: normal)
 cli-serial = ᛒᚱᚢᚴ ᛋᛁᚱᛁᛁᛚᛚ ᚴᚯᛦᚱᛁᚿᚵ ᛁ ᛋᛏᛆᛏᛁᚿ ᚠᚮᚱ ᛒᛆᚱᛆᛚᛚᛁᛚᛚ ᛒᚱᚮᛋᛁᛋᛋᛁᚿᚵ (ᛋᛏᛆᚿᛏᛆᚱᛏᚠᛁᚱᛏᛁ ᛁᚱ ᛒᛆᚱᛆᛚᛚᛁᛚᛚ)
+cli-native = ᛒᚱᚢᚴ ᛏᛁᚿ ᛁᚿᚿᛁᛒᛦᚵᛏᛁ ᛚᛁᛋᛆᚱᛁᚿ ᚠᚮᚱ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛆᚱ (.hfstol, .foma, .att) ᛁ ᛋᛏᛆᛏᛁᚿ ᚠᚮᚱ ᛁᛁᛏ ᛁᚴᛋᛏᛁᚱᚿᛏ lookup-ᚠᛁᚱᚴᛏᚯᛦ
+cli-timeout = ᛋᛁᚴᚢᚿᛏ ᚮ ᚠᛁᚿᛏᛆ ᛒᚮ ᚮᛒᛒᛋᛚᛆᚵᛁᛏ ᛆᚠ ᛁᛁᚿ ᛁᚿᛒᚢᛏ ᚠᚯᚱ ᚼᛆᚿ ᚠᛁᚱᛏ ᚱᛆᛒᛒᚮᚱᛏᛁᚱᛏ ᛋᚮᛘ ᛏᛁᛏᛋᛆᚠᛒᚱᚮᛏᛁᚿ [ᛋᛏᛆᚿᛏᛆᚱᛏ: 30, ᛁᛚᛚᛁᚱ ᛏᛁᛘᛁᚮᚢᛏ ᛁ YAML-ᛋᚮᚿᚠᛁᚵ]
+cli-cache = ᛏᛆ ᚠᛆᚱᛁ ᛒᚮ ᚮᛒᛒᛋᛚᛆᚵᛋᚱᛁᛋᚢᛚᛏᛆᛏ ᛒᚮ ᛏᛁᛋᚴ ᚮᚵ ᛒᚱᚢᚴ ᛏᛁᛁ ᚮᛘ ᛆᛏᛏ ᛋᚮ ᛚᛁᚿᚵᛁ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛁᚿ ᚮᚵ lookup-ᚠᛁᚱᚴᛏᚯᛦᛁᛏ ᛁᚱ ᚢᛁᚿᛏᚱᛆ
+cli-cache-dir = ᚴᛆᛏᛆᛚᚮᚵ ᚠᚮᚱ ᛘᛁᛚᛚᚮᛘᛚᛆᚵᚱᛆ ᚮᛒᛒᛋᛚᛆᚵᛋᚱᛁᛋᚢᛚᛏᛆᛏ (ᚵᛌᛁᚠ --cache) [ᛋᛏᛆᚿᛏᛆᚱᛏ: ᛋᛆᛋᚼᛁ-ᚴᛆᛏᛆᛚᚮᚵᛁᚿ ᛏᛁᛚ ᛒᚱᚢᚴᛆᚱᛁᚿ]
+cli-endpoint = ᚴᚯᛦᚱ ᛆᛚᛚᛁ ᛏᛁᛋᛏᛋᛁᛏᛏ ᛘᚮᛏ ᛁᛁ ᛘᚮᚱᚠᚮᛚᚮᚵᛁᛏᛁᚿᛁᛋᛏᛁ ᛒᚮ URL ᛁ ᛋᛏᛆᛏᛁᚿ ᚠᚮᚱ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛆᚿᛁ ᛏᛁᛁᚱᛆ
+cli-record = ᛏᛆ ᚮᛒᛒ ᛆᛚᛚᛁ ᚮᛒᛒᛋᛚᛆᚵ ᛁ ᛁᛁ ᚠᛁᛋᛏᚢᚱᛁ-FILE, ᚠᚮᚱ ᛆᚠᛋᛒᛁᛚᛁᚿᚵ ᚢᛏᛆᚿ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛆᚱ
+cli-replay = ᛋᚠᛆᚱ ᛒᚮ ᚮᛒᛒᛋᛚᛆᚵ ᚠᚱᚮ ᛁᛁ ᚠᛁᛋᛏᚢᚱᛁ-FILE ᛏᛁᚴᛁᚿ ᚮᛒᛒ ᛘᛁᛏ --record
+cli-normalize = ᚠᛌᛁᚱᚿ ᚠᛚᛆᚵᚵᛏᛁᛆᚴᚱᛁᛏᛁᚴᛆ ᛋᚮᛘ @P.x.y@ ᚠᚱᚮ ᚢᛏᛏᛆᛏᛆᛆ ᚠᚯᚱ ᛏᛁᛁ ᚠᛁᚱᛏ ᛋᛆᛘᛆᚿᛚᛁᚴᚿᛆ, ᛋᛚᛁᚴ Flags: true ᚢᚿᛏᛁᚱ Normalize ᛁ YAML-ᛋᚮᚿᚠᛁᚵ ᚵᛌᛁᚱ
 
 # Directions and modes  
 direction-generate = ᛚᛁᚴᛋᛁᚴᛆᛚᛋᚴ/ᚵᛁᚿᛁᚱᛁᚱᛁᚿᚵ
@@ -40,6 +48,7 @@ info-starting-tests = ᛋᛏᛆᚱᛏᛆᚱ ᛏᛁᛋᛏᛁᚿᚵ ({$count} ᛏ
 info-starting-parallel = ᛋᚢᛁᛏᛁ: {$name} (ᛒᛆᚱᛆᛚᛚᛁᛚᛚ ᛒᚱᚮᛋᛁᛋᛋᛁᚿᚵ)...
 info-finished = ᚠᛁᚱᛏᛁᚵ: ᛒᛆᛋᛋᛁᛏ {$passed}, ᚠᛖᛁᛚᛆ {$failed}. ᛋᚴᚱᛁᚠ ᚱᛆᛒᛒᚮᚱᛏ...
 info-all-finished = ᛆᛚᛚᛁ ᛏᛁᛋᛏᚴᚯᛦᚱᛁᚿᚵᛆᚱ ᚠᛁᚱᛏᛁᚵᛁ. ᛏᚮᛏᛆᛚ: {$total}, ᛒᛆᛋᛋᛁᛏ: {$passed}, ᚠᛖᛁᛚᛆ: {$failed}
+info-native-lookup = ᛁᚿᚿᛁᛒᛦᚵᛏ ᛚᛁᛋᛆᚱ
 
 # Test listing
 available-tests = ᛏᛁᛚᚵᛌᛁᚿᚵᛁᛚᛁᚵᛁ ᛏᛁᛋᛏᛆᚱ:
@@ -59,19 +68,42 @@ report-test-header = ᛏᛁᛋᛏ {$index}: {$group} ({$direction})
 report-test-summary = ᛏᛁᛋᛏ {$index} - ᛒᛁᛋᛏᚮᛏᛏ: {$passes}, ᚠᛖᛁᛚᛆ: {$fails}, ᛏᚮᛏᛆᛚᛏ: {$total}
 report-total-summary = ᛏᚮᛏᛆᛚᛏ ᛒᛁᛋᛏᚮᛏᛏ: {$passes}, ᛏᚮᛏᛆᛚᛏ ᚠᛖᛁᛚᛆ: {$fails}, ᛏᚮᛏᛆᛚᛏ: {$total}
 report-final-counts = {$passes}/{$fails}/{$total}
+report-weight-best = {$output} (ᛚᚮᚵᛆᛋᛏ ᚠᛁᚴᛏ)
+report-weight-below = {$output} (ᚠᛁᚴᛏ ᚢᚿᛏᛁᚱ {$limit})
+report-raw = ᚱᚮ
 
 # Backend error messages
 backend-failed-to-start = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᛋᛏᛆᚱᛏᛆ '{$cmd}'
 backend-missing-stdin = ᛘᛆᚿᚵᛚᛆᚱ stdin
-backend-process-failed = ᛚᚮᚮᚴᚢᛒ-ᛒᚱᚮᛋᛁᛋᛋ ᚠᛖᛁᛚᛆ ᛘᛁᛏ ᛋᛏᛆᛏᚢᛋ {$status}
-backend-timeout = ᛚᚮᚮᚴᚢᛒ ᛏᛁᛏᛋᛆᚠᛒᚱᚮᛏ ᛁᛏᛏᛁᚱ {$seconds} s
-backend-process-failed-stderr = ᛚᚮᚮᚴᚢᛒ-ᛒᚱᚮᛋᛁᛋᛋ ᚠᛖᛁᛚᛆ ᛘᛁᛏ ᛋᛏᛆᛏᚢᛋ {$status}
+backend-process-failed = ᛚᚮᚮᚴᚢᛒ-ᛒᚱᚮᛋᛁᛋᛋᛁᚿ ᛆᚠᛋᛚᚢᛏᛏᛆ ᛘᛁᛏ ᛋᛏᛆᛏᚢᛋ {$status} ᛁᛏᛏᛁᚱ ᚮ ᚼᛆ ᛋᚠᛆᚱᛏ ᛒᚮ ᛆᛚᛚ ᛁᚿᛒᚢᛏ
 Stderr: {$stderr}
 backend-analyzer-not-set = ᛆᚿᛆᛚᛦᛋᛁᚱ-FST ᛁᚴᚴᛁ ᛋᛁᛏᛏ
 backend-generator-not-set = ᚵᛁᚿᛁᚱᛆᛏᚮᚱ-FST ᛁᚴᚴᛁ ᛋᛁᛏᛏ
 backend-command-not-executable = ᛚᚮᚮᚴᚢᛒ-ᚴᚮᛘᛆᚿᛏᚮ '{$cmd}' ᚴᚢᚿᚿᛁ ᛁᚴᚴᛁ ᚴᚯᛦᚱᛆᛋᛏ: {$error}
 backend-command-not-found = ᛚᚮᚮᚴᚢᛒ-ᚴᚮᛘᛆᚿᛏᚮ '{$cmd}' ᚠᛁᚿᛋᛏ ᛁᚴᚴᛁ ᛁᛚᛚᛁᚱ ᚴᛆᚿ ᛁᚴᚴᛁ ᚴᚯᛦᚱᛆᛋᛏ. ᛋᛌᛁᚴ ᛆᛏ ᛏᛁᚿ ᛁᚱ ᛁᚿᛋᛏᛆᛚᛚᛁᚱᛏ ᚮᚵ ᛁ PATH.
 backend-command-error = ᚴᛆᚿ ᛁᚴᚴᛁ ᚴᚯᛦᚱᛁ ᛚᚮᚮᚴᚢᛒ-ᚴᚮᛘᛆᚿᛏᚮ '{$cmd}': {$error}
+backend-missing-stdout = ᛘᛆᚿᚵᛚᛆᚱ stdout
+backend-input-timeout = ᛁᚴᚴᛁ ᚿᚮᚴᚮ ᛋᚠᛆᚱ ᛁᚿᚿᛆᚿ {$seconds} ᛋ ᚠᚮᚱ ᛁᚿᛒᚢᛏ '{$input}'
+backend-process-ended = ᚮᛒᛒᛋᛚᛆᚵᛋᛒᚱᚮᛋᛁᛋᛋᛁᚿ ᛆᚠᛋᛚᚢᛏᛏᛆ ᛘᛁᛏᛆᚿ ᚼᛆᚿ ᛋᚠᛆᚱᛏᛁ ᛒᚮ ᛁᚿᛒᚢᛏ '{$input}' (ᚼᛆᚿ ᚠᛆᚱᛏ ᛋᛏᛆᚱᛏᛆ ᛒᚮ ᚿᛦᛏᛏ ᚠᚮᚱ ᚱᛁᛋᛏᛁᚿ)
+http-request-failed = ᚠᚯᚱᛁᛋᛒᚢᚱᚿᛆᛏ ᛏᛁᛚ {$endpoint} ᚠᛁᛁᛚᛆ: {$error}
+http-wrong-result-count = ᛏᛁᚿᛁᛋᛏᛆ ᛋᚠᛆᚱᛏᛁ ᛘᛁᛏ {$got} ᚱᛁᛋᚢᛚᛏᛆᛏ ᚠᚮᚱ {$expected} ᛁᚿᚿᛏᛆᛏᛆ
+http-unreachable = ᛘᚮᚱᚠᚮᛚᚮᚵᛁᛏᛁᚿᛁᛋᛏᛆ ᛒᚮ {$endpoint} ᚴᛆᚿ ᛁᚴᚴᛁ ᛒᚱᚢᚴᛆᛋᛏ: {$error}
+command-invalid-pattern = ᚢᚵᛦᛚᛏᛁᚵ ᚢᛏᛏᛆᛏᛆᛘᚯᚿᛋᛏᛁᚱ: {$pattern}
+command-pattern-without-output = ᚢᛏᛏᛆᛏᛆᛘᚯᚿᛋᛏᛁᚱᛁᛏ ᚼᛆᚱ ᛁᚿᚵᛁᚿ 'output'-ᚵᚱᚢᛒᛒᛁ: {$pattern}
+command-missing-pattern = ᚱᛁᚵᛁᛋ-ᛏᚮᛚᚴᛆᚱᛁᚿ ᚠᚮᚱ ᚢᛏᛏᛆᛏᛆ ᛏᚱᛁᚿᚵ ᛁᛁᛏ Pattern
+command-unknown-parser = ᚢᚴᛌᛁᚿᛏ ᚢᛏᛏᛆᛏᛆᛏᚮᛚᚴᛆᚱ '{$name}' (hfst, apertium, lines ᛁᛚᛚᛁᚱ regex)
+format-fst-not-found = ᚠᛆᚿᚿ ᛁᚴᚴᛁ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛁᚿ: {$file}
+format-fst-unreadable = ᚴᛆᚿ ᛁᚴᚴᛁ ᛚᛁᛋᛁ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛁᚿ: {$file}
+format-fst-wrong-format = {$file} ᛁᚱ {$format}, ᛋᚮᛘ '{$cmd}' ᛁᚴᚴᛁ ᚴᛆᚿ ᛚᛁᛋᛁ
+format-hfstol = ᛁᛁᚿ HFST optimized-lookup-ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ
+format-hfst = ᛁᛁᚿ HFST-ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ ᛆᚠ ᛏᛦᛒᛁᚿ {$kind}
+format-foma = ᛁᛁᛏ foma-ᚿᛁᛏᛏᚠᛁᚱᚴ
+format-att = ᛁᛁᚿ AT&T-ᛏᛁᚴᛋᛏᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ
+format-lttoolbox = ᛁᛁᚿ lttoolbox-ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ
+format-unknown = ᛆᚠ ᚢᚴᛌᛁᚿᛏ ᚠᚮᚱᛘᛆᛏ
+format-fst-unknown = ᚠᚮᚱᛘᛆᛏᛁᛏ ᛏᛁᛚ {$file} ᛁᚱ ᚢᚴᛌᛁᚿᛏ: ᚠᛁᛚᛆ ᚼᛆᚱ ᛁᚿᚵᛁᚿ HFST-ᚼᛁᛆᛏᛁᚱ ᚮᚵ ᛁᚱ ᚴᚮᚱᚴᛌᛁ ᛁᛁᛏ foma-ᚿᛁᛏᛏᚠᛁᚱᚴ ᛁᛚᛚᛁᚱ AT&T-ᛏᛁᚴᛋᛏ
+backend-gave-up = ᚮᛒᛒᛋᛚᛆᚵᛋᛒᚱᚮᛋᛁᛋᛋᛁᚿ ᚠᛖᛁᛚᛆ ᚠᚯᚱ ᚼᛆᚿ ᛋᚠᛆᚱᛏᛁ ᛒᚮ ᚿᚮᚴᛆ ᛁᚿᛒᚢᛏ, ᛋᚮ ᚼᛆᚿ ᚠᛆᚱᛏ ᛁᚴᚴᛁ ᛋᛏᛆᚱᛏᛆ ᛒᚮ ᚿᛦᛏᛏ: {$error}
+backend-not-looked-up = ᛁᚴᚴᛁ ᛋᛚᚮᛏᛏ ᚮᛒᛒ, ᚠᚮᚱᛏᛁ ᛁᛁᚿ ᚿᛦ ᚮᛒᛒᛋᛚᛆᚵᛋᛒᚱᚮᛋᛁᛋᛋ ᚠᛖᛁᛚᛆ ᚠᚯᚱ ᚼᛆᚿ ᛋᚠᛆᚱᛏᛁ ᛒᚮ ᚿᚮᚴᛆ ᛁᚿᛒᚢᛏ: {$error}
 
 # Pool error messages
 pool-io-error = IO-ᚠᛖᛁᛚ ᚠᛁᛏ ᛚᛁᛋᛁᚿᚵ ᚠᚱᚮ ᛒᚱᚮᛋᛁᛋᛋ: {$error}
@@ -85,28 +117,94 @@ pool-get-analyze-failed = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᚼᛁᚿᛏᛆ 
 pool-get-generate-failed = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᚼᛁᚿᛏᛆ ᛒᚱᚮᛋᛁᛋᛋ ᚠᚱᚮ ᚵᛁᚿᛁᚱᛁᚱᛁᚿᚵᛋᛒᚢᛚᛌᛁ: {$error}
 pool-validate-analyze-failed = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᚼᛁᚿᛏᛆ ᛒᚱᚮᛋᛁᛋᛋ ᚠᚱᚮ ᛆᚿᛆᛚᛦᛋᛁᛒᚢᛚᛌᛁ ᚠᚮᚱ ᚠᛆᛚᛁᛏᛆᛋᛌᚮᚿ: {$error}
 pool-validate-generate-failed = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᚼᛁᚿᛏᛆ ᛒᚱᚮᛋᛁᛋᛋ ᚠᚱᚮ ᚵᛁᚿᛁᚱᛁᚱᛁᚿᚵᛋᛒᚢᛚᛌᛁ ᚠᚮᚱ ᚠᛆᛚᛁᛏᛆᛋᛌᚮᚿ: {$error}
+pool-timeout = ᚮᛒᛒᛋᛚᛆᚵᛋᛒᚱᚮᛋᛁᛋᛋᛁᚿ ᛋᚠᛆᚱᛏᛁ ᛁᚴᚴᛁ ᛁᚿᚿᛆᚿ {$seconds} ᛋᛁᚴᚢᚿᛏ ᚠᚮᚱ ᛁᚿᛒᚢᛏ '{$input}'
+pool-process-ended = ᚮᛒᛒᛋᛚᛆᚵᛋᛒᚱᚮᛋᛁᛋᛋᛁᚿ ᛆᚠᛋᛚᚢᛏᛏᛆ ᚠᚯᚱ ᚼᛆᚿ ᛋᚠᛆᚱᛏᛁ ᛒᚮ ᛁᚿᛒᚢᛏ '{$input}'
+
+# Native backend messages
+native-failed-to-read = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᛚᛁᛋᛆ FST-ᚠᛁᛚ: {$file}
+native-failed-to-load = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᛚᛆᛋᛏᛆ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ: {$file}
+native-hfstol-truncated = ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᚠᛁᛚᛆ ᛁᚱ ᛆᚠᚴᚮᚱᛏᛆ ᛁᛚᛚᛁᚱ ᛋᚴᛆᛏᛏ
+native-hfstol-bad-header = ᚢᚵᛦᛚᛏᛁᚵ HFST-ᚼᚮᚠᚢᛏ
+native-hfstol-wrong-type = HFST-ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛏᛦᛒᛁᚿ '{$kind}' ᛁᚱ ᛁᚴᚴᛁ ᛋᛏᚯᛏᛏᛆ (ᚠᛁᚿᛏᛆ ᛁᛁᚿ optimized-lookup-ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ, HFST_OL ᛁᛚᛚᛁᚱ HFST_OLW)
+native-foma-bad-gzip = ᚴᚢᚿᚿᛁ ᛁᚴᚴᛁ ᛒᛆᚴᚴᛁ ᚢᛏ foma-ᚠᛁᛚ: {$error}
+native-foma-bad-header = ᛁᚴᚴᛁ ᛁᛁᛏ foma-ᚿᛁᛏᛏᚠᛁᚱᚴ (ᛘᛆᚿᚵᛚᛆᚱ ##foma-net-hovud)
+native-foma-bad-line = ᚢᚵᛦᛚᛏᛁᚵ ᛚᛁᚿᛌᛁ {$line} ᛁ foma-ᚿᛁᛏᛏᚠᛁᚱᚴ: {$text}
+native-att-bad-line = ᚢᚵᛦᛚᛏᛁᚵ ᛚᛁᚿᛌᛁ {$line} ᛁ AT&T-ᚠᛁᛚ: {$text}
+
+# Cache messages
+cache-no-dir = ᚠᛆᚿᚿ ᛁᚿᚵᛁᚿ ᛋᛆᛋᚼᛁ-ᚴᛆᛏᛆᛚᚮᚵ ᚠᚮᚱ ᛏᛁᚿᚿᛁ ᛒᚱᚢᚴᛆᚱᛁᚿ, ᚮᛒᛒᛋᛚᛆᚵ ᚠᛁᚱᛏ ᛁᚴᚴᛁ ᛘᛁᛚᛚᚮᛘᛚᛆᚵᚱᛆ (ᛒᚱᚢᚴ --cache-dir)
+cache-failed-to-hash = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᛚᛁᛋᛆ FST-ᚠᛁᛚ ᚠᚮᚱ ᚮᛒᛒᛋᛚᛆᚵᛋᛋᛆᛋᚼᛁᚿ: {$file}
+cache-failed-to-read = ᛋᛁᚱ ᛒᚮᚱᛏ ᚠᚱᚮ ᚢᛚᛁᛋᛁᛚᛁᚵ ᚮᛒᛒᛋᛚᛆᚵᛋᛋᛆᛋᚼᛁ {$file}: {$error}
+cache-failed-to-write = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᛋᚴᚱᛁᚠᛆ ᚮᛒᛒᛋᛚᛆᚵᛋᛋᛆᛋᚼᛁ {$file}: {$error}
+cache-missing-results = ᛒᛆᛋᚴᛁᚿᛏᛁᚿ ᚱᛁᛏᚢᚱᚿᛁᚱᛏᛁ ᚠᛅᚱᚱᛁ ᚱᛁᛋᚢᛚᛏᛆᛏ ᛁᚿᚿ ᛁᚿᛒᚢᛏ
+fixture-failed-to-read = ᚴᚢᚿᚿᛁ ᛁᚴᚴᛁ ᛚᛁᛋᛁ ᚮᛒᛒᛋᛚᛆᚵᛋᚠᛁᛋᛏᚢᚱᛁ: {$file}
+fixture-failed-to-write = ᚴᚢᚿᚿᛁ ᛁᚴᚴᛁ ᛋᚴᚱᛁᚠᛁ ᚮᛒᛒᛋᛚᛆᚵᛋᚠᛁᛋᛏᚢᚱᛁ: {$file}
+fixture-missing-inputs = {$count} ᛁᚿᚿᛏᛆᛏᛆ ᛘᛆᚿᚵᛚᛆᚱ ᛁ ᚠᛁᛋᛏᚢᚱᛁᚿ {$file}, ᛏᛆ ᚼᛆᚿ ᚮᛒᛒ ᛒᚮ ᚿᛦᛏᛏ: {$inputs}
 
 # Spec error messages
 spec-failed-to-read = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᛚᛁᛋᛆ: {$file}
 spec-yaml-error = YAML-ᚠᛖᛁᛚ ᛁ: {$file}
 spec-incomplete-config = ᛘᛆᚿᚵᛁᛚᚠᚢᛚᛚ ᛁᛚᛚᛁᚱ ᚢᛏᛦᛏᛁᛚᛁᚵ Config ᛁ {$file}
 spec-missing-config = Config ᛘᛆᚿᚵᛚᛆᚱ
-spec-missing-gen = ᚠᛆᚿ ᚠᛁᚱᚴᛁᚿ HFST.Gen ᛁᛚᛚᛁᚱ Foma.Gen ᛁ Config
+spec-missing-gen = ᚠᛆᚿ ᚠᛁᚱᚴᛁᚿ HFST.Gen, Foma.Gen ᛁᛚᛚᛁᚱ Apertium.Gen ᛁ Config
 spec-missing-hfst = Config.hfst ᛘᛆᚿᚵᛚᛆᚱ
 spec-missing-hfst-gen = Config.hfst.Gen ᛘᛆᚿᚵᛚᛆᚱ
 spec-missing-foma = Config.foma ᛘᛆᚿᚵᛚᛆᚱ
 spec-missing-foma-gen = Config.foma.Gen ᛘᛆᚿᚵᛚᛆᚱ
+spec-invalid-timeout = ᛏᛁᛘᛁᚮᚢᛏ ᛘᚮ ᚠᛁᚱᛁ ᛁᛁᛏ ᛒᚮᛋᛁᛏᛁᚠᛏ ᛏᛆᛚ ᛋᛁᚴᚢᚿᛏ, ᚠᛁᚴᚴ {$value}
+spec-missing-apertium = Config.apertium ᛘᛆᚿᚵᛚᛆᚱ
+spec-missing-apertium-gen = Config.apertium.Gen ᛘᛆᚿᚵᛚᛆᚱ
+spec-missing-http = Config.http ᛘᛆᚿᚵᛚᛆᚱ
+spec-missing-http-url = Config.http.Url ᛘᛆᚿᚵᛚᛆᚱ
+spec-missing-command = Config.command ᛘᛆᚿᚵᛚᛆᚱ
+spec-missing-command-gen = Config.command.Gen ᛘᛆᚿᚵᛚᛆᚱ
+spec-missing-command-run = Config.command.Run ᛘᛆᚿᚵᛚᛆᚱ
+spec-missing-fst-gen = ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛁᚿ {$fst} ᛁ Fsts ᛘᛆᚿᚵᛚᛆᚱ Gen
+spec-fsts-http = Fsts ᚴᛆᚿ ᛁᚴᚴᛁ ᛒᚱᚢᚴᛆᛋᛏ ᛘᛁᛏ ᛁᛁ ᛘᚮᚱᚠᚮᛚᚮᚵᛁᛏᛁᚿᛁᛋᛏᛁ (Http)
+spec-fsts-missing = ᛏᛁᛋᛏᛆᚱ ᚠᚮᚱ ᛁᛁᚿᛋᚴᛁᛚᛏᛁ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛆᚱ ᚮᚵ Relations ᚴᚱᛁᚠ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛆᚱ ᚿᛆᛘᚿᚵᛌᛁᚠᚿᛁ ᛁ Fsts
+spec-unknown-fst = ᚢᚴᛌᛁᚿᛏ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ {$fst}, Fsts ᚿᛆᛘᚿᚵᛌᛁᚠ {$known}
+spec-invalid-relation = ᚢᚵᛦᛚᛏᛁᚵ ᚱᛁᛚᛆᛋᛌᚮᚿ {$relation}, ᛋᚴᚱᛁᚠ ᚼᛆᚿ t.d. ᛋᚮᛘ `desc ⊇ norm` ᛁᛚᛚᛁᚱ `desc >= norm`
+project-failed-to-read = ᚴᚢᚿᚿᛁ ᛁᚴᚴᛁ ᛚᛁᛋᛁ ᛒᚱᚮᛋᛌᛁᚴᛏᚮᛒᛒᛋᛁᛏᛏ: {$file}
+project-yaml-error = YAML-ᚠᛁᛁᛚ ᛁ ᛒᚱᚮᛋᛌᛁᚴᛏᚮᛒᛒᛋᛁᛏᛏ: {$file}
+project-invalid-value = ᚢᚵᛦᛚᛏᛁᚵ ᚠᛁᚱᛏᛁ ᚠᚮᚱ {$option} ᛁ ᛒᚱᚮᛋᛌᛁᚴᛏᚮᛒᛒᛋᛁᛏᛏ: {$value}
+spec-unknown-variable = ᚢᚴᛌᛁᚿᛏ ᚠᛆᚱᛁᛆᛒᛁᛚ ${{$name}}: ᚼᛆᚿ ᛁᚱ ᚠᛁᚱᚴᛁᚿ yamldir, builddir, srcdir ᛁᛚᛚᛁᚱ fsttype, ᛁᛚᛚᛁᚱ ᛋᛁᛏᛏ ᛁ ᛘᛁᛚᛌᚯᛁᛏ
+spec-unclosed-variable = ᛁᛁᚿ ᚠᛆᚱᛁᛆᛒᛁᛚ ᛁᚱ ᛁᚴᚴᛁ ᛚᚢᚴᚴᛆ ᛘᛁᛏ } ᛁ {$value}
+spec-no-fsttype = ${fsttype} ᛁᚱ ᛒᚱᚢᚴᛏ, ᛘᛁᚿ ᚠᛁᛚᚿᛆᛘᚿᛁᛏ ᚵᛌᛁᚠ ᛁᚿᚵᛁᚿ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱᛏᛦᛒᛁ (ᛋᚮᛘ ᛁ nouns_gt-norm.yaml)
+spec-json-error = JSON-ᚠᛖᛁᛚ ᛁ: {$file}
+spec-toml-error = TOML-ᚠᛖᛁᛚ ᛁ: {$file}
+spec-table-error = ᚠᛖᛁᛚ ᛁ ᛏᛁᛋᛏᛏᛆᛒᛁᛚᛚᛁᚿ: {$file}
+table-missing-column = ᚮᚠᛁᚱᛋᚴᚱᛁᚠᛏᛋᚱᛆᛏᛆ ᚼᛆᚱ ᛁᚿᚵᛁᚿ {$column}-ᚴᚮᛚᚮᚿᚿᛁ; ᚵᛌᛁ ᚿᛆᛘᚿᛁᛏ ᛁ Table ᛁ Config
+table-incomplete-row = ᚱᛆᛏᛆ ᛒᚮ ᛚᛁᚿᛌᛁ {$line} ᛘᛆᚿᚵᛚᛆᚱ {$column}
+table-wide-column = ᚴᚮᛚᚮᚿᚿᛆ {$column} ᚼᚯᛦᚱᛁᚱ ᛒᛁᚱᚱᛁ ᚼᛁᛁᛘᛁ ᛁ ᛚᛆᚿᚵᛏ ᚠᚮᚱᛘᛆᛏ, ᛏᛁᚱ ᚴᚠᛆᚱ ᚱᛆᛏ ᚵᛌᛁᚠ ᛁᛁᚿ ᛚᛁᚴᛋᛁᚴᛆᛚᛋᚴ ᚿᚯᚴᚴᛁᛚ
+spec-paradigm-error = ᚠᛖᛁᛚ ᛁ Paradigms ᛁ: {$file}
+spec-paradigm-lemma-forms = ᛒᛆᚱᛆᛏᛁᚵᛘᛁᛏ {$name} ᛏᚱᛁᚿᚵ Lemma ᛘᛁᛏ Forms, ᛁᛚᛚᛁᚱ Lemmas
+spec-paradigm-length = ᛒᛆᚱᛆᛏᛁᚵᛘᛁᛏ {$name}: {$lemma} ᚼᛆᚱ {$forms} ᚠᚮᚱᛘᛁᚱ ᚠᚮᚱ {$tags} ᛏᛆᚵᚵᛋᛏᚱᛁᚿᚵᛆᚱ
+spec-paradigm-unknown-tag = ᛒᛆᚱᛆᛏᛁᚵᛘᛁᛏ {$name}: {$lemma} ᚼᛆᚱ ᛁᛁ ᚠᚮᚱᛘ ᚠᚮᚱ {$tags}, ᛋᚮᛘ ᛁᚴᚴᛁ ᛁᚱ ᛘᛁᛚᛚᚮᛘ Tags
+warn-lexc-duplicate = ᛏᚢᛒᛚᛁᛋᛁᚱᛏ lexc-ᛏᛁᛋᛏ ᛒᚮ ᛚᛁᚿᛌᛁ {$line} ᛁ {$file}, ᚠᛁᚱᛏ ᛏᛁᛋᛏᛆ ᛁᛁᚿ ᚵᚮᚿᚵ: {$test}
+spec-lexc-contradiction = Lexc-ᛏᛁᛋᛏᛁᚿ ᛒᚮ ᛚᛁᚿᛌᛁ {$line} ᛁ {$file} ᛘᚮᛏᛋᛁᛁᛁᚱ ᛚᛁᚿᛌᛁ {$first}, ᛋᚮᛘ ᚵᛌᛁᚠ ᛏᛁᛏ ᛋᛆᛘᛁ ᛒᛆᚱᛁᛏ ᚮᛘᚠᛁᚿᛏᛏ, ᛘᛁᛏ !!€ ᛁᛚᛚᛁᚱ !!$: {$test}
+spec-hfst-tool-guessed = {$file} ᛁᚱ {$format}, ᛁᚴᚴᛁ ᛁᛁᚿ HFST-ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ, ᛋᚮ ᚴᚠᛆ ᚠᚮᚱ HFST-ᚮᛒᛒᛋᛚᛆᚵᛋᚠᛁᚱᚴᛏᚯᛦ ᛋᚮᛘ ᛚᛁᛋ ᚼᛆᚿ, ᛁᚱ ᛒᛁᚱᚱᛁ ᚵᛁᛋᛋᛆ
 
 # Debug messages
 debug-batch-lookup = ᚴᚯᛦᚱᛁᚱ batch-ᚮᛒᛒᛋᛚᛆᚵ ᛘᛁᛏ {$count} ᛁᚿᚿᛏᛆᛏᛆ ᛘᛁᛏ FST: {$fst}
 debug-batch-completed = ᛒᛆᛏᚳ-ᚮᛒᛒᛋᛚᛆᚵ ᚠᚢᛚᛚᚠᚯᚱᛏ: {$inputs} ᛁᚿᚿᛏᛆᛏᛆ ᛒᚱᚮᛋᛁᛋᛋᛁᚱᛏᛁ, {$results} ᛏᚮᛏᛆᛚᛁ ᚱᛁᛋᚢᛚᛏᛆᛏ
 debug-pool-batch = ᛒᚢᛚᛌᛁ-ᛒᚱᚮᛋᛁᛋᛋ batch: ᛒᚱᚮᛋᛁᛋᛋᛁᚱᛁᚱ {$count} ᛁᚿᚿᛏᛆᛏᛆ
 debug-pool-completed = ᛒᚢᛚᛌᛁ-batch ᚠᚢᛚᛚᚠᚯᚱᛏ: {$inputs} ᛁᚿᚿᛏᛆᛏᛆ ᛒᚱᚮᛋᛁᛋᛋᛁᚱᛏᛁ, {$results} ᛏᚮᛏᛆᛚᛁ ᚱᛁᛋᚢᛚᛏᛆᛏ
+debug-native-load = ᛚᛆᛋᛏᛆᚱ ᛏᚱᛆᚿᛋᛏᚢᛋᛁᚱ ᛁᚿᚿ ᛁ ᛘᛁᚿᚿᛁᛏ: {$fst}
+debug-pool-retry = ᚮᛒᛒᛋᛚᛆᚵᛋᛒᚱᚮᛋᛁᛋᛋᛁᚿ ᚠᛁᛁᛚᛆ ᛒᚮ ᛁᛁᚿ ᛒᛁᛏ ᛘᛁᛏ {$count} ᛁᚿᛒᚢᛏ, ᛒᚱᚯᚠᛁᚱ ᛁᚵᛌᛁᚿ ᛘᛁᛏ ᛁᛁᚿ ᚿᛦ ᛒᚱᚮᛋᛁᛋᛋ: {$error}
+debug-pool-bisect = ᛏᛁᛚᛁᚱ ᛁᛁᚿ ᚠᛁᛁᛚᛆᚿᛏᛁ ᛒᛁᛏ ᛘᛁᛏ {$count} ᛁᚿᛒᚢᛏ ᚠᚮᚱ ᚮ ᚠᛁᚿᚿᛁ ᛁᚿᛒᚢᛏᛁᚿ ᛋᚮᛘ ᚠᚮᚱ ᚮᛒᛒᛋᛚᛆᚵᛋᛒᚱᚮᛋᛁᛋᛋᛁᚿ ᛏᛁᛚ ᚮ ᚠᛁᛁᛚᛁ
+debug-cache-open = ᚮᛒᛒᛋᛚᛆᚵᛋᛋᛆᛋᚼᛁᚿ {$file} ᚼᛆᚱ {$count} ᚮᛒᛒᚠᚯᚱᛁᚿᚵᛆᚱ
+debug-cache-hits = ᚮᛒᛒᛋᛚᛆᚵᛋᛋᛆᛋᚼᛁᚿ ᛋᚠᛆᚱᛏᛁ ᛒᚮ {$hits} ᛆᚠ {$total} ᛁᚿᛒᚢᛏ
+debug-http-request = ᛋᛁᚿᛏᛁᚱ {$count} ᛁᚿᚿᛏᛆᛏᛆ ᛏᛁᛚ {$endpoint}
+debug-fixture-open = ᛋᛒᛁᛚᛆᚱ ᛆᚠ {$count} ᚮᛒᛒᛋᛚᛆᚵ ᚠᚱᚮ {$file}
+debug-project-config = ᛒᚱᚢᚴᛆᚱ ᛒᚱᚮᛋᛌᛁᚴᛏᚮᛒᛒᛋᛁᛏᛏ {$file}
 
 # Engine messages
 engine-not-processed = ᛁᚴᚴᛁ ᛒᚱᚮᛋᛁᛋᛋᛁᚱᛏ
 engine-batch-analyze-error = ᛒᛆᛏᚳ-ᛆᚿᛆᛚᛦᛋᛁᚠᛖᛁᛚ: {$error}
 engine-batch-generate-error = ᛒᛆᛏᚳ-ᚵᛁᚿᛁᚱᛁᚱᛁᚿᚵᛋᚠᛖᛁᛚ: {$error}
+engine-lookup-error = ᚮᛒᛒᛋᛚᛆᚵᛁᛏ ᚠᛁᛁᛚᛆ ᚠᚮᚱ ᛏᛁᚿᚿᛁ ᛁᚿᛒᚢᛏᛁᚿ: {$error}
+warn-fsts-swapped = ᚵᛁᚿᛁᚱᛆᛏᚮᚱ ᚮᚵ ᛆᚿᛆᛚᛦᛋᛆᛏᚮᚱ ᛋᛁᚱ ᚢᛏ ᛏᛁᛚ ᚮ ᚠᛁᚱᛁ ᛒᛦᛏᛏᛁ ᚮᛘ: ᛆᚿᛆᛚᛦᛋᛆᛏᚮᚱᛁᚿ {$analyser} ᛋᚠᛆᚱᛆᚱ ᛒᚮ ᛏᛁᚿ ᛚᛁᚴᛋᛁᚴᛆᛚᛋᚴᛁ ᚠᚮᚱᛘᛆ '{$input}', ᚵᛁᚿᛁᚱᛆᛏᚮᚱᛁᚿ {$generator} ᛋᚠᛆᚱᛆᚱ ᛁᚴᚴᛁ ᛒᚮ ᚿᚮᚴᚮᚿ ᛆᚠ ᛏᛁᛁ ᛏᛁᛋᛏᛆ
+engine-wrong-result-count = ᛒᛆᛋᚴᛁᚿᛏᛁᚿ ᛋᚠᛆᚱᛏᛁ ᛒᚮ {$got} ᛆᚠ {$expected} ᛁᚿᛒᚢᛏ
 
 # I18N system messages
 i18n-init-failed = ᚴᛚᛆᚱᛏᛁ ᛁᚴᚴᛁ ᚮ ᛁᚿᛁᛋᛁᛆᛚᛁᛋᛂᚱᛂ ᛚᚮᚴᛆᛚᛁᛋᛂᚱᛁᚿᚵᛋᛋᛦᛋᛏᛂᛘᛂᛏ
@@ -1,6 +1,6 @@
 # CLI help text
 cli-about = Morfologisk testkøyrar (overflate/analyse og leksikalsk/generering)
-cli-backend = Vel fst-format/seksjon (hfst eller foma) [alias: -S/--section]
+cli-backend = Vel fst-format/seksjon (hfst, foma, apertium, http eller command) [alias: -S/--section]
 cli-generator = Overstyr generator-FST (.hfstol for HFST, .foma for Foma) [alias: --gen]
 cli-analyser = Overstyr analyse-FST (.hfstol for HFST, .foma for Foma) [alias: --morph, --analyzer]
 cli-silent = Stille modus: inga utskrift, og demp stderr frå lookup
@@ -16,6 +16,14 @@ cli-hide-passes = Skjul godkjende (PASS), vis berre feil (FAIL)
 cli-test = Køyr berre oppgjeven test: nummer 1..N, tittel „Gruppe (Lexical/Generation|Surface/Analysis)" eller berre gruppenamnet frå YAML. Spesialtestnamn: 0, 'null' eller 'liste' listar alle tilgjengelege testsett.
 cli-output = Rapportformat: normal | compact | terse | final (standard: normal)
 cli-serial = Bruk seriell køyring i staden for parallellprosessering (parallell er standard)
+cli-native = Bruk den innebygde lesaren for transduserar (.hfstol, .foma, .att) i staden for eit eksternt lookup-verktøy
+cli-timeout = Sekund å venta på oppslaget av éin input før han vert rapportert som tidsavbroten [standard: 30, eller Timeout i YAML-Config]
+cli-cache = Ta vare på oppslagsresultat på disk og bruk dei om att så lenge transduseren og lookup-verktøyet er uendra
+cli-cache-dir = Katalog for mellomlagra oppslagsresultat (gjev --cache) [standard: cache-katalogen til brukaren]
+cli-endpoint = Køyr alle testsett mot ei morfologiteneste på URL i staden for transduserane deira
+cli-record = Ta opp alle oppslag i ei fixture-FILE, for avspeling utan transduserar
+cli-replay = Svar på oppslag frå ei fixture-FILE teken opp med --record
+cli-normalize = Fjern flaggdiakritika som @P.x.y@ frå utdataa før dei vert samanlikna, slik Flags: true under Normalize i YAML-Config gjer
 
 # Directions and modes  
 direction-generate = Leksikalsk/Generering
@@ -40,6 +48,7 @@ info-starting-tests = Byrjar å testa ({$count} testar, modus: {$mode}) (batch p
 info-starting-parallel = Suite: {$name} (parallel processing)...
 info-finished = Ferdig: godkjende {$passed}, feila {$failed}. Skriv rapport...
 info-all-finished = Alle testkøyringar ferdige. I alt: {$total}, Godkjende: {$passed}, Feila: {$failed}
+info-native-lookup = innebygd lesar
 
 # Test listing
 available-tests = Tilgjengelege testar:
@@ -59,19 +68,42 @@ report-test-header = Test {$index}: {$group} ({$direction})
 report-test-summary = Test {$index} - Bestått: {$passes}, Feila: {$fails}, Totalt: {$total}
 report-total-summary = I alt godkjent: {$passes}, I alt feila: {$fails}, I alt: {$total}
 report-final-counts = {$passes}/{$fails}/{$total}
+report-weight-best = {$output} (lågast vekt)
+report-weight-below = {$output} (vekt under {$limit})
+report-raw = Rå
 
 # Backend error messages
 backend-failed-to-start = Klarte ikkje å starta '{$cmd}'
 backend-missing-stdin = Manglar stdin
-backend-process-failed = Lookup-prosess feila med status {$status}
-backend-timeout = Lookup-tidsavbrot etter {$seconds} s
-backend-process-failed-stderr = Lookup-prosess feila med status {$status}
+backend-process-failed = Lookup-prosessen avslutta med status {$status} etter å ha svart på all input
 Stderr: {$stderr}
 backend-analyzer-not-set = Analysator-FST ikkje sett
 backend-generator-not-set = Generator-FST ikkje sett
 backend-command-not-executable = Lookup-kommando '{$cmd}' kunne ikkje køyrast: {$error}
 backend-command-not-found = Lookup-kommando '{$cmd}' finst ikkje eller kan ikkje køyrast. Sjekk at den er installert og i PATH.
 backend-command-error = Kan ikkje køyre lookup-kommando '{$cmd}': {$error}
+backend-missing-stdout = Manglar stdout
+backend-input-timeout = Ikkje noko svar innan {$seconds} s for input '{$input}'
+backend-process-ended = Oppslagsprosessen avslutta medan han svarte på input '{$input}' (han vart starta på nytt for resten)
+http-request-failed = Førespurnad til {$endpoint} feila: {$error}
+http-wrong-result-count = Tenesta svarte med {$got} resultat for {$expected} inndata
+http-unreachable = Morfologitenesta på {$endpoint} kan ikkje brukast: {$error}
+command-invalid-pattern = Ugyldig utdatamønster: {$pattern}
+command-pattern-without-output = Utdatamønsteret har ingen 'output'-gruppe: {$pattern}
+command-missing-pattern = Regex-tolkaren for utdata treng eit Pattern
+command-unknown-parser = Ukjend utdatatolkar '{$name}' (hfst, apertium, lines eller regex)
+format-fst-not-found = Fann ikkje transduseren: {$file}
+format-fst-unreadable = Kan ikkje lese transduseren: {$file}
+format-fst-wrong-format = {$file} er {$format}, som '{$cmd}' ikkje kan lese
+format-hfstol = ein HFST optimized-lookup-transduser
+format-hfst = ein HFST-transduser av typen {$kind}
+format-foma = eit foma-nettverk
+format-att = ein AT&T-teksttransduser
+format-lttoolbox = ein lttoolbox-transduser
+format-unknown = av ukjent format
+format-fst-unknown = Formatet til {$file} er ukjent: fila har ingen HFST-header og er korkje eit foma-nettverk eller AT&T-tekst
+backend-gave-up = Oppslagsprosessen feila før han svarte på noka input, så han vart ikkje starta på nytt: {$error}
+backend-not-looked-up = Ikkje slått opp, fordi ein ny oppslagsprosess feila før han svarte på noka input: {$error}
 
 # Pool error messages
 pool-io-error = IO-feil ved lesing frå prosess: {$error}
@@ -85,28 +117,94 @@ pool-get-analyze-failed = Klarte ikkje å henta prosess frå analysepulje: {$err
 pool-get-generate-failed = Klarte ikkje å henta prosess frå genereringspulje: {$error}
 pool-validate-analyze-failed = Klarte ikkje å henta prosess frå analysepulje for validering: {$error}
 pool-validate-generate-failed = Klarte ikkje å henta prosess frå genereringspulje for validering: {$error}
+pool-timeout = Oppslagsprosessen svarte ikkje innan {$seconds} sekund for input '{$input}'
+pool-process-ended = Oppslagsprosessen avslutta før han svarte på input '{$input}'
+
+# Native backend messages
+native-failed-to-read = Klarte ikkje å lesa FST-fil: {$file}
+native-failed-to-load = Klarte ikkje å lasta transduser: {$file}
+native-hfstol-truncated = Transduserfila er avkorta eller skadd
+native-hfstol-bad-header = Ugyldig HFST-hovud
+native-hfstol-wrong-type = HFST-transdusertypen '{$kind}' er ikkje støtta (venta ein optimized-lookup-transduser, HFST_OL eller HFST_OLW)
+native-foma-bad-gzip = Kunne ikkje pakke ut foma-fil: {$error}
+native-foma-bad-header = Ikkje eit foma-nettverk (manglar ##foma-net-hovud)
+native-foma-bad-line = Ugyldig linje {$line} i foma-nettverk: {$text}
+native-att-bad-line = Ugyldig linje {$line} i AT&T-fil: {$text}
+
+# Cache messages
+cache-no-dir = Fann ingen cache-katalog for denne brukaren, oppslag vert ikkje mellomlagra (bruk --cache-dir)
+cache-failed-to-hash = Klarte ikkje å lesa FST-fil for oppslagscachen: {$file}
+cache-failed-to-read = Ser bort frå uleseleg oppslagscache {$file}: {$error}
+cache-failed-to-write = Klarte ikkje å skriva oppslagscache {$file}: {$error}
+cache-missing-results = Backenden returnerte færre resultat enn input
+fixture-failed-to-read = Kunne ikkje lese oppslagsfixture: {$file}
+fixture-failed-to-write = Kunne ikkje skrive oppslagsfixture: {$file}
+fixture-missing-inputs = {$count} inndata manglar i fixturen {$file}, ta han opp på nytt: {$inputs}
 
 # Spec error messages
 spec-failed-to-read = Klarte ikkje å lesa: {$file}
 spec-yaml-error = YAML-feil i: {$file}
 spec-incomplete-config = Mangelfull eller utydeleg Config i {$file}
 spec-missing-config = Config manglar
-spec-missing-gen = Fann korkje HFST.Gen eller Foma.Gen i Config
+spec-missing-gen = Fann korkje HFST.Gen, Foma.Gen eller Apertium.Gen i Config
 spec-missing-hfst = Config.hfst manglar
 spec-missing-hfst-gen = Config.hfst.Gen manglar
 spec-missing-foma = Config.foma manglar
 spec-missing-foma-gen = Config.foma.Gen manglar
+spec-invalid-timeout = Timeout må vere eit positivt tal sekund, fekk {$value}
+spec-missing-apertium = Config.apertium manglar
+spec-missing-apertium-gen = Config.apertium.Gen manglar
+spec-missing-http = Config.http manglar
+spec-missing-http-url = Config.http.Url manglar
+spec-missing-command = Config.command manglar
+spec-missing-command-gen = Config.command.Gen manglar
+spec-missing-command-run = Config.command.Run manglar
+spec-missing-fst-gen = Transduseren {$fst} i Fsts manglar Gen
+spec-fsts-http = Fsts kan ikkje brukast med ei morfologiteneste (Http)
+spec-fsts-missing = Testar for einskilde transduserar og Relations krev transduserar namngjevne i Fsts
+spec-unknown-fst = Ukjend transduser {$fst}, Fsts namngjev {$known}
+spec-invalid-relation = Ugyldig relasjon {$relation}, skriv han t.d. som `desc ⊇ norm` eller `desc >= norm`
+project-failed-to-read = Kunne ikkje lese prosjektoppsett: {$file}
+project-yaml-error = YAML-feil i prosjektoppsett: {$file}
+project-invalid-value = Ugyldig verdi for {$option} i prosjektoppsett: {$value}
+spec-unknown-variable = Ukjend variabel ${{$name}}: han er verken yamldir, builddir, srcdir eller fsttype, eller sett i miljøet
+spec-unclosed-variable = Ein variabel er ikkje lukka med } i {$value}
+spec-no-fsttype = ${fsttype} er brukt, men filnamnet gjev ingen transdusertype (som i nouns_gt-norm.yaml)
+spec-json-error = JSON-feil i: {$file}
+spec-toml-error = TOML-feil i: {$file}
+spec-table-error = Feil i testtabellen: {$file}
+table-missing-column = Overskriftsrada har ingen {$column}-kolonne; gje namnet i Table i Config
+table-incomplete-row = Rada på linje {$line} manglar {$column}
+table-wide-column = Kolonna {$column} høyrer berre heime i langt format, der kvar rad gjev éin leksikalsk nøkkel
+spec-paradigm-error = Feil i Paradigms i: {$file}
+spec-paradigm-lemma-forms = Paradigmet {$name} treng Lemma med Forms, eller Lemmas
+spec-paradigm-length = Paradigmet {$name}: {$lemma} har {$forms} former for {$tags} taggstrengar
+spec-paradigm-unknown-tag = Paradigmet {$name}: {$lemma} har ei form for {$tags}, som ikkje er mellom Tags
+warn-lexc-duplicate = Duplisert lexc-test på linje {$line} i {$file}, vert testa éin gong: {$test}
+spec-lexc-contradiction = Lexc-testen på linje {$line} i {$file} motseier linje {$first}, som gjev det same paret omvendt, med !!€ eller !!$: {$test}
+spec-hfst-tool-guessed = {$file} er {$format}, ikkje ein HFST-transduser, så kva for HFST-oppslagsverktøy som les han, er berre gissa
 
 # Debug messages
 debug-batch-lookup = Køyrer batch-oppslag med {$count} inndata med FST: {$fst}
 debug-batch-completed = Batch-oppslag fullført: {$inputs} inndata prosesserte, {$results} totale resultat
 debug-pool-batch = Pulje-prosess batch: prosesserer {$count} inndata
 debug-pool-completed = Pulje-batch fullført: {$inputs} inndata prosesserte, {$results} totale resultat
+debug-native-load = Lastar transduser inn i minnet: {$fst}
+debug-pool-retry = Oppslagsprosessen feila på ein bit med {$count} input, prøver igjen med ein ny prosess: {$error}
+debug-pool-bisect = Deler ein feilande bit med {$count} input for å finne inputen som får oppslagsprosessen til å feile
+debug-cache-open = Oppslagscachen {$file} har {$count} oppføringar
+debug-cache-hits = Oppslagscachen svarte på {$hits} av {$total} input
+debug-http-request = Sender {$count} inndata til {$endpoint}
+debug-fixture-open = Spelar av {$count} oppslag frå {$file}
+debug-project-config = Brukar prosjektoppsett {$file}
 
 # Engine messages
 engine-not-processed = Ikkje prosessert
 engine-batch-analyze-error = Batch-analysefeil: {$error}
 engine-batch-generate-error = Batch-genereringsfeil: {$error}
+engine-lookup-error = Oppslaget feila for denne inputen: {$error}
+warn-fsts-swapped = Generator og analysator ser ut til å vere bytte om: analysatoren {$analyser} svarar på den leksikalske forma '{$input}', generatoren {$generator} svarar ikkje på nokon av dei testa
+engine-wrong-result-count = Backenden svarte på {$got} av {$expected} input
 
 # I18N system messages  
 i18n-init-failed = Klarte ikkje å initialisere lokaliseringsystemet
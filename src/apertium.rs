@@ -0,0 +1,318 @@
+use crate::backend::{Backend, DEFAULT_TIMEOUT, LookupArgs, check_command, give_up};
+use crate::format::{check_fsts, named_like_hfst};
+use crate::types::{LookupOutcome, LookupOutput};
+use crate::{t, t_args};
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// The Apertium tool for a transducer: `hfst-proc` for HFST files, `lt-proc` otherwise
+pub fn default_proc_tool(gen_path: &str, morph_path: Option<&str>) -> String {
+    if named_like_hfst(gen_path) || morph_path.is_some_and(named_like_hfst) {
+        "hfst-proc".to_string()
+    } else {
+        "lt-proc".to_string()
+    }
+}
+
+/// Split Apertium stream text into its lexical units, each as the unescaped
+/// parts between `^`, `/` and `$`: the surface form followed by its readings.
+/// Superblanks and text outside lexical units are skipped.
+pub fn parse_stream(text: &str) -> Vec<Vec<String>> {
+    let mut units = Vec::new();
+    let mut parts: Option<Vec<String>> = None;
+    let mut in_superblank = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, parts.as_mut()) {
+            ('\\', Some(parts)) => {
+                if let Some(escaped) = chars.next() {
+                    parts.last_mut().unwrap().push(escaped);
+                }
+            }
+            ('\\', None) => {
+                chars.next();
+            }
+            ('[', None) => in_superblank = true,
+            (']', None) => in_superblank = false,
+            ('^', None) if !in_superblank => parts = Some(vec![String::new()]),
+            ('/', Some(parts)) => parts.push(String::new()),
+            ('$', Some(_)) => units.extend(parts.take()),
+            (c, Some(parts)) => parts.last_mut().unwrap().push(c),
+            (_, None) => {}
+        }
+    }
+    units
+}
+
+/// Split on `/` that is not escaped, unescaping the parts
+fn split_alternatives(text: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => parts.last_mut().unwrap().extend(chars.next()),
+            '/' => parts.push(String::new()),
+            c => parts.last_mut().unwrap().push(c),
+        }
+    }
+    parts
+}
+
+/// Unknown words are marked `*` in analysis, and `#` or `@` in generation
+fn is_unknown(reading: &str) -> bool {
+    reading.starts_with(['*', '#', '@'])
+}
+
+/// Analyses of a surface form. An input that splits into several lexical
+/// units gets every combination of their readings, separated by spaces.
+pub fn analyses(output: &str) -> Vec<String> {
+    let mut combined = vec![String::new()];
+    let units = parse_stream(output);
+    if units.is_empty() {
+        return vec![];
+    }
+    for unit in units {
+        let readings: Vec<&String> = unit.iter().skip(1).filter(|r| !is_unknown(r)).collect();
+        combined = combined
+            .iter()
+            .flat_map(|prefix| {
+                readings.iter().map(move |reading| {
+                    if prefix.is_empty() {
+                        reading.to_string()
+                    } else {
+                        format!("{prefix} {reading}")
+                    }
+                })
+            })
+            .collect();
+    }
+    combined
+}
+
+/// Surface forms generated from a lexical form
+pub fn generations(output: &str) -> Vec<String> {
+    split_alternatives(output.trim())
+        .into_iter()
+        .map(|form| form.trim().to_string())
+        .filter(|form| !form.is_empty() && !is_unknown(form))
+        .collect()
+}
+
+/// Surface forms go to the analyser as plain text, with the characters that
+/// have a meaning in the stream format escaped
+pub(crate) fn analyzer_input(surface: &str) -> String {
+    let mut escaped = String::with_capacity(surface.len());
+    for c in surface.chars() {
+        if matches!(c, '^' | '$' | '/' | '<' | '>' | '@' | '\\' | '[' | ']' | '{' | '}') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Lexical forms go to the generator as lexical units
+pub(crate) fn generator_input(lexical: &str) -> String {
+    if lexical.starts_with('^') && lexical.ends_with('$') {
+        lexical.to_string()
+    } else {
+        format!("^{lexical}$")
+    }
+}
+
+pub(crate) fn to_outputs(forms: Vec<String>) -> Vec<LookupOutput> {
+    let mut outputs: Vec<LookupOutput> = Vec::with_capacity(forms.len());
+    for form in forms {
+        if !outputs.iter().any(|o| o.output == form) {
+            outputs.push(form.into());
+        }
+    }
+    outputs
+}
+
+/// Backend that runs `lt-proc` or `hfst-proc` and reads the Apertium stream
+/// format. The tool runs in null-flush mode (`-z`): every input is ended by a
+/// NUL character, and the tool answers it in full before a NUL of its own.
+#[derive(Debug, Clone)]
+pub struct ApertiumBackend {
+    pub proc_cmd: String, // "lt-proc" or "hfst-proc"
+    pub args: LookupArgs,
+    pub generator_fst: Option<String>,
+    pub analyzer_fst: Option<String>,
+    pub timeout: Option<Duration>, // per input
+    pub quiet: bool, // suppress stderr from the tool when true
+}
+
+impl ApertiumBackend {
+    fn run_batch(&self, fst: &str, generate: bool, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        debug!(
+            "{}",
+            t_args!("debug-batch-lookup",
+                "count" => inputs.len(),
+                "fst" => fst
+            )
+        );
+
+        // As with the other lookup tools, an input that hangs or crashes the
+        // process fails on its own, and the rest go to a fresh process
+        let mut outcomes = Vec::with_capacity(inputs.len());
+        let mut answered = false;
+        while outcomes.len() < inputs.len() {
+            let done = outcomes.len();
+            if self.run_until_failure(fst, generate, &inputs[done..], timeout, &mut outcomes)? {
+                answered = true;
+            } else {
+                give_up(&inputs[outcomes.len()..], answered, &mut outcomes)?;
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Look up inputs in a single process until one fails, returning whether
+    /// the process answered any input before that
+    fn run_until_failure(
+        &self,
+        fst: &str,
+        generate: bool,
+        inputs: &[String],
+        timeout: Duration,
+        outcomes: &mut Vec<LookupOutcome>,
+    ) -> Result<bool> {
+        let (mode, args) = if generate {
+            (Some("-g"), &self.args.generate)
+        } else {
+            (None, &self.args.analyze)
+        };
+        let mut cmd = Command::new(&self.proc_cmd);
+        cmd.arg("-z")
+            .args(mode)
+            .args(args)
+            .arg(fst)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(if self.quiet {
+                Stdio::null()
+            } else {
+                Stdio::inherit()
+            });
+
+        let mut child = cmd
+            .spawn()
+            .with_context(|| t_args!("backend-failed-to-start", "cmd" => &self.proc_cmd))?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!(t!("backend-missing-stdin")))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!(t!("backend-missing-stdout")))?;
+
+        let sections: Vec<String> = inputs
+            .iter()
+            .map(|input| input.trim())
+            .filter(|input| !input.is_empty())
+            .map(|input| if generate { generator_input(input) } else { analyzer_input(input) })
+            .collect();
+        std::thread::spawn(move || {
+            for section in sections {
+                if write!(stdin, "{section}\0").and_then(|_| stdin.flush()).is_err() {
+                    break;
+                }
+            }
+        });
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for section in BufReader::new(stdout).split(b'\0') {
+                if tx.send(section).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut answered = false;
+        for input in inputs {
+            let input = input.trim();
+            if input.is_empty() {
+                outcomes.push(Ok(vec![]));
+                continue;
+            }
+            match read_section(&rx, input, timeout) {
+                Ok(text) => {
+                    let forms = if generate { generations(&text) } else { analyses(&text) };
+                    outcomes.push(Ok(to_outputs(forms)));
+                    answered = true;
+                }
+                Err(reason) => {
+                    outcomes.push(Err(reason));
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Ok(answered);
+                }
+            }
+        }
+
+        // Every input has its answer by now, so a failing exit only gets a warning
+        let status = child.wait()?;
+        if !status.success() {
+            warn!("{}", t_args!("backend-process-failed", "status" => status));
+        }
+        Ok(true)
+    }
+
+    /// The tool is driven with blocking IO, so it runs off the async runtime
+    async fn lookup_blocking(&self, fst: String, generate: bool, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let backend = self.clone();
+        let inputs = inputs.to_vec();
+        tokio::task::spawn_blocking(move || backend.run_batch(&fst, generate, &inputs)).await?
+    }
+}
+
+/// Wait for the section answering `input`, giving up after `timeout`
+fn read_section(
+    rx: &mpsc::Receiver<std::io::Result<Vec<u8>>>,
+    input: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(bytes)) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(t_args!("backend-input-timeout",
+            "seconds" => timeout.as_secs_f64(),
+            "input" => input
+        )),
+        Ok(Err(_)) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(t_args!("backend-process-ended", "input" => input))
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for ApertiumBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let fst = self
+            .analyzer_fst
+            .clone()
+            .ok_or_else(|| anyhow!(t!("backend-analyzer-not-set")))?;
+        self.lookup_blocking(fst, false, inputs).await
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let fst = self
+            .generator_fst
+            .clone()
+            .ok_or_else(|| anyhow!(t!("backend-generator-not-set")))?;
+        self.lookup_blocking(fst, true, inputs).await
+    }
+
+    async fn validate(&self) -> Result<()> {
+        check_command(&self.proc_cmd)?;
+        let fsts = [&self.generator_fst, &self.analyzer_fst];
+        check_fsts(&self.proc_cmd, fsts.into_iter().flatten().map(String::as_str))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::fst::{Fst, State, SymbolTable, Transition};
+use crate::t_args;
+use anyhow::{Result, anyhow};
+
+/// Returns true for paths with the AT&T text format extension
+pub fn is_att_file(path: &str) -> bool {
+    path.ends_with(".att")
+}
+
+/// Parse the first transducer of an AT&T file, in the hfst convention: lines of
+/// `source target input output [weight]` and `state [weight]` for final states,
+/// with state 0 as the start state. Lookups apply it from input to output.
+pub fn parse_att(text: &str) -> Result<Fst> {
+    let mut symbols = SymbolTable::new();
+    let mut states: Vec<State> = Vec::new();
+    // Weights are only reported for files that give any
+    let mut weighted = false;
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Further transducers follow a `--` line
+        if line == "--" {
+            break;
+        }
+        let fields: Vec<&str> = if line.contains('\t') {
+            line.split('\t').collect()
+        } else {
+            line.split_whitespace().collect()
+        };
+        let state = |field: &str| field.parse::<usize>().map_err(|_| bad_line(n, line));
+        weighted |= matches!(fields.len(), 2 | 5);
+        let weight = |field: Option<&&str>| match field {
+            Some(w) => w.parse::<f32>().map_err(|_| bad_line(n, line)),
+            None => Ok(0.0),
+        };
+        match fields.len() {
+            1 | 2 => {
+                let s = state(fields[0])?;
+                ensure_state(&mut states, s);
+                states[s].final_weight = Some(weight(fields.get(1))?);
+            }
+            4 | 5 => {
+                let source = state(fields[0])?;
+                let target = state(fields[1])?;
+                ensure_state(&mut states, source.max(target));
+                let transition = Transition {
+                    input: symbols.intern(att_symbol(fields[2])),
+                    output: symbols.intern(att_symbol(fields[3])),
+                    target,
+                    weight: weight(fields.get(4))?,
+                };
+                states[source].transitions.push(transition);
+            }
+            _ => return Err(bad_line(n, line)),
+        }
+    }
+    Ok(Fst::new(symbols, states, weighted))
+}
+
+fn ensure_state(states: &mut Vec<State>, state: usize) {
+    if states.len() <= state {
+        states.resize_with(state + 1, State::default);
+    }
+}
+
+/// Map the escapes used in AT&T files to the symbols they stand for
+fn att_symbol(field: &str) -> &str {
+    match field {
+        "@0@" | "@_EPSILON_SYMBOL_@" => "@_EPSILON_SYMBOL_@",
+        "@_SPACE_@" => " ",
+        "@_TAB_@" => "\t",
+        other => other,
+    }
+}
+
+fn bad_line(n: usize, line: &str) -> anyhow::Error {
+    anyhow!(t_args!("native-att-bad-line", "line" => n + 1, "text" => line))
+}
@@ -1,33 +1,128 @@
+use crate::format::check_fsts;
+use crate::types::{LookupOutcome, LookupOutput};
 use crate::{t, t_args};
 use anyhow::{Context, Result, anyhow};
-use indexmap::IndexMap;
-use std::io::Write;
+use async_trait::async_trait;
+use std::io::{BufRead, BufReader, Write};
 use std::process::{Command, Stdio};
-use std::time::Duration;
-use tracing::debug;
-use wait_timeout::ChildExt;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
 
-/// 30 seconds per lookup
+/// 30 seconds per input
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Extra arguments for the lookup tool, per direction, passed before the transducer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct LookupArgs {
+    pub analyze: Vec<String>,
+    pub generate: Vec<String>,
+}
+
 /// Generic backend that runs an external lookup program (hfst-optimised-lookup, flookup, etc.)
 #[derive(Debug, Clone)]
 pub struct ExternalBackend {
     pub lookup_cmd: String, // "hfst-optimised-lookup" or "flookup"
+    pub args: LookupArgs,
     pub generator_fst: Option<String>,
     pub analyzer_fst: Option<String>,
-    pub timeout: Option<Duration>,
+    pub timeout: Option<Duration>, // per input
     pub quiet: bool, // suppress stderr from lookup when true
 }
 
+/// Looks up inputs with a transducer. Every input gets its own outcome, so
+/// that one input crashing or hanging the lookup does not fail the whole batch;
+/// the outer error is for when nothing could be looked up at all.
+#[async_trait]
 pub trait Backend: Send + Sync {
-    fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>>;
-    fn generate_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>>;
-    fn validate(&self) -> Result<()>;
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>>;
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>>;
+    async fn validate(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl<B: Backend + ?Sized> Backend for Box<B> {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        (**self).analyze_batch(inputs).await
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        (**self).generate_batch(inputs).await
+    }
+
+    async fn validate(&self) -> Result<()> {
+        (**self).validate().await
+    }
+}
+
+/// Collects one record of lookup tool output: the result lines for a single
+/// input, ended by a blank line. Outputs keep the order the tool gave them in.
+#[derive(Debug, Default)]
+pub(crate) struct Record {
+    lines: usize,
+    outputs: Vec<LookupOutput>,
+}
+
+impl Record {
+    /// Add a line of output, returning true once the record is complete
+    pub(crate) fn push_line(&mut self, raw_line: &str) -> bool {
+        let trimmed = raw_line.trim();
+        if trimmed.is_empty() {
+            // Blank lines before a record has started are not separators
+            return self.lines > 0;
+        }
+        // Skip comments, warnings and prompts (anything without a tab is not FST output)
+        if !trimmed.contains('\t') || trimmed.starts_with("> ") {
+            return false;
+        }
+        self.lines += 1;
+
+        let cols: Vec<&str> = trimmed.split('\t').collect();
+        let input = cols[0].trim();
+        let output = cols[1].trim();
+
+        // Handle +inf (no result) marker
+        if output == "+inf" {
+            return false;
+        }
+
+        // Handle cases where FST couldn't process: input==output with +? in third column
+        if cols.len() >= 3 && input == output && cols[2].trim().contains("+?") {
+            return false;
+        }
+
+        if !output.is_empty() && output != "@" {
+            // Tools that know of no weights, like flookup, leave the column out
+            let weight = cols.get(2).and_then(|w| w.trim().parse::<f64>().ok());
+            self.add(output, weight);
+        }
+        false
+    }
+
+    /// Whether any result line has been read
+    pub(crate) fn is_started(&self) -> bool {
+        self.lines > 0
+    }
+
+    /// The same output reached by several paths is kept once, with its lowest weight
+    pub(crate) fn add(&mut self, output: &str, weight: Option<f64>) {
+        match self.outputs.iter_mut().find(|o| o.output == output) {
+            Some(known) => {
+                if let (Some(w), Some(k)) = (weight, known.weight) {
+                    known.weight = Some(w.min(k));
+                }
+            }
+            None => self.outputs.push(LookupOutput::new(output, weight)),
+        }
+    }
+
+    pub(crate) fn into_outputs(self) -> Vec<LookupOutput> {
+        self.outputs
+    }
 }
 
 impl ExternalBackend {
-    fn run_lookup_batch(&self, fst: &str, inputs: &[String]) -> Result<Vec<Vec<String>>> {
+    fn run_lookup_batch(&self, fst: &str, args: &[String], inputs: &[String]) -> Result<Vec<LookupOutcome>> {
         let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
         debug!(
             "{}",
@@ -37,8 +132,50 @@ impl ExternalBackend {
             )
         );
 
+        // An input that hangs or crashes the process is reported on its own,
+        // and the inputs after it go to a fresh process
+        let mut outcomes = Vec::with_capacity(inputs.len());
+        let mut answered = false;
+        while outcomes.len() < inputs.len() {
+            let done = outcomes.len();
+            if self.run_until_failure(fst, args, &inputs[done..], timeout, &mut outcomes)? {
+                answered = true;
+            } else {
+                give_up(&inputs[outcomes.len()..], answered, &mut outcomes)?;
+            }
+        }
+
+        debug!(
+            "{}",
+            t_args!("debug-batch-completed",
+                "inputs" => inputs.len(),
+                "results" => outcomes.iter().flatten().map(|r| r.len()).sum::<usize>()
+            )
+        );
+        Ok(outcomes)
+    }
+
+    /// The lookup process is driven with blocking IO, so it runs off the async runtime
+    async fn lookup_blocking(&self, fst: String, args: Vec<String>, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let backend = self.clone();
+        let inputs = inputs.to_vec();
+        tokio::task::spawn_blocking(move || backend.run_lookup_batch(&fst, &args, &inputs)).await?
+    }
+
+    /// Look up inputs in a single process, adding an outcome per input until
+    /// all are answered or one of them times out or ends the process. Returns
+    /// whether the process answered any input before failing.
+    fn run_until_failure(
+        &self,
+        fst: &str,
+        args: &[String],
+        inputs: &[String],
+        timeout: Duration,
+        outcomes: &mut Vec<LookupOutcome>,
+    ) -> Result<bool> {
         let mut cmd = Command::new(&self.lookup_cmd);
-        cmd.arg(fst)
+        cmd.args(args)
+            .arg(fst)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(if self.quiet {
@@ -50,171 +187,180 @@ impl ExternalBackend {
         let mut child = cmd
             .spawn()
             .with_context(|| t_args!("backend-failed-to-start", "cmd" => &self.lookup_cmd))?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!(t!("backend-missing-stdin")))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!(t!("backend-missing-stdout")))?;
 
-        // Send all inputs at once
-        {
-            let stdin = child
-                .stdin
-                .as_mut()
-                .ok_or_else(|| anyhow!(t!("backend-missing-stdin")))?;
-
-            for input in inputs {
-                let input_trimmed = input.trim();
-                stdin.write_all(input_trimmed.as_bytes())?;
-                stdin.write_all(b"\n")?;
-            }
-            // Drop stdin to close it and signal EOF
-        }
-
-        // Wait for completion with timeout
-        match child.wait_timeout(timeout)? {
-            Some(status) => {
-                if !status.success() {
-                    return Err(anyhow!(
-                        t_args!("backend-process-failed", "status" => status)
-                    ));
+        // Empty lines get no record from some tools, so they are not sent
+        let lines: Vec<String> = inputs
+            .iter()
+            .map(|input| input.trim().to_string())
+            .filter(|input| !input.is_empty())
+            .collect();
+        // Write and read on separate threads so neither pipe can fill up and block,
+        // and so reading can give up on an input that takes too long
+        std::thread::spawn(move || {
+            for line in lines {
+                if writeln!(stdin, "{line}").is_err() {
+                    break;
                 }
             }
-            None => {
-                let _ = child.kill();
-                let _ = child.wait();
-                return Err(anyhow!(
-                    t_args!("backend-timeout", "seconds" => timeout.as_secs())
-                ));
+            // Dropping stdin closes it and signals EOF
+        });
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
             }
-        }
+        });
 
-        let out = child.wait_with_output()?;
-        if !out.status.success() {
-            let stderr = String::from_utf8_lossy(&out.stderr);
-            return Err(anyhow!(t_args!("backend-process-failed-stderr",
-                "status" => out.status,
-                "stderr" => stderr.as_ref()
-            )));
-        }
-
-        // Parse batch output - FST tools output: input\toutput format
-        let stdout = String::from_utf8_lossy(&out.stdout);
-        let mut results_map: IndexMap<String, std::collections::BTreeSet<String>> = IndexMap::new();
-
-        // Initialize all inputs in the map to preserve order and handle no-result cases
+        let mut answered = false;
         for input in inputs {
-            results_map.insert(input.trim().to_string(), std::collections::BTreeSet::new());
-        }
-
-        for raw_line in stdout.lines() {
-            let trimmed = raw_line.trim();
-            if trimmed.is_empty() {
+            let input = input.trim();
+            if input.is_empty() {
+                outcomes.push(Ok(vec![]));
                 continue;
             }
-            // Skip comments and warnings (only if the line doesn't contain a tab, meaning it's not FST output)
-            if (trimmed.starts_with('!') || trimmed.starts_with('#')) && !trimmed.contains('\t') {
-                continue;
-            }
-            // Skip HFST warnings and messages
-            if trimmed.starts_with("/usr/local/bin/hfst-lookup: Warning:") ||
-               trimmed.starts_with("Using HFST basic transducer format") ||
-               trimmed.starts_with("Using ") ||
-               trimmed.starts_with("> ") ||
-               trimmed == ">" {
-                continue;
-            }
-
-            let cols: Vec<&str> = raw_line.split('\t').collect();
-            if cols.len() >= 2 {
-                let input = cols[0].trim().to_string();
-                let output = cols[1].trim().to_string();
-
-                // Handle +inf (no result) marker
-                if output == "+inf" {
-                    // Input with no results - already initialized as empty Set
-                    continue;
+            match read_record(&rx, input, timeout) {
+                Ok(outputs) => {
+                    outcomes.push(Ok(outputs));
+                    answered = true;
                 }
-
-                // Handle cases where FST couldn't process: input==output with +? in third column
-                if cols.len() >= 3 && input == output && cols[2].trim().contains("+?") {
-                    // FST failed to generate/analyze - treat as no result
-                    continue;
-                }
-
-                if !output.is_empty() && output != "@" {
-                    if let Some(results) = results_map.get_mut(&input) {
-                        results.insert(output);
-                    }
+                Err(reason) => {
+                    outcomes.push(Err(reason));
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Ok(answered);
                 }
             }
         }
 
-        // Build ordered results matching input order
-        let mut all_results = Vec::new();
-        for input in inputs {
-            let input_key = input.trim().to_string();
-            let results_set = results_map.get(&input_key).cloned().unwrap_or_default();
-            let results: Vec<String> = results_set.into_iter().collect();
-            all_results.push(results);
+        // Every input has its answer by now, so a failing exit only gets a warning
+        let status = child.wait()?;
+        if !status.success() {
+            warn!("{}", t_args!("backend-process-failed", "status" => status));
+        }
+        Ok(true)
+    }
+}
+
+/// Wait for the record answering `input`, giving up after `timeout`
+fn read_record(
+    rx: &mpsc::Receiver<std::io::Result<String>>,
+    input: &str,
+    timeout: Duration,
+) -> LookupOutcome {
+    let deadline = Instant::now() + timeout;
+    let mut record = Record::default();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok(Ok(line)) => {
+                if record.push_line(&line) {
+                    return Ok(record.into_outputs());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                return Err(t_args!("backend-input-timeout",
+                    "seconds" => timeout.as_secs_f64(),
+                    "input" => input
+                ));
+            }
+            // A last record may end with the output instead of a blank line
+            Err(mpsc::RecvTimeoutError::Disconnected) if record.is_started() => {
+                return Ok(record.into_outputs());
+            }
+            Ok(Err(_)) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(t_args!("backend-process-ended", "input" => input));
+            }
         }
+    }
+}
 
-        debug!(
-            "{}",
-            t_args!("debug-batch-completed",
-                "inputs" => inputs.len(),
-                "results" => all_results.iter().map(|r| r.len()).sum::<usize>()
-            )
-        );
-        Ok(all_results)
+/// Called when a fresh lookup process failed before answering its first input.
+/// That points to a broken transducer or tool, which every process after it
+/// would fail on as well, each after up to a timeout, so none is started. The
+/// batch fails if nothing in it was answered, and otherwise the inputs still
+/// to be looked up do.
+pub(crate) fn give_up(remaining: &[String], answered: bool, outcomes: &mut Vec<LookupOutcome>) -> Result<()> {
+    let reason = match outcomes.last() {
+        Some(Err(reason)) => reason.clone(),
+        _ => String::new(),
+    };
+    if !answered {
+        return Err(anyhow!(t_args!("backend-gave-up", "error" => reason)));
     }
+    outcomes.extend(
+        remaining
+            .iter()
+            .map(|input| Err(t_args!("backend-not-looked-up", "input" => input.trim(), "error" => &reason))),
+    );
+    Ok(())
 }
 
+#[async_trait]
 impl Backend for ExternalBackend {
-    fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
         let fst = self
             .analyzer_fst
-            .as_ref()
+            .clone()
             .ok_or_else(|| anyhow!(t!("backend-analyzer-not-set")))?;
-        self.run_lookup_batch(fst, inputs)
+        self.lookup_blocking(fst, self.args.analyze.clone(), inputs).await
     }
 
-    fn generate_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
         let fst = self
             .generator_fst
-            .as_ref()
+            .clone()
             .ok_or_else(|| anyhow!(t!("backend-generator-not-set")))?;
-        self.run_lookup_batch(fst, inputs)
+        self.lookup_blocking(fst, self.args.generate.clone(), inputs).await
     }
 
-    fn validate(&self) -> Result<()> {
-        // Check if lookup command exists and is executable
-        use std::process::Command;
+    async fn validate(&self) -> Result<()> {
+        check_command(&self.lookup_cmd)?;
+        let fsts = [&self.generator_fst, &self.analyzer_fst];
+        check_fsts(&self.lookup_cmd, fsts.into_iter().flatten().map(String::as_str))
+    }
+}
 
-        let mut cmd = Command::new(&self.lookup_cmd);
-        cmd.arg("--help")
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped());
-
-        match cmd.spawn() {
-            Ok(mut child) => {
-                match child.wait() {
-                    Ok(_status) => {
-                        // Command exists and ran, don't care about exit code for --help
-                        Ok(())
-                    }
-                    Err(e) => Err(anyhow!(t_args!("backend-command-not-executable",
-                        "cmd" => &self.lookup_cmd,
-                        "error" => &e
-                    ))),
+/// Check that a lookup command exists and is executable
+pub(crate) fn check_command(lookup_cmd: &str) -> Result<()> {
+    let mut cmd = Command::new(lookup_cmd);
+    cmd.arg("--help")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    match cmd.spawn() {
+        Ok(mut child) => {
+            match child.wait() {
+                Ok(_status) => {
+                    // Command exists and ran, don't care about exit code for --help
+                    Ok(())
                 }
+                Err(e) => Err(anyhow!(t_args!("backend-command-not-executable",
+                    "cmd" => lookup_cmd,
+                    "error" => &e
+                ))),
             }
-            Err(e) => {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    Err(anyhow!(
-                        t_args!("backend-command-not-found", "cmd" => &self.lookup_cmd)
-                    ))
-                } else {
-                    Err(anyhow!(t_args!("backend-command-error",
-                        "cmd" => &self.lookup_cmd,
-                        "error" => &e
-                    )))
-                }
+        }
+        Err(e) => {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                Err(anyhow!(
+                    t_args!("backend-command-not-found", "cmd" => lookup_cmd)
+                ))
+            } else {
+                Err(anyhow!(t_args!("backend-command-error",
+                    "cmd" => lookup_cmd,
+                    "error" => &e
+                )))
             }
         }
     }
@@ -0,0 +1,249 @@
+use crate::backend::{Backend, LookupArgs};
+use crate::types::{Direction, LookupOutcome, LookupOutput};
+use crate::{t, t_args};
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::{debug, warn};
+
+/// Where lookup results are kept when no cache directory is given
+pub fn default_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")))
+}
+
+pub(crate) fn hex_digest(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Test groups sharing a transducer share its cache file, so saves are taken
+/// one at a time and each merges in what the others wrote
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Numbers the temporary files of saves, which may overlap across files
+static SAVE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Lookup results for one transducer, lookup command and direction, stored in
+/// a single file named after the hash of all three (the command including its
+/// arguments). The transducer is hashed by content, so a rebuilt FST gets a
+/// fresh cache while an unchanged one keeps its results however often the test
+/// files are edited.
+pub struct LookupCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, Vec<LookupOutput>>>,
+}
+
+impl LookupCache {
+    pub fn open(
+        dir: &Path,
+        lookup_cmd: &str,
+        args: &[String],
+        fst: &str,
+        direction: Direction,
+    ) -> Result<Self> {
+        let data = std::fs::read(fst)
+            .with_context(|| t_args!("cache-failed-to-hash", "file" => fst))?;
+        let key = format!(
+            "{}\0{}\0{}\0{}\0{:?}",
+            env!("CARGO_PKG_VERSION"),
+            lookup_cmd,
+            args.join("\0"),
+            hex_digest(&data),
+            direction
+        );
+        let path = dir.join(format!("{}.json", hex_digest(key.as_bytes())));
+
+        // A missing or unreadable cache file just means starting over
+        let entries = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                warn!("{}", t_args!("cache-failed-to-read", "file" => path.display(), "error" => e));
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        debug!(
+            "{}",
+            t_args!("debug-cache-open",
+                "file" => path.display(),
+                "count" => entries.len()
+            )
+        );
+
+        Ok(Self {
+            path,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Results already known for the inputs, and the inputs still to be looked up
+    fn split(&self, inputs: &[String]) -> (Vec<Option<Vec<LookupOutput>>>, Vec<String>) {
+        let entries = self.entries.lock().unwrap();
+        let known: Vec<Option<Vec<LookupOutput>>> = inputs
+            .iter()
+            .map(|input| entries.get(input).cloned())
+            .collect();
+        let missing = inputs
+            .iter()
+            .zip(&known)
+            .filter(|(_, k)| k.is_none())
+            .map(|(input, _)| input.clone())
+            .collect();
+        (known, missing)
+    }
+
+    /// Fill in the looked up results, remembering the successful ones
+    fn merge(
+        &self,
+        known: Vec<Option<Vec<LookupOutput>>>,
+        missing: Vec<String>,
+        looked_up: Vec<LookupOutcome>,
+    ) -> Result<Vec<LookupOutcome>> {
+        debug!(
+            "{}",
+            t_args!("debug-cache-hits",
+                "hits" => known.len() - missing.len(),
+                "total" => known.len()
+            )
+        );
+        if missing.is_empty() {
+            return Ok(known.into_iter().flatten().map(Ok).collect());
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        let mut looked_up = missing.into_iter().zip(looked_up);
+        let mut outcomes = Vec::with_capacity(known.len());
+        for k in known {
+            match k {
+                Some(outputs) => outcomes.push(Ok(outputs)),
+                None => {
+                    let (input, outcome) = looked_up
+                        .next()
+                        .ok_or_else(|| anyhow!(t!("cache-missing-results")))?;
+                    // Timeouts and crashes are not cached, they may not happen next time
+                    if let Ok(outputs) = &outcome {
+                        entries.insert(input, outputs.clone());
+                    }
+                    outcomes.push(outcome);
+                }
+            }
+        }
+        self.save(&mut entries);
+        Ok(outcomes)
+    }
+
+    /// Write the cache file, keeping the entries other groups saved to it
+    /// meanwhile. Failing to do so only costs speed on the next run, so it is a
+    /// warning rather than an error.
+    fn save(&self, entries: &mut HashMap<String, Vec<LookupOutput>>) {
+        let _lock = SAVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let result = (|| -> Result<()> {
+            if let Some(dir) = self.path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let on_disk: HashMap<String, Vec<LookupOutput>> = std::fs::read(&self.path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+                .unwrap_or_default();
+            for (input, outputs) in on_disk {
+                entries.entry(input).or_insert(outputs);
+            }
+            // Write to a temporary file first so an interrupted run cannot leave
+            // a half-written cache behind
+            let tmp = self.path.with_extension(format!(
+                "{}-{}.tmp",
+                std::process::id(),
+                SAVE_COUNT.fetch_add(1, Ordering::Relaxed)
+            ));
+            std::fs::write(&tmp, serde_json::to_vec(&*entries)?)?;
+            std::fs::rename(&tmp, &self.path)?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            warn!(
+                "{}",
+                t_args!("cache-failed-to-write",
+                    "file" => self.path.display(),
+                    "error" => e
+                )
+            );
+        }
+    }
+}
+
+/// Wraps a backend and answers inputs from the on-disk cache where possible
+pub struct CachedBackend<B> {
+    pub inner: B,
+    analyzer: Option<LookupCache>,
+    generator: Option<LookupCache>,
+}
+
+impl<B> CachedBackend<B> {
+    pub fn new(
+        inner: B,
+        dir: &Path,
+        lookup_cmd: &str,
+        args: &LookupArgs,
+        analyzer_fst: Option<&str>,
+        generator_fst: Option<&str>,
+    ) -> Result<Self> {
+        let analyzer = analyzer_fst
+            .map(|fst| LookupCache::open(dir, lookup_cmd, &args.analyze, fst, Direction::Analyze))
+            .transpose()?;
+        let generator = generator_fst
+            .map(|fst| LookupCache::open(dir, lookup_cmd, &args.generate, fst, Direction::Generate))
+            .transpose()?;
+        Ok(Self {
+            inner,
+            analyzer,
+            generator,
+        })
+    }
+
+    /// Pass every lookup straight through to the wrapped backend
+    pub fn uncached(inner: B) -> Self {
+        Self {
+            inner,
+            analyzer: None,
+            generator: None,
+        }
+    }
+}
+
+#[async_trait]
+impl<B: Backend> Backend for CachedBackend<B> {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let Some(cache) = &self.analyzer else {
+            return self.inner.analyze_batch(inputs).await;
+        };
+        let (known, missing) = cache.split(inputs);
+        let looked_up = if missing.is_empty() {
+            vec![]
+        } else {
+            self.inner.analyze_batch(&missing).await?
+        };
+        cache.merge(known, missing, looked_up)
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let Some(cache) = &self.generator else {
+            return self.inner.generate_batch(inputs).await;
+        };
+        let (known, missing) = cache.split(inputs);
+        let looked_up = if missing.is_empty() {
+            vec![]
+        } else {
+            self.inner.generate_batch(&missing).await?
+        };
+        cache.merge(known, missing, looked_up)
+    }
+
+    async fn validate(&self) -> Result<()> {
+        self.inner.validate().await
+    }
+}
@@ -0,0 +1,321 @@
+use crate::apertium::{analyses, analyzer_input, generations, generator_input};
+use crate::backend::{Backend, DEFAULT_TIMEOUT, LookupArgs, Record, check_command, give_up};
+use crate::format::check_fsts;
+use crate::types::{Direction, LookupOutcome, LookupOutput};
+use crate::{t, t_args};
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use regex::Regex;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// How the output of a command is read
+#[derive(Debug, Clone)]
+pub enum OutputParser {
+    /// `input<TAB>output[<TAB>weight]` lines and a blank line after each
+    /// input, as from hfst-lookup and flookup
+    Tabular,
+    /// One line of Apertium stream format per input, as from lt-proc
+    Apertium,
+    /// One output per line and a blank line after each input
+    Lines,
+    /// Lines matching a pattern with an `output` group, and optionally a
+    /// `weight` group, and a blank line after each input. Other lines are skipped.
+    Regex(Regex),
+}
+
+impl OutputParser {
+    /// The parser called `name` in a spec; a pattern alone means `regex`
+    pub fn from_config(name: Option<&str>, pattern: Option<&str>) -> Result<Self> {
+        let name = name.map(|n| n.trim().to_lowercase());
+        match (name.as_deref(), pattern) {
+            (Some("regex"), Some(pattern)) | (None, Some(pattern)) => {
+                let re = Regex::new(pattern)
+                    .with_context(|| t_args!("command-invalid-pattern", "pattern" => pattern))?;
+                if !re.capture_names().any(|group| group == Some("output")) {
+                    return Err(anyhow!(t_args!("command-pattern-without-output", "pattern" => pattern)));
+                }
+                Ok(OutputParser::Regex(re))
+            }
+            (Some("regex"), None) => Err(anyhow!(t!("command-missing-pattern"))),
+            (Some("hfst" | "xerox" | "tabular") | None, None) => Ok(OutputParser::Tabular),
+            (Some("apertium"), None) => Ok(OutputParser::Apertium),
+            (Some("lines"), None) => Ok(OutputParser::Lines),
+            (Some(other), _) => Err(anyhow!(t_args!("command-unknown-parser", "name" => other))),
+        }
+    }
+
+    /// The name of the parser in a spec, with the pattern of a `regex` one
+    pub fn name(&self) -> String {
+        match self {
+            OutputParser::Tabular => "tabular".to_string(),
+            OutputParser::Apertium => "apertium".to_string(),
+            OutputParser::Lines => "lines".to_string(),
+            OutputParser::Regex(re) => format!("regex {}", re.as_str()),
+        }
+    }
+}
+
+/// Put the transducer and direction into a command template. The
+/// transducer goes last when the template has no place for it.
+pub fn fill_template(template: &[String], fst: &str, direction: &Direction) -> Vec<String> {
+    let direction = match direction {
+        Direction::Analyze => "analyze",
+        Direction::Generate => "generate",
+    };
+    let mut args: Vec<String> = template
+        .iter()
+        .map(|arg| arg.replace("{fst}", fst).replace("{direction}", direction))
+        .collect();
+    if !template.iter().any(|arg| arg.contains("{fst}")) {
+        args.push(fst.to_string());
+    }
+    args
+}
+
+/// Collects the outputs for one input as the command's lines arrive
+struct Collector<'a> {
+    parser: &'a OutputParser,
+    generate: bool,
+    started: bool,
+    record: Record,
+}
+
+impl Collector<'_> {
+    /// Add a line of output, returning true once the input is answered
+    fn push_line(&mut self, line: &str) -> bool {
+        let blank = line.trim().is_empty();
+        match self.parser {
+            OutputParser::Tabular => return self.record.push_line(line),
+            OutputParser::Apertium => {
+                let forms = if self.generate { generations(line) } else { analyses(line) };
+                for form in forms {
+                    self.record.add(&form, None);
+                }
+                return true;
+            }
+            OutputParser::Lines if !blank => self.record.add(line.trim(), None),
+            OutputParser::Regex(re) if !blank => {
+                if let Some(caps) = re.captures(line)
+                    && let Some(output) = caps.name("output")
+                {
+                    let weight = caps.name("weight").and_then(|w| w.as_str().trim().parse().ok());
+                    self.record.add(output.as_str().trim(), weight);
+                }
+            }
+            OutputParser::Lines | OutputParser::Regex(_) => return true,
+        }
+        self.started = true;
+        false
+    }
+
+    fn is_started(&self) -> bool {
+        self.started || self.record.is_started()
+    }
+}
+
+/// Backend that runs a command made from a template, such as a wrapper
+/// script, and reads its output with the configured parser. Inputs go to the
+/// command's standard input, one per line.
+#[derive(Debug, Clone)]
+pub struct CommandBackend {
+    pub program: String,
+    /// The rest of the template, with `{fst}` and `{direction}` placeholders
+    pub args: LookupArgs,
+    pub parser: OutputParser,
+    pub generator_fst: Option<String>,
+    pub analyzer_fst: Option<String>,
+    pub timeout: Option<Duration>, // per input
+    pub quiet: bool, // suppress stderr from the command when true
+}
+
+impl CommandBackend {
+    fn run_batch(&self, fst: &str, direction: Direction, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        debug!(
+            "{}",
+            t_args!("debug-batch-lookup",
+                "count" => inputs.len(),
+                "fst" => fst
+            )
+        );
+
+        // As with the lookup tools, an input that hangs or crashes the
+        // command fails on its own, and the rest go to a fresh process
+        let mut outcomes = Vec::with_capacity(inputs.len());
+        let mut answered = false;
+        while outcomes.len() < inputs.len() {
+            let done = outcomes.len();
+            if self.run_until_failure(fst, &direction, &inputs[done..], timeout, &mut outcomes)? {
+                answered = true;
+            } else {
+                give_up(&inputs[outcomes.len()..], answered, &mut outcomes)?;
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Look up inputs in a single process until one fails, returning whether
+    /// the process answered any input before that
+    fn run_until_failure(
+        &self,
+        fst: &str,
+        direction: &Direction,
+        inputs: &[String],
+        timeout: Duration,
+        outcomes: &mut Vec<LookupOutcome>,
+    ) -> Result<bool> {
+        let generate = matches!(direction, Direction::Generate);
+        let template = if generate { &self.args.generate } else { &self.args.analyze };
+        let mut cmd = Command::new(&self.program);
+        cmd.args(fill_template(template, fst, direction))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(if self.quiet {
+                Stdio::null()
+            } else {
+                Stdio::inherit()
+            });
+
+        let mut child = cmd
+            .spawn()
+            .with_context(|| t_args!("backend-failed-to-start", "cmd" => &self.program))?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!(t!("backend-missing-stdin")))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!(t!("backend-missing-stdout")))?;
+
+        let apertium = matches!(self.parser, OutputParser::Apertium);
+        let lines: Vec<String> = inputs
+            .iter()
+            .map(|input| input.trim())
+            .filter(|input| !input.is_empty())
+            .map(|input| match (apertium, generate) {
+                (true, true) => generator_input(input),
+                (true, false) => analyzer_input(input),
+                (false, _) => input.to_string(),
+            })
+            .collect();
+        std::thread::spawn(move || {
+            for line in lines {
+                if writeln!(stdin, "{line}").is_err() {
+                    break;
+                }
+            }
+        });
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut answered = false;
+        for input in inputs {
+            let input = input.trim();
+            if input.is_empty() {
+                outcomes.push(Ok(vec![]));
+                continue;
+            }
+            let collector = Collector {
+                parser: &self.parser,
+                generate,
+                started: false,
+                record: Record::default(),
+            };
+            match read_outputs(&rx, collector, input, timeout) {
+                Ok(outputs) => {
+                    outcomes.push(Ok(outputs));
+                    answered = true;
+                }
+                Err(reason) => {
+                    outcomes.push(Err(reason));
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Ok(answered);
+                }
+            }
+        }
+
+        // Every input has its answer by now, so a failing exit only gets a warning
+        let status = child.wait()?;
+        if !status.success() {
+            warn!("{}", t_args!("backend-process-failed", "status" => status));
+        }
+        Ok(true)
+    }
+
+    /// The command is driven with blocking IO, so it runs off the async runtime
+    async fn lookup_blocking(&self, fst: String, direction: Direction, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let backend = self.clone();
+        let inputs = inputs.to_vec();
+        tokio::task::spawn_blocking(move || backend.run_batch(&fst, direction, &inputs)).await?
+    }
+}
+
+/// Wait for the lines answering `input`, giving up after `timeout`
+fn read_outputs(
+    rx: &mpsc::Receiver<std::io::Result<String>>,
+    mut collector: Collector,
+    input: &str,
+    timeout: Duration,
+) -> Result<Vec<LookupOutput>, String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok(Ok(line)) => {
+                if collector.push_line(&line) {
+                    return Ok(collector.record.into_outputs());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                return Err(t_args!("backend-input-timeout",
+                    "seconds" => timeout.as_secs_f64(),
+                    "input" => input
+                ));
+            }
+            // The last answer may end with the output instead of a blank line
+            Err(mpsc::RecvTimeoutError::Disconnected) if collector.is_started() => {
+                return Ok(collector.record.into_outputs());
+            }
+            Ok(Err(_)) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(t_args!("backend-process-ended", "input" => input));
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for CommandBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let fst = self
+            .analyzer_fst
+            .clone()
+            .ok_or_else(|| anyhow!(t!("backend-analyzer-not-set")))?;
+        self.lookup_blocking(fst, Direction::Analyze, inputs).await
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let fst = self
+            .generator_fst
+            .clone()
+            .ok_or_else(|| anyhow!(t!("backend-generator-not-set")))?;
+        self.lookup_blocking(fst, Direction::Generate, inputs).await
+    }
+
+    async fn validate(&self) -> Result<()> {
+        check_command(&self.program)?;
+        let fsts = [&self.generator_fst, &self.analyzer_fst];
+        check_fsts(&self.program, fsts.into_iter().flatten().map(String::as_str))
+    }
+}
@@ -1,9 +1,14 @@
 use crate::backend::Backend;
 use crate::report::calculate_counts;
-use crate::types::{CaseResult, Direction, Summary, TestSuite};
+use crate::types::{CaseResult, Direction, LookupOutcome, Relation, Summary, TestCase, TestSuite};
 use crate::{t, t_args};
+use anyhow::{Result, anyhow};
+use indexmap::IndexMap;
 use std::collections::BTreeSet;
 
+/// Lexical forms looked up to check that the transducers are the right way round
+const DIRECTION_CHECK_INPUTS: usize = 5;
+
 fn set_eq(a: &[String], b: &[String]) -> bool {
     let sa: BTreeSet<&str> = a.iter().map(|s| s.as_str()).collect();
     let sb: BTreeSet<&str> = b.iter().map(|s| s.as_str()).collect();
@@ -17,8 +22,9 @@ fn expected_subset_of_actual(actual: &[String], expected: &[String]) -> bool {
 }
 
 fn test_with_negatives(actual: &[String], expected: &[String], expected_not: &[String], ignore_extra_analyses: bool) -> bool {
-    // First check positive expectations
-    let positive_pass = if ignore_extra_analyses {
+    // First check positive expectations. A case with only unwanted outputs
+    // checks nothing else.
+    let positive_pass = if ignore_extra_analyses || (expected.is_empty() && !expected_not.is_empty()) {
         expected_subset_of_actual(actual, expected)
     } else {
         set_eq(actual, expected)
@@ -39,120 +45,138 @@ fn test_with_negatives(actual: &[String], expected: &[String], expected_not: &[S
     true
 }
 
-pub fn run_suites<B: Backend>(
-    backend: &B,
-    suites: &[TestSuite],
-    ignore_extra_analyses: bool,
-) -> Summary {
-    let mut all_cases = Vec::new();
-    for s in suites {
-        for c in &s.cases {
-            all_cases.push(c.clone());
-        }
+/// Result for a case whose lookup timed out or crashed the lookup process
+fn lookup_failed(case: &TestCase, reason: String) -> CaseResult {
+    CaseResult {
+        name: case.name.clone(),
+        direction: case.direction.clone(),
+        input: case.input.clone(),
+        expected: case.expect.clone(),
+        unwanted: case.expect_not.clone(),
+        actual: vec![],
+        outputs: vec![],
+        weight_checks: case.weight_checks.clone(),
+        error: Some(t_args!("engine-lookup-error", "error" => reason)),
+        passed: false,
     }
+}
 
-    // Group cases by direction for batch processing
-    let mut analyze_cases = Vec::new();
-    let mut generate_cases = Vec::new();
-
-    for (idx, case) in all_cases.iter().enumerate() {
-        match case.direction {
-            Direction::Analyze => analyze_cases.push((idx, case)),
-            Direction::Generate => generate_cases.push((idx, case)),
-        }
-    }
+/// The distinct inputs of the cases, and for each case where its input is among them.
+/// The same string often turns up in many groups and files, but is looked up once.
+fn dedup_inputs(cases: &[(usize, &TestCase)]) -> (Vec<String>, Vec<usize>) {
+    let mut index: IndexMap<&str, usize> = IndexMap::new();
+    let positions = cases
+        .iter()
+        .map(|(_, case)| {
+            let next = index.len();
+            *index.entry(case.input.as_str()).or_insert(next)
+        })
+        .collect();
+    (index.into_keys().map(str::to_string).collect(), positions)
+}
 
-    let mut results = vec![
-        CaseResult {
-            name: String::new(),
-            direction: Direction::Analyze,
-            input: String::new(),
-            expected: vec![],
-            actual: vec![],
-            error: Some(t!("engine-not-processed")),
-            passed: false,
-        };
-        all_cases.len()
-    ];
-
-    // Process analyze tests in batch
-    if !analyze_cases.is_empty() {
-        let inputs: Vec<String> = analyze_cases
-            .iter()
-            .map(|(_, case)| case.input.clone())
-            .collect();
-        match backend.analyze_batch(&inputs) {
-            Ok(batch_results) => {
-                for ((idx, case), actual) in analyze_cases.iter().zip(batch_results.iter()) {
-                    let passed = test_with_negatives(actual, &case.expect, &case.expect_not, ignore_extra_analyses);
-
-                    results[*idx] = CaseResult {
-                        name: case.name.clone(),
-                        direction: case.direction.clone(),
-                        input: case.input.clone(),
-                        expected: case.expect.clone(),
-                        actual: actual.clone(),
-                        error: None,
-                        passed,
-                    };
-                }
+/// Check the outcome of each case's lookup against its expectations
+fn check_outcomes(
+    results: &mut [CaseResult],
+    cases: &[(usize, &TestCase)],
+    positions: &[usize],
+    outcomes: Result<Vec<LookupOutcome>>,
+    ignore_extra_analyses: bool,
+    batch_error_key: &str,
+) {
+    match outcomes {
+        Ok(batch_results) => {
+            for ((idx, case), &pos) in cases.iter().zip(positions) {
+                results[*idx] = match &batch_results[pos] {
+                    Ok(outputs) => {
+                        let actual: Vec<String> = outputs
+                            .iter()
+                            .map(|o| o.output.clone())
+                            .collect::<BTreeSet<_>>()
+                            .into_iter()
+                            .collect();
+                        let passed = test_with_negatives(&actual, &case.expect, &case.expect_not, ignore_extra_analyses)
+                            && case.weight_checks.iter().all(|check| check.holds(outputs));
+                        CaseResult {
+                            name: case.name.clone(),
+                            direction: case.direction.clone(),
+                            input: case.input.clone(),
+                            expected: case.expect.clone(),
+                            unwanted: case.expect_not.clone(),
+                            actual,
+                            outputs: outputs.clone(),
+                            weight_checks: case.weight_checks.clone(),
+                            error: None,
+                            passed,
+                        }
+                    }
+                    Err(reason) => lookup_failed(case, reason.clone()),
+                };
             }
-            Err(e) => {
-                // Mark all analyze tests as failed due to batch error
-                for (idx, case) in &analyze_cases {
-                    results[*idx] = CaseResult {
-                        name: case.name.clone(),
-                        direction: case.direction.clone(),
-                        input: case.input.clone(),
-                        expected: case.expect.clone(),
-                        actual: vec![],
-                        error: Some(t_args!("engine-batch-analyze-error", "error" => &e)),
-                        passed: false,
-                    };
-                }
+        }
+        Err(e) => {
+            // Mark all tests in this direction as failed due to batch error
+            for (idx, case) in cases {
+                results[*idx] = CaseResult {
+                    name: case.name.clone(),
+                    direction: case.direction.clone(),
+                    input: case.input.clone(),
+                    expected: case.expect.clone(),
+                    unwanted: case.expect_not.clone(),
+                    actual: vec![],
+                    outputs: vec![],
+                    weight_checks: case.weight_checks.clone(),
+                    error: Some(t_args!(batch_error_key, "error" => &e)),
+                    passed: false,
+                };
             }
         }
     }
+}
 
-    // Process generate tests in batch
-    if !generate_cases.is_empty() {
-        let inputs: Vec<String> = generate_cases
-            .iter()
-            .map(|(_, case)| case.input.clone())
-            .collect();
-        match backend.generate_batch(&inputs) {
-            Ok(batch_results) => {
-                for ((idx, case), actual) in generate_cases.iter().zip(batch_results.iter()) {
-                    let passed = test_with_negatives(actual, &case.expect, &case.expect_not, false);
-
-                    results[*idx] = CaseResult {
-                        name: case.name.clone(),
-                        direction: case.direction.clone(),
-                        input: case.input.clone(),
-                        expected: case.expect.clone(),
-                        actual: actual.clone(),
-                        error: None,
-                        passed,
-                    };
-                }
-            }
-            Err(e) => {
-                // Mark all generate tests as failed due to batch error
-                for (idx, case) in &generate_cases {
-                    results[*idx] = CaseResult {
-                        name: case.name.clone(),
-                        direction: case.direction.clone(),
-                        input: case.input.clone(),
-                        expected: case.expect.clone(),
-                        actual: vec![],
-                        error: Some(t_args!("engine-batch-generate-error", "error" => &e)),
-                        passed: false,
-                    };
-                }
+/// Cases of all suites, split by direction, with placeholders for their results
+struct PendingCases<'a> {
+    analyze: Vec<(usize, &'a TestCase)>,
+    generate: Vec<(usize, &'a TestCase)>,
+    results: Vec<CaseResult>,
+}
+
+impl<'a> PendingCases<'a> {
+    fn new(suites: &'a [TestSuite]) -> Self {
+        // Group cases by direction for batch processing
+        let mut analyze = Vec::new();
+        let mut generate = Vec::new();
+        for (idx, case) in suites.iter().flat_map(|s| &s.cases).enumerate() {
+            match case.direction {
+                Direction::Analyze => analyze.push((idx, case)),
+                Direction::Generate => generate.push((idx, case)),
             }
         }
+
+        let results = vec![
+            CaseResult {
+                name: String::new(),
+                direction: Direction::Analyze,
+                input: String::new(),
+                expected: vec![],
+                unwanted: vec![],
+                actual: vec![],
+                outputs: vec![],
+                weight_checks: vec![],
+                error: Some(t!("engine-not-processed")),
+                passed: false,
+            };
+            analyze.len() + generate.len()
+        ];
+        Self {
+            analyze,
+            generate,
+            results,
+        }
     }
+}
 
+fn summarize(results: Vec<CaseResult>, ignore_extra_analyses: bool) -> Summary {
     let passed = results.iter().filter(|r| r.passed).count();
     let failed = results.len() - passed;
 
@@ -172,7 +196,156 @@ pub fn run_suites<B: Backend>(
     }
 }
 
-// With batch processing, parallelization is less critical since we only spawn
-// 1-2 subprocesses per suite (analyze + generate). We can add chunked parallel
-// batching later if needed, but the batch processing alone should provide
-// the main performance improvement.
+/// Split the results of all suites' cases into a summary per suite
+fn summarize_each(
+    suites: &[TestSuite],
+    results: Vec<CaseResult>,
+    ignore_extra_analyses: bool,
+) -> Vec<Summary> {
+    let mut results = results.into_iter();
+    suites
+        .iter()
+        .map(|suite| {
+            let cases = results.by_ref().take(suite.cases.len()).collect();
+            summarize(cases, ignore_extra_analyses)
+        })
+        .collect()
+}
+
+/// Look up the inputs, unless there are none. A backend that does not answer
+/// every input fails the batch, as its outcomes cannot be matched to inputs.
+async fn lookup_inputs<'a>(
+    inputs: &'a [String],
+    lookup: impl Future<Output = Result<Vec<LookupOutcome>>> + 'a,
+) -> Result<Vec<LookupOutcome>> {
+    if inputs.is_empty() {
+        return Ok(Vec::new());
+    }
+    let outcomes = lookup.await?;
+    if outcomes.len() != inputs.len() {
+        return Err(anyhow!(t_args!("engine-wrong-result-count",
+            "got" => outcomes.len(),
+            "expected" => inputs.len()
+        )));
+    }
+    Ok(outcomes)
+}
+
+async fn check_suites<B: Backend + ?Sized>(
+    backend: &B,
+    suites: &[TestSuite],
+    ignore_extra_analyses: bool,
+) -> Vec<CaseResult> {
+    let mut pending = PendingCases::new(suites);
+    let (analyze_inputs, analyze_positions) = dedup_inputs(&pending.analyze);
+    let (generate_inputs, generate_positions) = dedup_inputs(&pending.generate);
+
+    // Process analyze and generate batches concurrently
+    let (analyze_result, generate_result) = futures::future::join(
+        lookup_inputs(&analyze_inputs, backend.analyze_batch(&analyze_inputs)),
+        lookup_inputs(&generate_inputs, backend.generate_batch(&generate_inputs)),
+    )
+    .await;
+
+    check_outcomes(
+        &mut pending.results,
+        &pending.analyze,
+        &analyze_positions,
+        analyze_result,
+        ignore_extra_analyses,
+        "engine-batch-analyze-error",
+    );
+    check_outcomes(
+        &mut pending.results,
+        &pending.generate,
+        &generate_positions,
+        generate_result,
+        false,
+        "engine-batch-generate-error",
+    );
+
+    pending.results
+}
+
+/// A lexical form of the suites that the analyser answers while the
+/// generator answered none of them in the run, as happens when the two are
+/// swapped. Lookups that failed tell nothing, so they count as no answer
+/// either. The analyser is only asked when no generation case got an output.
+pub async fn swapped_direction_hint<B: Backend + ?Sized>(backend: &B, summaries: &[Summary]) -> Option<String> {
+    let generations: Vec<&CaseResult> = summaries
+        .iter()
+        .flat_map(|summary| &summary.cases)
+        .filter(|case| case.direction == Direction::Generate)
+        .collect();
+    if generations.is_empty() || generations.iter().any(|case| case.error.is_none() && !case.outputs.is_empty()) {
+        return None;
+    }
+    let lexical: Vec<String> = generations
+        .iter()
+        .map(|case| case.input.clone())
+        .collect::<indexmap::IndexSet<_>>()
+        .into_iter()
+        .take(DIRECTION_CHECK_INPUTS)
+        .collect();
+    let analysed = backend.analyze_batch(&lexical).await.ok()?;
+    lexical
+        .into_iter()
+        .zip(analysed)
+        .find(|(_, outcome)| outcome.as_ref().is_ok_and(|outputs| !outputs.is_empty()))
+        .map(|(input, _)| input)
+}
+
+pub async fn run_suites<B: Backend + ?Sized>(
+    backend: &B,
+    suites: &[TestSuite],
+    ignore_extra_analyses: bool,
+) -> Summary {
+    let results = check_suites(backend, suites, ignore_extra_analyses).await;
+    summarize(results, ignore_extra_analyses)
+}
+
+/// Like `run_suites`, but with a summary for each suite. Inputs shared between
+/// suites are still looked up only once.
+pub async fn run_suites_separately<B: Backend + ?Sized>(
+    backend: &B,
+    suites: &[TestSuite],
+    ignore_extra_analyses: bool,
+) -> Vec<Summary> {
+    let results = check_suites(backend, suites, ignore_extra_analyses).await;
+    summarize_each(suites, results, ignore_extra_analyses)
+}
+
+/// Check a relation between the analyses of two suites that have run, one
+/// case per surface form analysed in both. The analyses of the subset are
+/// what is expected of the superset, so extra analyses there are no failure.
+pub fn check_relation(relation: &Relation, superset: &Summary, subset: &Summary) -> Summary {
+    let analysed = |summary: &Summary| -> IndexMap<String, CaseResult> {
+        let mut cases = IndexMap::new();
+        for case in summary.cases.iter().filter(|c| c.direction == Direction::Analyze) {
+            cases.entry(case.input.clone()).or_insert_with(|| case.clone());
+        }
+        cases
+    };
+    let superset = analysed(superset);
+    let results = analysed(subset)
+        .into_values()
+        .filter_map(|sub| {
+            let sup = superset.get(&sub.input)?;
+            let error = sup.error.clone().or(sub.error);
+            let passed = error.is_none() && expected_subset_of_actual(&sup.actual, &sub.actual);
+            Some(CaseResult {
+                name: format!("{}: {}", relation.label, sub.input),
+                direction: Direction::Analyze,
+                input: sub.input,
+                expected: sub.actual,
+                unwanted: vec![],
+                actual: sup.actual.clone(),
+                outputs: sup.outputs.clone(),
+                weight_checks: vec![],
+                error,
+                passed,
+            })
+        })
+        .collect();
+    summarize(results, true)
+}
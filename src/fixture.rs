@@ -0,0 +1,244 @@
+use crate::backend::{Backend, LookupArgs};
+use crate::cache::hex_digest;
+use crate::command::OutputParser;
+use crate::types::{Direction, LookupOutcome, LookupOutput};
+use crate::t_args;
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Missing inputs named in the error when replaying
+const MISSING_SHOWN: usize = 10;
+
+/// The recorded lookups of one backend: the outputs for every input, per direction
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Lookups {
+    #[serde(default)]
+    pub analyze: BTreeMap<String, Vec<LookupOutput>>,
+    #[serde(default)]
+    pub generate: BTreeMap<String, Vec<LookupOutput>>,
+}
+
+impl Lookups {
+    fn entries(&self, direction: &Direction) -> &BTreeMap<String, Vec<LookupOutput>> {
+        match direction {
+            Direction::Analyze => &self.analyze,
+            Direction::Generate => &self.generate,
+        }
+    }
+
+    fn entries_mut(&mut self, direction: &Direction) -> &mut BTreeMap<String, Vec<LookupOutput>> {
+        match direction {
+            Direction::Analyze => &mut self.analyze,
+            Direction::Generate => &mut self.generate,
+        }
+    }
+}
+
+/// The key that the lookups of a backend are recorded under, made of what its
+/// raw outputs depend on. Transducers are named by a hash of their contents
+/// and the lookup tool by its file name, so that a fixture recorded in one
+/// directory or checkout replays in another.
+pub fn fixture_key(
+    backend: &str,
+    lookup_cmd: &str,
+    args: &LookupArgs,
+    parser: Option<&OutputParser>,
+    fsts: &[&str],
+) -> String {
+    let tool = Path::new(lookup_cmd)
+        .file_name()
+        .map_or_else(|| lookup_cmd.to_string(), |name| name.to_string_lossy().into_owned());
+    let fsts: Vec<String> = fsts.iter().map(|fst| fst_key(fst)).collect();
+    format!(
+        "{}__{}__{}__{}__{}__{}",
+        backend,
+        tool,
+        args.analyze.join(" "),
+        args.generate.join(" "),
+        parser.map(OutputParser::name).unwrap_or_default(),
+        fsts.join("__")
+    )
+}
+
+/// A transducer by the hash of its contents, or as given when it cannot be
+/// read, as with the URL of an HTTP backend
+fn fst_key(fst: &str) -> String {
+    match std::fs::read(fst) {
+        Ok(data) => hex_digest(&data),
+        Err(_) => fst.to_string(),
+    }
+}
+
+/// Recorded lookups, per backend: suites run against different transducers
+/// or lookup tools get different answers for the same input. Kept sorted so
+/// that a fixture checked in with the tests diffs well.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Fixture {
+    pub backends: BTreeMap<String, Lookups>,
+}
+
+impl Fixture {
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| t_args!("fixture-failed-to-read", "file" => path.display()))?;
+        let fixture: Self = serde_json::from_slice(&bytes)
+            .with_context(|| t_args!("fixture-failed-to-read", "file" => path.display()))?;
+        debug!(
+            "{}",
+            t_args!("debug-fixture-open",
+                "file" => path.display(),
+                "count" => fixture.backends.values().map(|l| l.analyze.len() + l.generate.len()).sum::<usize>()
+            )
+        );
+        Ok(fixture)
+    }
+
+    /// Write to a temporary file first so an interrupted run cannot leave a
+    /// half-written fixture behind
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let result = (|| -> Result<()> {
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+            std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        })();
+        result.with_context(|| t_args!("fixture-failed-to-write", "file" => path.display()))
+    }
+}
+
+/// A fixture being recorded, shared by every backend that records into it.
+/// It is written once, with [`Recorder::save`], when the run is over.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    path: PathBuf,
+    fixture: Arc<Mutex<Fixture>>,
+}
+
+impl Recorder {
+    /// Record into `path`, adding to what it already holds
+    pub fn open(path: &Path) -> Result<Self> {
+        let fixture = if path.exists() {
+            Fixture::load(path)?
+        } else {
+            Fixture::default()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            fixture: Arc::new(Mutex::new(fixture)),
+        })
+    }
+
+    /// Add the successful lookups of a backend. Timeouts and crashes are not
+    /// recorded, so replaying the input fails.
+    fn record(&self, backend: &str, direction: Direction, inputs: &[String], outcomes: &[LookupOutcome]) {
+        let mut fixture = self.fixture.lock().unwrap();
+        let entries = fixture.backends.entry(backend.to_string()).or_default().entries_mut(&direction);
+        for (input, outcome) in inputs.iter().zip(outcomes) {
+            if let Ok(outputs) = outcome {
+                entries.insert(input.clone(), outputs.clone());
+            }
+        }
+    }
+
+    /// Write the fixture with everything recorded so far
+    pub fn save(&self) -> Result<()> {
+        self.fixture.lock().unwrap().save(&self.path)
+    }
+}
+
+/// Wraps a backend and records every lookup it answers under `backend`,
+/// the key its lookups are replayed with
+pub struct RecordingBackend<B> {
+    pub inner: B,
+    recorder: Recorder,
+    backend: String,
+}
+
+impl<B> RecordingBackend<B> {
+    pub fn new(inner: B, recorder: Recorder, backend: impl Into<String>) -> Self {
+        Self {
+            inner,
+            recorder,
+            backend: backend.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl<B: Backend> Backend for RecordingBackend<B> {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let outcomes = self.inner.analyze_batch(inputs).await?;
+        self.recorder.record(&self.backend, Direction::Analyze, inputs, &outcomes);
+        Ok(outcomes)
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let outcomes = self.inner.generate_batch(inputs).await?;
+        self.recorder.record(&self.backend, Direction::Generate, inputs, &outcomes);
+        Ok(outcomes)
+    }
+
+    async fn validate(&self) -> Result<()> {
+        self.inner.validate().await
+    }
+}
+
+/// Answers lookups from the lookups a fixture recorded for one backend,
+/// without any transducer or lookup tool. An input that was not recorded
+/// fails the whole batch.
+#[derive(Debug)]
+pub struct ReplayBackend {
+    path: PathBuf,
+    lookups: Lookups,
+}
+
+impl ReplayBackend {
+    /// Replay the lookups recorded under `backend` in the fixture loaded from `path`
+    pub fn new(path: &Path, fixture: &Fixture, backend: &str) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            lookups: fixture.backends.get(backend).cloned().unwrap_or_default(),
+        }
+    }
+
+    fn replay(&self, direction: Direction, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let entries = self.lookups.entries(&direction);
+        let missing: Vec<&str> = inputs
+            .iter()
+            .map(String::as_str)
+            .filter(|input| !entries.contains_key(*input))
+            .collect();
+        if !missing.is_empty() {
+            return Err(anyhow!(t_args!("fixture-missing-inputs",
+                "count" => missing.len(),
+                "file" => self.path.display(),
+                "inputs" => missing.iter().take(MISSING_SHOWN).copied().collect::<Vec<_>>().join(", ")
+            )));
+        }
+        Ok(inputs.iter().map(|input| Ok(entries[input].clone())).collect())
+    }
+}
+
+#[async_trait]
+impl Backend for ReplayBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        self.replay(Direction::Analyze, inputs)
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        self.replay(Direction::Generate, inputs)
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+/// Flag diacritic operator, the letter in `@X.feature.value@`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagOp {
+    Positive,
+    Negative,
+    Require,
+    Disallow,
+    Clear,
+    Unify,
+}
+
+/// A parsed flag diacritic with interned feature and value numbers.
+/// Value 0 means "no value given" (e.g. `@R.Case@`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlagDiacritic {
+    pub op: FlagOp,
+    pub feature: usize,
+    pub value: i32,
+}
+
+/// Returns true if the symbol looks like a flag diacritic (`@P.x.y@`, `@C.x@`, ...)
+pub fn is_flag_diacritic(symbol: &str) -> bool {
+    split_flag(symbol).is_some()
+}
+
+fn split_flag(symbol: &str) -> Option<(FlagOp, &str, Option<&str>)> {
+    let inner = symbol.strip_prefix('@')?.strip_suffix('@')?;
+    let mut parts = inner.splitn(3, '.');
+    let op = match parts.next()? {
+        "P" => FlagOp::Positive,
+        "N" => FlagOp::Negative,
+        "R" => FlagOp::Require,
+        "D" => FlagOp::Disallow,
+        "C" => FlagOp::Clear,
+        "U" => FlagOp::Unify,
+        _ => return None,
+    };
+    let feature = parts.next().filter(|f| !f.is_empty())?;
+    let value = parts.next();
+    Some((op, feature, value))
+}
+
+/// Interns features and values of all flag diacritics in an alphabet
+#[derive(Debug, Default)]
+pub struct FlagTable {
+    features: HashMap<String, usize>,
+    values: HashMap<String, i32>,
+}
+
+impl FlagTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a symbol, returning `None` when it is not a flag diacritic
+    pub fn intern(&mut self, symbol: &str) -> Option<FlagDiacritic> {
+        let (op, feature, value) = split_flag(symbol)?;
+        let next_feature = self.features.len();
+        let feature = *self
+            .features
+            .entry(feature.to_string())
+            .or_insert(next_feature);
+        let value = match value {
+            Some(v) => {
+                let next_value = self.values.len() as i32 + 1;
+                *self.values.entry(v.to_string()).or_insert(next_value)
+            }
+            None => 0,
+        };
+        Some(FlagDiacritic { op, feature, value })
+    }
+
+    pub fn feature_count(&self) -> usize {
+        self.features.len()
+    }
+}
+
+impl FlagDiacritic {
+    /// Check the flag against the current feature state. Returns the new value
+    /// of the flag's feature when the path may continue, `None` when it is blocked.
+    /// A positive state value means "set to value", negative means "set to not value",
+    /// and 0 means unset.
+    pub fn apply(&self, state: &[i32]) -> Option<i32> {
+        let current = state[self.feature];
+        match self.op {
+            FlagOp::Positive => Some(self.value),
+            FlagOp::Negative => Some(-self.value),
+            FlagOp::Clear => Some(0),
+            FlagOp::Require => {
+                let ok = if self.value == 0 {
+                    current != 0
+                } else {
+                    current == self.value
+                };
+                ok.then_some(current)
+            }
+            FlagOp::Disallow => {
+                let blocked = if self.value == 0 {
+                    current != 0
+                } else {
+                    current == self.value
+                };
+                (!blocked).then_some(current)
+            }
+            FlagOp::Unify => {
+                let ok = current == 0
+                    || current == self.value
+                    || (current < 0 && -current != self.value);
+                ok.then_some(self.value)
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::fst::{Fst, State, SymbolTable, Transition};
+use crate::{t, t_args};
+use anyhow::{Result, anyhow};
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::io::Read;
+
+/// Returns true if the data starts with the gzip magic number used by `.foma` files
+pub fn is_gzip(data: &[u8]) -> bool {
+    data.starts_with(&[0x1f, 0x8b])
+}
+
+/// Read the first network of a foma binary file (gzipped `##foma-net 1.0##` text)
+pub fn read_foma(data: &[u8]) -> Result<Fst> {
+    let mut text = String::new();
+    GzDecoder::new(data)
+        .read_to_string(&mut text)
+        .map_err(|e| anyhow!(t_args!("native-foma-bad-gzip", "error" => e)))?;
+    parse_foma_net(&text)
+}
+
+/// Parse the text of a foma network. Arcs go from the upper (`in`) to the lower (`out`) side.
+pub fn parse_foma_net(text: &str) -> Result<Fst> {
+    let mut lines = text.lines();
+    if !lines.next().is_some_and(|l| l.starts_with("##foma-net")) {
+        return Err(anyhow!(t!("native-foma-bad-header")));
+    }
+
+    let mut symbols = SymbolTable::new();
+    let mut sigma: HashMap<i64, usize> = HashMap::new();
+    let mut states: Vec<State> = Vec::new();
+    let mut section = "";
+    let mut current: i64 = -1;
+
+    for (n, line) in lines.enumerate() {
+        if line.starts_with("##") {
+            section = line;
+            if section == "##end##" {
+                break;
+            }
+            continue;
+        }
+        match section {
+            "##sigma##" => {
+                let (number, symbol) = line
+                    .split_once(' ')
+                    .ok_or_else(|| bad_line(n, line))?;
+                let number: i64 = number.parse().map_err(|_| bad_line(n, line))?;
+                sigma.insert(number, symbols.intern(symbol));
+            }
+            "##states##" => {
+                let fields: Vec<i64> = line
+                    .split_whitespace()
+                    .map(|f| f.parse())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| bad_line(n, line))?;
+                // Lines starting a new state carry the state number and finality,
+                // and `in` is left out when it equals `out`
+                let (state, input, output, target, is_final) = match fields[..] {
+                    [input, target] => (current, input, input, target, None),
+                    [input, output, target] => (current, input, output, target, None),
+                    [state, input, target, f] => (state, input, input, target, Some(f)),
+                    [state, input, output, target, f] => (state, input, output, target, Some(f)),
+                    _ => return Err(bad_line(n, line)),
+                };
+                if state == -1 {
+                    break;
+                }
+                current = state;
+                let state = usize::try_from(state).map_err(|_| bad_line(n, line))?;
+                if states.len() <= state {
+                    states.resize_with(state + 1, State::default);
+                }
+                if is_final == Some(1) {
+                    states[state].final_weight = Some(0.0);
+                }
+                if target == -1 {
+                    continue;
+                }
+                let symbol = |number| sigma.get(&number).copied().ok_or_else(|| bad_line(n, line));
+                let target = usize::try_from(target).map_err(|_| bad_line(n, line))?;
+                if states.len() <= target {
+                    states.resize_with(target + 1, State::default);
+                }
+                states[state].transitions.push(Transition {
+                    input: symbol(input)?,
+                    output: symbol(output)?,
+                    target,
+                    weight: 0.0,
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(Fst::new(symbols, states, false))
+}
+
+fn bad_line(n: usize, line: &str) -> anyhow::Error {
+    anyhow!(t_args!("native-foma-bad-line", "line" => n + 2, "text" => line))
+}
@@ -0,0 +1,169 @@
+use crate::att::is_att_file;
+use crate::foma::is_gzip;
+use crate::{t, t_args};
+use anyhow::{Context, Result, anyhow};
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read from the start of a transducer file to tell its format
+const SNIFF_SIZE: u64 = 64 * 1024;
+
+/// The format of a transducer file, as far as its first bytes tell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FstFormat {
+    /// HFST optimized lookup (`HFST_OL` or `HFST_OLW`), read by hfst-optimised-lookup and hfst-proc
+    HfstOptimized,
+    /// Any other HFST3 transducer type, such as `TROPICAL_OPENFST_TYPE`, read by hfst-lookup
+    Hfst(String),
+    /// A gzipped foma network
+    Foma,
+    /// AT&T text
+    Att,
+    /// An lttoolbox binary, as read by lt-proc
+    Lttoolbox,
+    /// None of the above; old headerless HFST and lttoolbox files look like this
+    Unknown,
+}
+
+impl fmt::Display for FstFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            FstFormat::HfstOptimized => t!("format-hfstol"),
+            FstFormat::Hfst(kind) => t_args!("format-hfst", "kind" => kind),
+            FstFormat::Foma => t!("format-foma"),
+            FstFormat::Att => t!("format-att"),
+            FstFormat::Lttoolbox => t!("format-lttoolbox"),
+            FstFormat::Unknown => t!("format-unknown"),
+        };
+        f.write_str(&label)
+    }
+}
+
+/// The `type` property of an HFST3 header (`HFST\0`, length, NUL, properties)
+fn hfst3_type(data: &[u8]) -> Option<String> {
+    let rest = data.strip_prefix(b"HFST\0")?;
+    let len = u16::from_le_bytes([*rest.first()?, *rest.get(1)?]) as usize;
+    let props = rest.get(3..3 + len)?;
+    let fields: Vec<&[u8]> = props.split(|b| *b == 0).collect();
+    fields
+        .chunks(2)
+        .find(|pair| pair[0] == b"type")
+        .and_then(|pair| pair.get(1))
+        .map(|kind| String::from_utf8_lossy(kind).into_owned())
+}
+
+/// AT&T text starts with a `source target input output` or a final state line
+fn looks_like_att(data: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(&data[..data.len().min(1024)]) else {
+        return false;
+    };
+    let Some(first) = text.lines().next() else {
+        return false;
+    };
+    let cols: Vec<&str> = first.split('\t').collect();
+    matches!(cols.len(), 1 | 2 | 4 | 5) && cols[0].parse::<u64>().is_ok()
+}
+
+/// Tell the format of a transducer from its first bytes and its name
+pub fn sniff_format(path: &str, data: &[u8]) -> FstFormat {
+    if let Some(kind) = hfst3_type(data) {
+        if kind == "HFST_OL" || kind == "HFST_OLW" {
+            FstFormat::HfstOptimized
+        } else {
+            FstFormat::Hfst(kind)
+        }
+    } else if is_gzip(data) {
+        FstFormat::Foma
+    } else if data.starts_with(b"LTTB") {
+        FstFormat::Lttoolbox
+    } else if path.ends_with(".att") || looks_like_att(data) {
+        FstFormat::Att
+    } else {
+        FstFormat::Unknown
+    }
+}
+
+/// Check that a transducer exists and can be read, and tell its format
+pub fn read_format(path: &str) -> Result<FstFormat> {
+    if !Path::new(path).is_file() {
+        return Err(anyhow!(t_args!("format-fst-not-found", "file" => path)));
+    }
+    let mut data = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(SNIFF_SIZE).read_to_end(&mut data))
+        .with_context(|| t_args!("format-fst-unreadable", "file" => path))?;
+    Ok(sniff_format(path, &data))
+}
+
+/// The format of the first of the transducers that can be read
+pub fn detect_format(gen_path: &str, morph_path: Option<&str>) -> Option<FstFormat> {
+    std::iter::once(gen_path)
+        .chain(morph_path)
+        .find_map(|path| read_format(path).ok())
+}
+
+/// AT&T text, by extension or by content, which only the built-in reader reads
+pub fn is_att_transducer(path: &str) -> bool {
+    is_att_file(path) || read_format(path).is_ok_and(|format| format == FstFormat::Att)
+}
+
+/// Transducers of unknown format are taken to be old headerless HFST files
+/// when named like them
+pub fn named_like_hfst(path: &str) -> bool {
+    path.ends_with(".hfst") || path.ends_with(".hfstol") || path.ends_with(".hfst.ol")
+}
+
+/// Tools that read HFST transducers
+const HFST_TOOLS: [&str; 4] = [
+    "hfst-lookup",
+    "hfst-optimised-lookup",
+    "hfst-optimized-lookup",
+    "hfst-proc",
+];
+
+fn tool_name(lookup_cmd: &str) -> String {
+    Path::new(lookup_cmd)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// The formats a lookup tool reads, by the tool's file name. Tools that are
+/// not known here read anything.
+fn tool_reads(name: &str, format: &FstFormat) -> bool {
+    match name {
+        "hfst-optimised-lookup" | "hfst-optimized-lookup" | "hfst-proc" => {
+            matches!(format, FstFormat::HfstOptimized | FstFormat::Unknown)
+        }
+        "hfst-lookup" => matches!(
+            format,
+            FstFormat::HfstOptimized | FstFormat::Hfst(_) | FstFormat::Unknown
+        ),
+        "flookup" => matches!(format, FstFormat::Foma),
+        "lt-proc" => matches!(format, FstFormat::Lttoolbox | FstFormat::Unknown),
+        _ => true,
+    }
+}
+
+/// Check that each transducer exists, can be read, and is in a format the
+/// lookup tool reads
+pub fn check_fsts<'a>(lookup_cmd: &str, fsts: impl IntoIterator<Item = &'a str>) -> Result<()> {
+    let name = tool_name(lookup_cmd);
+    for fst in fsts {
+        let format = read_format(fst)?;
+        if format == FstFormat::Unknown && HFST_TOOLS.contains(&name.as_str()) && !named_like_hfst(fst) {
+            return Err(anyhow!(t_args!("format-fst-unknown", "file" => fst)));
+        }
+        if !tool_reads(&name, &format) {
+            // The label is localized too, so it cannot be made while the message is
+            let format = format.to_string();
+            return Err(anyhow!(t_args!("format-fst-wrong-format",
+                "file" => fst,
+                "format" => format,
+                "cmd" => lookup_cmd
+            )));
+        }
+    }
+    Ok(())
+}
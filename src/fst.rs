@@ -0,0 +1,222 @@
+use crate::flags::{FlagDiacritic, FlagTable};
+use crate::native::{Deadline, Tokenizer, Transducer, note_result, ranked};
+use crate::types::LookupOutput;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+pub const EPSILON: usize = 0;
+pub const UNKNOWN: usize = 1;
+pub const IDENTITY: usize = 2;
+/// Steps after which a path is abandoned, to survive epsilon loops
+const MAX_DEPTH: usize = 2000;
+
+/// Interns symbol strings, with epsilon, unknown and identity at fixed numbers
+#[derive(Debug, Clone)]
+pub struct SymbolTable {
+    symbols: Vec<String>,
+    numbers: HashMap<String, usize>,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        let mut table = Self {
+            symbols: Vec::new(),
+            numbers: HashMap::new(),
+        };
+        for s in ["@_EPSILON_SYMBOL_@", "@_UNKNOWN_SYMBOL_@", "@_IDENTITY_SYMBOL_@"] {
+            table.intern(s);
+        }
+        table
+    }
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, symbol: &str) -> usize {
+        if let Some(n) = self.numbers.get(symbol) {
+            return *n;
+        }
+        let n = self.symbols.len();
+        self.symbols.push(symbol.to_string());
+        self.numbers.insert(symbol.to_string(), n);
+        n
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Transition {
+    pub input: usize,
+    pub output: usize,
+    pub target: usize,
+    pub weight: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub transitions: Vec<Transition>,
+    pub final_weight: Option<f32>,
+}
+
+/// Which side of the transducer the input is matched against. `Down` reads the
+/// input (upper) side and writes the output side, `Up` the reverse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Apply {
+    Down,
+    Up,
+}
+
+/// A transducer held as plain states and transitions, start state 0
+#[derive(Debug)]
+pub struct Fst {
+    symbols: Vec<String>,
+    flags: Vec<Option<FlagDiacritic>>,
+    flag_features: usize,
+    states: Vec<State>,
+    tokenizer: Tokenizer,
+    /// Whether the format carries weights; lookups only report them if so
+    weighted: bool,
+}
+
+impl Fst {
+    pub fn new(symbols: SymbolTable, states: Vec<State>, weighted: bool) -> Self {
+        let symbols = symbols.symbols;
+        let mut flag_table = FlagTable::new();
+        let flags: Vec<Option<FlagDiacritic>> =
+            symbols.iter().map(|s| flag_table.intern(s)).collect();
+
+        // Anything in the alphabet is a known symbol, whichever side it occurs on
+        let tokenizer = Tokenizer::new(
+            symbols
+                .iter()
+                .enumerate()
+                .filter(|(n, _)| *n > IDENTITY && flags[*n].is_none())
+                .map(|(n, s)| (s.as_str(), n)),
+        );
+
+        Self {
+            flag_features: flag_table.feature_count(),
+            symbols,
+            flags,
+            states,
+            tokenizer,
+            weighted,
+        }
+    }
+
+    pub fn lookup(&self, input: &str, apply: Apply) -> Vec<LookupOutput> {
+        self.lookup_until(input, apply, None).unwrap_or_default()
+    }
+
+    /// Look up an input, or give up with `None` once `deadline` has passed
+    pub fn lookup_until(&self, input: &str, apply: Apply, deadline: Option<Instant>) -> Option<Vec<LookupOutput>> {
+        let tokens = self.tokenizer.segment(input);
+        let mut search = Search {
+            fst: self,
+            apply,
+            tokens: &tokens,
+            output: Vec::new(),
+            weight: 0.0,
+            flag_state: vec![0; self.flag_features],
+            results: HashMap::new(),
+            deadline: Deadline::new(deadline),
+        };
+        if !self.states.is_empty() {
+            search.walk(0, 0, 0);
+        }
+        (!search.deadline.passed()).then(|| ranked(search.results, self.weighted))
+    }
+}
+
+/// A transducer applied in one fixed direction
+pub struct DirectedFst {
+    pub fst: Arc<Fst>,
+    pub apply: Apply,
+}
+
+impl Transducer for DirectedFst {
+    fn lookup_until(&self, input: &str, deadline: Option<Instant>) -> Option<Vec<LookupOutput>> {
+        self.fst.lookup_until(input, self.apply, deadline)
+    }
+
+    fn inverted(&self) -> Option<Arc<dyn Transducer>> {
+        let apply = match self.apply {
+            Apply::Down => Apply::Up,
+            Apply::Up => Apply::Down,
+        };
+        Some(Arc::new(DirectedFst {
+            fst: self.fst.clone(),
+            apply,
+        }))
+    }
+}
+
+struct Search<'a> {
+    fst: &'a Fst,
+    apply: Apply,
+    tokens: &'a [(&'a str, Option<usize>)],
+    output: Vec<&'a str>,
+    weight: f32,
+    flag_state: Vec<i32>,
+    results: HashMap<String, f32>,
+    deadline: Deadline,
+}
+
+impl<'a> Search<'a> {
+    fn walk(&mut self, state: usize, pos: usize, depth: usize) {
+        if depth > MAX_DEPTH || self.deadline.tick() {
+            return;
+        }
+        let fst = self.fst;
+        let state_ref = &fst.states[state];
+        if pos == self.tokens.len()
+            && let Some(final_weight) = state_ref.final_weight
+        {
+            note_result(&mut self.results, self.output.concat(), self.weight + final_weight);
+        }
+        for t in &state_ref.transitions {
+            let (matched, emitted) = match self.apply {
+                Apply::Down => (t.input, t.output),
+                Apply::Up => (t.output, t.input),
+            };
+            if let Some(flag) = fst.flags[matched] {
+                if let Some(value) = flag.apply(&self.flag_state) {
+                    let previous = self.flag_state[flag.feature];
+                    self.flag_state[flag.feature] = value;
+                    self.step(t, pos, depth, emitted, "");
+                    self.flag_state[flag.feature] = previous;
+                }
+            } else if matched == EPSILON {
+                self.step(t, pos, depth, emitted, "");
+            } else if let Some(&(text, number)) = self.tokens.get(pos) {
+                let matches = match number {
+                    Some(n) => n == matched,
+                    None => matched == IDENTITY || matched == UNKNOWN,
+                };
+                if matches {
+                    self.step(t, pos + 1, depth, emitted, text);
+                }
+            }
+        }
+    }
+
+    fn step(&mut self, t: &Transition, pos: usize, depth: usize, emitted: usize, text: &'a str) {
+        let fst = self.fst;
+        let piece = match emitted {
+            EPSILON => "",
+            IDENTITY => text,
+            UNKNOWN => "?",
+            n if fst.flags[n].is_some() => "",
+            n => fst.symbols[n].as_str(),
+        };
+        self.output.push(piece);
+        let weight = self.weight;
+        self.weight += t.weight;
+        self.walk(t.target, pos, depth + 1);
+        self.weight = weight;
+        self.output.pop();
+    }
+}
@@ -0,0 +1,326 @@
+use crate::flags::{FlagDiacritic, FlagTable};
+use crate::native::{Deadline, Tokenizer, Transducer, note_result, ranked};
+use crate::types::LookupOutput;
+use crate::{t, t_args};
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Table positions at or above this point refer to the transition table
+const TARGET_TABLE_START: u32 = 1 << 31;
+const NO_SYMBOL: u16 = u16::MAX;
+const NO_TABLE_INDEX: u32 = u32::MAX;
+/// Size of the fixed transducer header following the optional HFST3 header
+const HEADER_SIZE: usize = 56;
+/// Output length at which a path is abandoned, to survive epsilon loops
+const MAX_OUTPUT_LENGTH: usize = 2000;
+
+#[derive(Debug, Clone, Copy)]
+struct IndexEntry {
+    input: u16,
+    target: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TransitionEntry {
+    input: u16,
+    output: u16,
+    target: u32,
+    weight: f32,
+}
+
+/// An hfst-optimized-lookup transducer (the `.hfstol` format), weighted or unweighted
+#[derive(Debug)]
+pub struct HfstolTransducer {
+    symbols: Vec<String>,
+    flags: Vec<Option<FlagDiacritic>>,
+    flag_features: usize,
+    weighted: bool,
+    indices: Vec<IndexEntry>,
+    transitions: Vec<TransitionEntry>,
+    tokenizer: Tokenizer,
+}
+
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| anyhow!(t!("native-hfstol-truncated")))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn cstr(&mut self) -> Result<String> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| anyhow!(t!("native-hfstol-truncated")))?;
+        let s = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Ok(s)
+    }
+}
+
+/// Skip the HFST3 header (`HFST\0`, length, properties) if present, checking the type
+fn skip_hfst3_header(reader: &mut ByteReader) -> Result<()> {
+    if !reader.data.starts_with(b"HFST\0") {
+        return Ok(());
+    }
+    reader.take(5)?;
+    let len = reader.u16()? as usize;
+    if reader.take(1)? != [0] {
+        return Err(anyhow!(t!("native-hfstol-bad-header")));
+    }
+    let props = reader.take(len)?;
+    let fields: Vec<&[u8]> = props.split(|b| *b == 0).collect();
+    for pair in fields.chunks(2) {
+        if let [key, value] = pair
+            && *key == b"type"
+        {
+            let kind = String::from_utf8_lossy(value);
+            if kind != "HFST_OL" && kind != "HFST_OLW" {
+                return Err(anyhow!(t_args!("native-hfstol-wrong-type", "kind" => kind)));
+            }
+        }
+    }
+    Ok(())
+}
+
+impl HfstolTransducer {
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader { data, pos: 0 };
+        skip_hfst3_header(&mut reader)?;
+
+        if data.len() < reader.pos + HEADER_SIZE {
+            return Err(anyhow!(t!("native-hfstol-truncated")));
+        }
+        let input_symbol_count = reader.u16()? as usize;
+        let symbol_count = reader.u16()? as usize;
+        let index_table_size = reader.u32()? as usize;
+        let transition_table_size = reader.u32()? as usize;
+        let _states = reader.u32()?;
+        let _transitions = reader.u32()?;
+        let weighted = reader.u32()? != 0;
+        // deterministic, input_deterministic, minimized, cyclic and the epsilon properties
+        reader.take(8 * 4)?;
+
+        let mut symbols = Vec::with_capacity(symbol_count);
+        for _ in 0..symbol_count {
+            symbols.push(reader.cstr()?);
+        }
+
+        let mut flag_table = FlagTable::new();
+        let flags: Vec<Option<FlagDiacritic>> =
+            symbols.iter().map(|s| flag_table.intern(s)).collect();
+
+        let mut indices = Vec::with_capacity(index_table_size);
+        for _ in 0..index_table_size {
+            let input = reader.u16()?;
+            let target = reader.u32()?;
+            indices.push(IndexEntry { input, target });
+        }
+
+        let mut transitions = Vec::with_capacity(transition_table_size);
+        for _ in 0..transition_table_size {
+            let input = reader.u16()?;
+            let output = reader.u16()?;
+            let target = reader.u32()?;
+            let weight = if weighted {
+                f32::from_bits(reader.u32()?)
+            } else {
+                0.0
+            };
+            transitions.push(TransitionEntry {
+                input,
+                output,
+                target,
+                weight,
+            });
+        }
+
+        let tokenizer = Tokenizer::new(
+            symbols
+                .iter()
+                .enumerate()
+                .take(input_symbol_count)
+                .skip(1)
+                .filter(|(i, _)| flags[*i].is_none())
+                .map(|(i, s)| (s.as_str(), i)),
+        );
+
+        Ok(Self {
+            symbols,
+            flags,
+            flag_features: flag_table.feature_count(),
+            weighted,
+            indices,
+            transitions,
+            tokenizer,
+        })
+    }
+
+    /// The final weight of the state at index `i`, if it is final
+    fn index_final(&self, i: usize) -> Option<f32> {
+        // Unweighted tables store 1 for final states, weighted ones store the final weight
+        let e = self.indices.get(i).filter(|e| e.input == NO_SYMBOL)?;
+        if self.weighted {
+            (e.target != NO_TABLE_INDEX).then(|| f32::from_bits(e.target))
+        } else {
+            (e.target == 1).then_some(0.0)
+        }
+    }
+
+    /// The final weight of the state at transition `i`, if it is final
+    fn transition_final(&self, i: usize) -> Option<f32> {
+        self.transitions
+            .get(i)
+            .filter(|e| e.input == NO_SYMBOL && e.output == NO_SYMBOL && e.target == 1)
+            .map(|e| e.weight)
+    }
+}
+
+impl Transducer for HfstolTransducer {
+    fn lookup_until(&self, input: &str, deadline: Option<Instant>) -> Option<Vec<LookupOutput>> {
+        let Some(tokens) = self.tokenizer.tokenize(input) else {
+            return Some(Vec::new());
+        };
+        let input: Vec<u16> = tokens.into_iter().map(|t| t as u16).collect();
+        let mut search = Search {
+            fst: self,
+            input: &input,
+            output: Vec::new(),
+            weight: 0.0,
+            flag_state: vec![0; self.flag_features],
+            results: HashMap::new(),
+            deadline: Deadline::new(deadline),
+        };
+        search.get_analyses(0, 0);
+        (!search.deadline.passed()).then(|| ranked(search.results, self.weighted))
+    }
+}
+
+/// Depth-first traversal state for a single lookup
+struct Search<'a> {
+    fst: &'a HfstolTransducer,
+    input: &'a [u16],
+    output: Vec<u16>,
+    weight: f32,
+    flag_state: Vec<i32>,
+    results: HashMap<String, f32>,
+    deadline: Deadline,
+}
+
+impl Search<'_> {
+    fn get_analyses(&mut self, pos: usize, i: u32) {
+        if self.output.len() > MAX_OUTPUT_LENGTH || self.deadline.tick() {
+            return;
+        }
+        if i >= TARGET_TABLE_START {
+            let i = (i - TARGET_TABLE_START) as usize;
+            self.try_epsilon_transitions(pos, i + 1);
+            if pos == self.input.len() {
+                if let Some(final_weight) = self.fst.transition_final(i) {
+                    self.note_analysis(final_weight);
+                }
+                return;
+            }
+            self.find_transitions(self.input[pos], pos + 1, i + 1);
+        } else {
+            let i = i as usize;
+            self.try_epsilon_indices(pos, i + 1);
+            if pos == self.input.len() {
+                if let Some(final_weight) = self.fst.index_final(i) {
+                    self.note_analysis(final_weight);
+                }
+                return;
+            }
+            self.find_index(self.input[pos], pos + 1, i + 1);
+        }
+    }
+
+    fn try_epsilon_indices(&mut self, pos: usize, i: usize) {
+        if let Some(entry) = self.fst.indices.get(i)
+            && entry.input == 0
+            && entry.target >= TARGET_TABLE_START
+        {
+            self.try_epsilon_transitions(pos, (entry.target - TARGET_TABLE_START) as usize);
+        }
+    }
+
+    fn try_epsilon_transitions(&mut self, pos: usize, mut i: usize) {
+        while let Some(entry) = self.fst.transitions.get(i).copied() {
+            if entry.input == 0 {
+                self.follow(pos, entry);
+            } else if let Some(flag) = self.fst.flags.get(entry.input as usize).copied().flatten() {
+                if let Some(value) = flag.apply(&self.flag_state) {
+                    let previous = self.flag_state[flag.feature];
+                    self.flag_state[flag.feature] = value;
+                    self.follow(pos, entry);
+                    self.flag_state[flag.feature] = previous;
+                }
+            } else {
+                return;
+            }
+            i += 1;
+        }
+    }
+
+    fn find_index(&mut self, symbol: u16, pos: usize, i: usize) {
+        if let Some(entry) = self.fst.indices.get(i + symbol as usize)
+            && entry.input == symbol
+            && entry.target >= TARGET_TABLE_START
+        {
+            self.find_transitions(symbol, pos, (entry.target - TARGET_TABLE_START) as usize);
+        }
+    }
+
+    fn find_transitions(&mut self, symbol: u16, pos: usize, mut i: usize) {
+        while let Some(entry) = self.fst.transitions.get(i).copied() {
+            if entry.input != symbol || entry.input == NO_SYMBOL {
+                return;
+            }
+            self.follow(pos, entry);
+            i += 1;
+        }
+    }
+
+    fn follow(&mut self, pos: usize, entry: TransitionEntry) {
+        self.output.push(entry.output);
+        let weight = self.weight;
+        self.weight += entry.weight;
+        self.get_analyses(pos, entry.target);
+        self.weight = weight;
+        self.output.pop();
+    }
+
+    fn note_analysis(&mut self, final_weight: f32) {
+        let mut out = String::new();
+        for &symbol in &self.output {
+            let s = symbol as usize;
+            if s == 0 || self.fst.flags.get(s).is_some_and(|f| f.is_some()) {
+                continue;
+            }
+            if let Some(text) = self.fst.symbols.get(s) {
+                out.push_str(text);
+            }
+        }
+        note_result(&mut self.results, out, self.weight + final_weight);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::backend::{Backend, DEFAULT_TIMEOUT};
+use crate::types::{Direction, LookupOutcome, LookupOutput};
+use crate::t_args;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::debug;
+
+/// Inputs sent to the service in one request
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// Request body: the inputs of one batch and which way to look them up
+#[derive(Debug, Serialize)]
+struct LookupRequest<'a> {
+    direction: &'static str,
+    inputs: &'a [String],
+}
+
+/// Response body: one result per input, in the order of the request
+#[derive(Debug, Deserialize)]
+struct LookupResponse {
+    results: Vec<InputResult>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum InputResult {
+    Outputs(Vec<ServiceOutput>),
+    Failed { error: String },
+}
+
+/// An output is a plain string, or an object with `output` and `weight`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ServiceOutput {
+    Plain(String),
+    Weighted(LookupOutput),
+}
+
+impl From<ServiceOutput> for LookupOutput {
+    fn from(output: ServiceOutput) -> Self {
+        match output {
+            ServiceOutput::Plain(output) => output.into(),
+            ServiceOutput::Weighted(output) => output,
+        }
+    }
+}
+
+/// Backend that posts batches of inputs as JSON to a morphology service:
+/// `{"direction": "analyze", "inputs": [...]}` is answered with
+/// `{"results": [[...], ...]}`, a list of outputs or `{"error": "..."}` per input
+#[derive(Debug, Clone)]
+pub struct HttpBackend {
+    pub endpoint: String,
+    pub batch_size: usize,
+    agent: ureq::Agent,
+}
+
+impl HttpBackend {
+    /// The timeout applies to each request as a whole
+    pub fn new(endpoint: &str, timeout: Option<Duration>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(timeout.unwrap_or(DEFAULT_TIMEOUT)))
+            .build()
+            .into();
+        Self {
+            endpoint: endpoint.trim().to_string(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            agent,
+        }
+    }
+
+    fn post(&self, direction: &Direction, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let request = LookupRequest {
+            direction: match direction {
+                Direction::Analyze => "analyze",
+                Direction::Generate => "generate",
+            },
+            inputs,
+        };
+        let response: LookupResponse = self
+            .agent
+            .post(&self.endpoint)
+            .send_json(&request)?
+            .body_mut()
+            .read_json()?;
+        if response.results.len() != inputs.len() {
+            return Err(anyhow!(t_args!("http-wrong-result-count",
+                "expected" => inputs.len(),
+                "got" => response.results.len()
+            )));
+        }
+        Ok(response
+            .results
+            .into_iter()
+            .map(|result| match result {
+                InputResult::Outputs(outputs) => Ok(outputs.into_iter().map(Into::into).collect()),
+                InputResult::Failed { error } => Err(error),
+            })
+            .collect())
+    }
+
+    /// A request that fails only fails the inputs it carried
+    fn lookup(&self, direction: Direction, inputs: &[String]) -> Vec<LookupOutcome> {
+        let inputs: Vec<String> = inputs.iter().map(|input| input.trim().to_string()).collect();
+        let mut outcomes = Vec::with_capacity(inputs.len());
+        for chunk in inputs.chunks(self.batch_size.max(1)) {
+            debug!(
+                "{}",
+                t_args!("debug-http-request",
+                    "count" => chunk.len(),
+                    "endpoint" => &self.endpoint
+                )
+            );
+            match self.post(&direction, chunk) {
+                Ok(results) => outcomes.extend(results),
+                Err(e) => {
+                    let reason = t_args!("http-request-failed",
+                        "endpoint" => &self.endpoint,
+                        "error" => e
+                    );
+                    outcomes.extend(chunk.iter().map(|_| Err(reason.clone())));
+                }
+            }
+        }
+        outcomes
+    }
+
+    /// HTTP requests block, so they are made off the async runtime
+    async fn lookup_blocking(&self, direction: Direction, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let backend = self.clone();
+        let inputs = inputs.to_vec();
+        Ok(tokio::task::spawn_blocking(move || backend.lookup(direction, &inputs)).await?)
+    }
+}
+
+#[async_trait]
+impl Backend for HttpBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        self.lookup_blocking(Direction::Analyze, inputs).await
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        self.lookup_blocking(Direction::Generate, inputs).await
+    }
+
+    /// An empty batch checks that the service is there and speaks the protocol
+    async fn validate(&self) -> Result<()> {
+        let backend = self.clone();
+        tokio::task::spawn_blocking(move || backend.post(&Direction::Analyze, &[]))
+            .await?
+            .map(|_| ())
+            .map_err(|e| {
+                anyhow!(t_args!("http-unreachable",
+                    "endpoint" => &self.endpoint,
+                    "error" => e
+                ))
+            })
+    }
+}
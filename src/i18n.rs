@@ -70,14 +70,14 @@ impl Localizer {
         
         if let Ok(entries) = fs::read_dir(&locales_dir) {
             for entry in entries.flatten() {
-                if let Some(filename) = entry.file_name().to_str() {
-                    if filename.ends_with(".ftl") {
-                        let locale_str = filename.strip_suffix(".ftl").unwrap();
-                        
-                        // Try to parse as a valid BCP-47 locale
-                        if let Ok(locale_id) = locale_str.parse::<LanguageIdentifier>() {
-                            self.available_locales.push(locale_id);
-                        }
+                if let Some(filename) = entry.file_name().to_str()
+                    && filename.ends_with(".ftl")
+                {
+                    let locale_str = filename.strip_suffix(".ftl").unwrap();
+                    
+                    // Try to parse as a valid BCP-47 locale
+                    if let Ok(locale_id) = locale_str.parse::<LanguageIdentifier>() {
+                        self.available_locales.push(locale_id);
                     }
                 }
             }
@@ -122,21 +122,20 @@ impl Localizer {
             if let Ok(value) = std::env::var(var) {
                 // Parse locale, removing encoding (e.g., "nn-Runr.UTF-8" -> "nn-Runr")
                 let locale_str = value.split('.').next().unwrap_or(&value).replace('_', "-");
-                if let Ok(locale_id) = locale_str.parse::<LanguageIdentifier>() {
-                    if !preferences.contains(&locale_id) {
-                        preferences.push(locale_id);
-                    }
+                if let Ok(locale_id) = locale_str.parse::<LanguageIdentifier>()
+                    && !preferences.contains(&locale_id)
+                {
+                    preferences.push(locale_id);
                 }
             }
         }
         
         // Try system locale as fallback
-        if let Some(system_locale) = sys_locale::get_locale() {
-            if let Ok(locale_id) = system_locale.replace('_', "-").parse::<LanguageIdentifier>() {
-                if !preferences.contains(&locale_id) {
-                    preferences.push(locale_id);
-                }
-            }
+        if let Some(system_locale) = sys_locale::get_locale()
+            && let Ok(locale_id) = system_locale.replace('_', "-").parse::<LanguageIdentifier>()
+            && !preferences.contains(&locale_id)
+        {
+            preferences.push(locale_id);
         }
         
         // Add English as final fallback
@@ -252,13 +251,9 @@ impl Localizer {
         }
         
         // Check each fallback
-        for fallback in fallback_chain {
-            if self.available_locales.contains(&fallback) {
-                return Some(fallback);
-            }
-        }
-        
-        None
+        fallback_chain
+            .into_iter()
+            .find(|fallback| self.available_locales.contains(fallback))
     }
 
     /// Load messages for the specified locale
@@ -1,11 +1,26 @@
+pub mod apertium;
+pub mod att;
 pub mod backend;
+pub mod cache;
+pub mod command;
 pub mod engine;
-pub mod engine_async;
+pub mod fixture;
+pub mod flags;
+pub mod foma;
+pub mod format;
+pub mod fst;
+pub mod hfstol;
+pub mod http;
 pub mod i18n;
+pub mod native;
+pub mod normalize;
 pub mod pool;
+pub mod project;
 pub mod report;
 pub mod spec;
+pub mod table;
 pub mod types;
+pub mod vars;
 
 // Re-export the localization macros
 pub use crate::i18n::{t, t_with_args};
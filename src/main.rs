@@ -1,20 +1,35 @@
-use anyhow::Result;
-use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use anyhow::{Result, anyhow};
+use clap::parser::ValueSource;
+use clap::{error::ErrorKind, ArgMatches, CommandFactory, FromArgMatches, Parser, ValueEnum};
 use colored::control::set_override as set_color_override;
 use colored::Colorize;
 use regex::Regex;
 use futures::future::try_join_all;
-use morph_test2::backend::{Backend, DEFAULT_TIMEOUT, ExternalBackend};
-use morph_test2::engine::run_suites;
-use morph_test2::engine_async::run_suites_async;
+use morph_test2::apertium::{ApertiumBackend, default_proc_tool};
+use morph_test2::backend::{Backend, DEFAULT_TIMEOUT, ExternalBackend, LookupArgs};
+use morph_test2::cache::{CachedBackend, default_cache_dir};
+use morph_test2::command::CommandBackend;
+use morph_test2::engine::{check_relation, run_suites_separately, swapped_direction_hint};
+use morph_test2::fixture::{Fixture, Recorder, RecordingBackend, ReplayBackend};
+use morph_test2::format::{FstFormat, detect_format, is_att_transducer};
+use morph_test2::http::HttpBackend;
 use morph_test2::i18n;
+use morph_test2::native::NativeBackend;
+use morph_test2::normalize::NormalizingBackend;
 use morph_test2::pool::PooledBackend;
+use morph_test2::project::{ProjectConfig, ProjectOptions, find_project_config};
 use morph_test2::report::{OutputKind, print_human, calculate_counts};
 use morph_test2::spec::{BackendChoice, load_specs, determine_hfst_lookup_tool};
+use morph_test2::types::{Relation, TestSuite};
 use morph_test2::{t, t_args};
-use std::collections::{HashMap, HashSet};
+use indexmap::{IndexMap, IndexSet};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tracing::{error, info};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Stands in for the lookup command in cache keys when the built-in reader is used
+const NATIVE_CACHE_KEY: &str = "native";
 
 #[cfg(feature = "mimalloc")]
 #[global_allocator]
@@ -25,6 +40,9 @@ enum BackendOpt {
     Auto,
     Hfst,
     Foma,
+    Apertium,
+    Http,
+    Command,
 }
 
 impl From<BackendOpt> for BackendChoice {
@@ -33,6 +51,9 @@ impl From<BackendOpt> for BackendChoice {
             BackendOpt::Auto => BackendChoice::Auto,
             BackendOpt::Hfst => BackendChoice::Hfst,
             BackendOpt::Foma => BackendChoice::Foma,
+            BackendOpt::Apertium => BackendChoice::Apertium,
+            BackendOpt::Http => BackendChoice::Http,
+            BackendOpt::Command => BackendChoice::Command,
         }
     }
 }
@@ -60,7 +81,7 @@ impl From<OutputFormat> for OutputKind {
     about = t!("cli-about")
 )]
 struct Cli {
-    // TEST_PATHS: one or more YAML files/directories with test data
+    // TEST_PATHS: one or more YAML, JSON, TOML or lexc files/directories with test data
     #[arg(value_name = "TEST_PATHS", required = true)]
     tests: Vec<PathBuf>,
 
@@ -109,6 +130,15 @@ struct Cli {
     )]
     lookup_tool: Option<String>,
 
+    // Run every suite against a morphology service instead of its transducers
+    #[arg(
+        long = "endpoint",
+        value_name = "URL",
+        conflicts_with_all = ["native", "lookup_tool"],
+        help = t!("cli-endpoint")
+    )]
+    endpoint: Option<String>,
+
     // Ignore extra analyses in Analyze mode
     #[arg(
         short = 'i',
@@ -200,6 +230,90 @@ struct Cli {
         help = t!("cli-serial")
     )]
     use_serial: bool,
+
+    // Per-input lookup timeout, overrides Timeout in the YAML Config
+    #[arg(
+        long = "timeout",
+        value_name = "SECONDS",
+        value_parser = parse_timeout,
+        help = t!("cli-timeout")
+    )]
+    timeout: Option<Duration>,
+
+    // Strip flag diacritics from the outputs, as `Flags: true` under `Normalize` does
+    #[arg(
+        long = "normalize",
+        help = t!("cli-normalize")
+    )]
+    normalize: bool,
+
+    // In-process lookup with the built-in transducer reader
+    #[arg(
+        long = "native",
+        help = t!("cli-native")
+    )]
+    native: bool,
+
+    // Keep lookup results on disk between runs
+    #[arg(
+        long = "cache",
+        help = t!("cli-cache")
+    )]
+    cache: bool,
+
+    #[arg(
+        long = "cache-dir",
+        value_name = "DIR",
+        help = t!("cli-cache-dir")
+    )]
+    cache_dir: Option<PathBuf>,
+
+    // Write every lookup to a fixture file
+    #[arg(
+        long = "record",
+        value_name = "FILE",
+        conflicts_with = "replay",
+        help = t!("cli-record")
+    )]
+    record: Option<PathBuf>,
+
+    // Answer lookups from a recorded fixture instead of the transducers
+    #[arg(
+        long = "replay",
+        value_name = "FILE",
+        conflicts_with_all = ["native", "endpoint", "cache", "cache_dir"],
+        help = t!("cli-replay")
+    )]
+    replay: Option<PathBuf>,
+}
+
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|d| !d.is_zero())
+        .ok_or_else(|| t_args!("spec-invalid-timeout", "value" => value))
+}
+
+/// Timeout for a suite: the CLI wins over the spec's Config
+fn effective_timeout(swc: &morph_test2::spec::SuiteWithConfig, cli: &Cli) -> Duration {
+    cli.timeout.or(swc.timeout).unwrap_or(DEFAULT_TIMEOUT)
+}
+
+/// Directory for cached lookup results, if caching is enabled
+fn cache_dir(cli: &Cli) -> Option<PathBuf> {
+    if cli.cache_dir.is_some() {
+        return cli.cache_dir.clone();
+    }
+    if !cli.cache {
+        return None;
+    }
+    let dir = default_cache_dir();
+    if dir.is_none() {
+        warn!("{}", t!("cache-no-dir"));
+    }
+    dir
 }
 
 fn display_path(path: &str) -> String {
@@ -219,6 +333,33 @@ fn resolve_lookup_path(cmd: &str) -> String {
     }
 }
 
+/// Whether a suite is looked up in-process rather than with an external tool
+fn uses_native(swc: &morph_test2::spec::SuiteWithConfig, cli: &Cli) -> bool {
+    let att_override = [&cli.generator, &cli.analyser]
+        .into_iter()
+        .flatten()
+        .any(|fst| is_att_transducer(fst));
+    // Foma networks given on the command line are read as if in the foma section
+    let foma_override = (cli.generator.is_some() || cli.analyser.is_some())
+        && cli.lookup_tool.is_none()
+        && matches!(swc.backend, BackendChoice::Hfst | BackendChoice::Foma)
+        && swc.app.is_none()
+        && {
+            let (effective_gen, effective_morph, _) = effective_setup(swc, cli);
+            detect_format(&effective_gen, effective_morph.as_deref()) == Some(FstFormat::Foma)
+        };
+    cli.native
+        || (cli.endpoint.is_none() && (att_override || foma_override || (swc.native && cli.lookup_tool.is_none())))
+}
+
+/// The service a suite is looked up with: `--endpoint`, or its `Http` config
+fn http_endpoint(swc: &morph_test2::spec::SuiteWithConfig, cli: &Cli) -> Option<String> {
+    cli.endpoint
+        .as_deref()
+        .map(|url| url.trim().to_string())
+        .or_else(|| matches!(swc.backend, BackendChoice::Http).then(|| swc.lookup_cmd.clone()))
+}
+
 fn mode_label(dir: &morph_test2::types::Direction) -> String {
     match dir {
         morph_test2::types::Direction::Generate => t!("direction-generate"),
@@ -353,6 +494,42 @@ fn create_custom_help() -> String {
     cleaned_lines.join("\n")
 }
 
+/// Print a command line error, or the help or version asked for, and exit
+fn exit_with_clap_error(error: clap::Error) -> ! {
+    let kind = error.kind();
+    if kind == ErrorKind::DisplayHelp {
+        println!("{}", format_clap_error(error));
+        std::process::exit(0);
+    } else if kind == ErrorKind::DisplayVersion {
+        format_clap_error(error); // This will print version and exit
+        unreachable!();
+    } else {
+        eprintln!("{}", format_clap_error(error));
+        std::process::exit(1);
+    }
+}
+
+/// Options from the project config, for those not given on the command line.
+/// Flags can only be turned on, and not where they would conflict with others.
+fn apply_project_options(cli: &mut Cli, matches: &ArgMatches, options: ProjectOptions) -> Result<()> {
+    let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+    let invalid = |option: &str, value: &str| anyhow!(t_args!("project-invalid-value", "option" => option, "value" => value));
+    if let Some(backend) = options.backend.filter(|_| !given("backend")) {
+        cli.backend = BackendOpt::from_str(backend.trim(), true).map_err(|_| invalid("Backend", &backend))?;
+    }
+    if let Some(output) = options.output.filter(|_| !given("output")) {
+        cli.output = OutputFormat::from_str(output.trim(), true).map_err(|_| invalid("Output", &output))?;
+    }
+    cli.ignore_extra_analyses |= options.ignore_extra_analyses;
+    cli.use_serial |= options.serial;
+    if cli.replay.is_none() {
+        cli.native |= options.native && cli.endpoint.is_none() && cli.lookup_tool.is_none();
+        cli.cache |= options.cache;
+        cli.cache_dir = cli.cache_dir.take().or(options.cache_dir);
+    }
+    Ok(())
+}
+
 /// Print custom localized version information
 fn print_custom_version() {
     let version = env!("CARGO_PKG_VERSION");
@@ -365,22 +542,10 @@ async fn main() -> Result<()> {
     // Initialize localization first
     i18n::init();
 
-    let cli = match Cli::try_parse() {
-        Ok(cli) => cli,
-        Err(error) => {
-            let kind = error.kind();
-            if kind == ErrorKind::DisplayHelp {
-                println!("{}", format_clap_error(error));
-                std::process::exit(0);
-            } else if kind == ErrorKind::DisplayVersion {
-                format_clap_error(error); // This will print version and exit
-                unreachable!();
-            } else {
-                eprintln!("{}", format_clap_error(error));
-                std::process::exit(1);
-            }
-        }
-    };
+    // The matches tell which options were given, so the project config
+    // fills in only the others
+    let matches = Cli::command().try_get_matches().unwrap_or_else(|error| exit_with_clap_error(error));
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| exit_with_clap_error(error));
 
     // Initialize tracing based on verbose flag and environment
     let filter = if cli.verbose {
@@ -393,6 +558,11 @@ async fn main() -> Result<()> {
 
     tracing_subscriber::fmt().with_env_filter(filter).init();
 
+    if let Some(path) = cli.tests.first().and_then(|path| find_project_config(path)) {
+        let options = ProjectConfig::load(&path)?.options;
+        apply_project_options(&mut cli, &matches, options)?;
+    }
+
     // Colors: default on, --no-color turns off
     if cli.no_color {
         set_color_override(false);
@@ -405,6 +575,9 @@ async fn main() -> Result<()> {
 
     // Filter direction before we build blocks
     for swc in &mut suites {
+        if cli.normalize {
+            swc.normalizer.strip_flags = true;
+        }
         if cli.surface {
             swc.suite
                 .cases
@@ -543,17 +716,84 @@ async fn main() -> Result<()> {
             )
         );
     }
+    // Relations between named transducers are checked once their suites have run
+    let relations: IndexSet<Relation> = suites.iter().flat_map(|swc| swc.relations.clone()).collect();
+    // Suites with the same transducers and lookup tool share a backend, so an
+    // input found in several suites is looked up once
+    let groups = group_by_backend(suites, &cli);
+    // All groups record into, or replay from, the same fixture, read once
+    let recorder = match &cli.record {
+        Some(path) => match Recorder::open(path) {
+            Ok(recorder) => Some(recorder),
+            Err(e) => {
+                error!("{}", t_args!("error-validation-failed", "error" => format!("{e:#}")));
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
+    let replay = match &cli.replay {
+        Some(path) => match Fixture::load(path) {
+            Ok(fixture) => Some((path.as_path(), fixture)),
+            Err(e) => {
+                error!("{}", t_args!("error-validation-failed", "error" => format!("{e:#}")));
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
+    let fixtures = Fixtures {
+        recorder: recorder.as_ref(),
+        replay: replay.as_ref().map(|(path, fixture)| (*path, fixture)),
+    };
+    let mut suite_summaries = Vec::new();
     if cli.use_serial {
         // Use traditional sequential processing
-        process_suites_sequential(suites, &cli, &mut aggregate, &mut failed_files).await?;
+        for group in groups.into_values() {
+            suite_summaries.extend(run_group(group, &cli, false, fixtures).await?);
+        }
     } else {
-        // Use process pool for parallel execution (default)
-        process_suites_with_pool(suites, &cli, &mut aggregate, &mut failed_files).await?;
+        // Native lookups are parallelised per batch and need no process pool
+        let (native_groups, pooled_groups): (Vec<_>, Vec<_>) = groups
+            .into_values()
+            .partition(|group| uses_native(&group[0], &cli));
+        for group in native_groups {
+            suite_summaries.extend(run_group(group, &cli, false, fixtures).await?);
+        }
+        // Use process pools for the rest, with the groups running in parallel (default)
+        let pooled = try_join_all(pooled_groups.into_iter().map(|group| run_group(group, &cli, true, fixtures))).await?;
+        suite_summaries.extend(pooled.into_iter().flatten());
+    }
+    if let Some(recorder) = &recorder
+        && let Err(e) = recorder.save()
+    {
+        error!("{}", t_args!("error-validation-failed", "error" => format!("{e:#}")));
+        std::process::exit(2);
+    }
+    let relation_summaries = check_relations(&relations, &suite_summaries, &cli);
+    for (suite_name, summary) in suite_summaries {
+        // Track files with failures
+        if summary.failed > 0 {
+            failed_files.push(suite_name);
+        }
+        add_to_aggregate(&mut aggregate, summary);
     }
 
     // Calculate final counts using the same method as the report
     let all_cases: Vec<&morph_test2::types::CaseResult> = aggregate.cases.iter().collect();
-    let (total_passes, total_fails, total_checks) = calculate_counts(&all_cases, cli.ignore_extra_analyses);
+    let (mut total_passes, mut total_fails, mut total_checks) =
+        calculate_counts(&all_cases, cli.ignore_extra_analyses);
+    // Extra analyses are what a superset may have, so relations count them as passes
+    for (name, summary) in relation_summaries {
+        let cases: Vec<&morph_test2::types::CaseResult> = summary.cases.iter().collect();
+        let (passes, fails, checks) = calculate_counts(&cases, true);
+        total_passes += passes;
+        total_fails += fails;
+        total_checks += checks;
+        if summary.failed > 0 {
+            failed_files.push(name);
+        }
+    }
 
     if cli.verbose && !cli.silent {
         info!(
@@ -568,25 +808,8 @@ async fn main() -> Result<()> {
 
     if total_fails > 0 {
         // Report failing files in quiet mode or when there are multiple files
-        if (cli.silent || !cli.verbose) {
-            if !failed_files.is_empty() {
-                eprintln!("Tests failed in files: {}", failed_files.join(", "));
-            } else {
-                // Fallback: extract file names from failing test cases (for pool mode)
-                let mut failing_files = std::collections::HashSet::new();
-                for case in &aggregate.cases {
-                    if !case.passed {
-                        // Try to extract file name from the test suite structure
-                        // This is a heuristic approach since we don't have direct file tracking in pool mode
-                        // We could improve this by modifying the data structures
-                        failing_files.insert("multiple files");
-                        break;
-                    }
-                }
-                if !failing_files.is_empty() {
-                    eprintln!("Tests failed (use --serial for detailed file reporting)");
-                }
-            }
+        if (cli.silent || !cli.verbose) && !failed_files.is_empty() {
+            eprintln!("Tests failed in files: {}", failed_files.join(", "));
         }
         std::process::exit(1);
     }
@@ -594,294 +817,322 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn process_suites_sequential(
-    suites: Vec<morph_test2::spec::SuiteWithConfig>,
-    cli: &Cli,
-    aggregate: &mut morph_test2::types::Summary,
-    failed_files: &mut Vec<String>,
-) -> Result<()> {
-    // Run per suite
-    for swc in suites {
-        // Override from CLI
-        let effective_gen = cli.generator.clone().unwrap_or_else(|| swc.gen_fst.clone());
-        let effective_morph = cli.analyser.clone().or(swc.morph_fst.clone());
-
-        let effective_lookup = if let Some(lookup) = &cli.lookup_tool {
-            lookup.trim().to_string()
-        } else {
-            // If CLI FSTs are provided but no lookup tool, determine based on FST extensions
-            if cli.generator.is_some() || cli.analyser.is_some() {
-                // Check if this is an HFST backend
-                if matches!(swc.backend, BackendChoice::Hfst) {
-                    determine_hfst_lookup_tool(&effective_gen, effective_morph.as_deref())
-                } else {
-                    swc.lookup_cmd.clone()
-                }
+/// Transducers and lookup command for a suite, after CLI overrides
+fn effective_setup(swc: &morph_test2::spec::SuiteWithConfig, cli: &Cli) -> (String, Option<String>, String) {
+    // A service does both directions itself
+    if let Some(url) = http_endpoint(swc, cli) {
+        return (url.clone(), Some(url.clone()), url);
+    }
+    let effective_gen = cli.generator.clone().unwrap_or_else(|| swc.gen_fst.clone());
+    let effective_morph = cli.analyser.clone().or(swc.morph_fst.clone());
+    let effective_lookup = if let Some(lookup) = &cli.lookup_tool {
+        lookup.trim().to_string()
+    } else {
+        // If CLI FSTs are provided but no lookup tool, determine based on FST extensions
+        if cli.generator.is_some() || cli.analyser.is_some() {
+            // Check if this is an HFST backend without an `App` of its own
+            if matches!(swc.backend, BackendChoice::Hfst) && swc.app.is_none() {
+                determine_hfst_lookup_tool(&effective_gen, effective_morph.as_deref())
+            } else if matches!(swc.backend, BackendChoice::Apertium) && swc.app.is_none() {
+                default_proc_tool(&effective_gen, effective_morph.as_deref())
             } else {
                 swc.lookup_cmd.clone()
             }
-        };
-
-        if cli.verbose && !cli.silent {
-            let lookup_full = resolve_lookup_path(&effective_lookup);
-            let gen_full = display_path(&effective_gen);
-            let morph_full = effective_morph
-                .as_deref()
-                .map(display_path)
-                .unwrap_or_else(|| "-".to_string());
-            let mode_txt = if cli.surface {
-                t!("mode-analyze-only")
-            } else if cli.lexical {
-                t!("mode-generate-only")
-            } else {
-                t!("mode-all")
-            };
-            info!("{}", t_args!("info-suite", "name" => &swc.suite.name));
-            info!("{}", t_args!("info-lookup-tool", "path" => &lookup_full));
-            info!("{}", t_args!("info-generator", "path" => &gen_full));
-            info!("{}", t_args!("info-analyzer", "path" => &morph_full));
-            info!(
-                "{}",
-                t_args!("info-starting-tests",
-                    "count" => swc.suite.cases.len(),
-                    "mode" => &mode_txt
-                )
-            );
+        } else {
+            swc.lookup_cmd.clone()
         }
+    };
+    (effective_gen, effective_morph, effective_lookup)
+}
 
-        let backend = ExternalBackend {
-            lookup_cmd: effective_lookup,
-            generator_fst: Some(effective_gen),
-            analyzer_fst: effective_morph,
-            timeout: Some(DEFAULT_TIMEOUT),
-            quiet: cli.silent,
-        };
-
-        // Validate backend before running tests - fail fast on configuration errors
-        if let Err(e) = backend.validate() {
-            error!("{}", t_args!("error-validation-failed", "error" => e));
-            std::process::exit(2);
-        }
+/// Suites with the same key can share a backend, and with it their lookups
+fn backend_key(swc: &morph_test2::spec::SuiteWithConfig, cli: &Cli) -> String {
+    let (effective_gen, effective_morph, effective_lookup) = effective_setup(swc, cli);
+    format!(
+        "{:?}__{}__{:?}__{:?}__{:?}__{}__{}__{:?}__{}",
+        swc.backend,
+        effective_lookup,
+        swc.lookup_args,
+        swc.output_parser,
+        swc.normalizer,
+        effective_gen,
+        effective_morph.unwrap_or_default(),
+        effective_timeout(swc, cli),
+        uses_native(swc, cli)
+    )
+}
 
-        let suite_name = swc.suite.name.clone();
-        let summary = run_suites(&backend, &[swc.suite], cli.ignore_extra_analyses);
+/// Recorded lookups are kept apart by what the raw outputs depend on, which
+/// leaves out the normalizing and the timeout of `backend_key`
+fn fixture_key(swc: &morph_test2::spec::SuiteWithConfig, cli: &Cli) -> String {
+    let (effective_gen, effective_morph, effective_lookup) = effective_setup(swc, cli);
+    let backend = if uses_native(swc, cli) { "native" } else { swc.backend.name() };
+    let fsts: Vec<&str> = std::iter::once(effective_gen.as_str()).chain(effective_morph.as_deref()).collect();
+    morph_test2::fixture::fixture_key(backend, &effective_lookup, &swc.lookup_args, swc.output_parser.as_ref(), &fsts)
+}
 
-        if cli.verbose && !cli.silent {
-            info!(
-                "{}",
-                t_args!("info-finished",
-                    "passed" => summary.passed,
-                    "failed" => summary.failed
-                )
-            );
-        }
+/// Group suites by backend, keeping the order in which they were loaded
+fn group_by_backend(
+    suites: Vec<morph_test2::spec::SuiteWithConfig>,
+    cli: &Cli,
+) -> IndexMap<String, Vec<morph_test2::spec::SuiteWithConfig>> {
+    let mut groups: IndexMap<String, Vec<morph_test2::spec::SuiteWithConfig>> = IndexMap::new();
+    for swc in suites {
+        groups.entry(backend_key(&swc, cli)).or_default().push(swc);
+    }
+    groups
+}
 
-        if !cli.silent {
-            print_human(
-                &summary,
-                cli.ignore_extra_analyses,
-                cli.verbose,
-                cli.hide_fails,
-                cli.hide_passes,
-                cli.output.into(),
-            );
-        }
+fn log_suite_start(swc: &morph_test2::spec::SuiteWithConfig, cli: &Cli) {
+    let (effective_gen, effective_morph, effective_lookup) = effective_setup(swc, cli);
+    let lookup_full = if uses_native(swc, cli) {
+        t!("info-native-lookup")
+    } else {
+        resolve_lookup_path(&effective_lookup)
+    };
+    let gen_full = display_path(&effective_gen);
+    let morph_full = effective_morph
+        .as_deref()
+        .map(display_path)
+        .unwrap_or_else(|| "-".to_string());
+    let mode_txt = if cli.surface {
+        t!("mode-analyze-only")
+    } else if cli.lexical {
+        t!("mode-generate-only")
+    } else {
+        t!("mode-all")
+    };
+    info!("{}", t_args!("info-suite", "name" => &swc.suite.name));
+    info!("{}", t_args!("info-lookup-tool", "path" => &lookup_full));
+    info!("{}", t_args!("info-generator", "path" => &gen_full));
+    info!("{}", t_args!("info-analyzer", "path" => &morph_full));
+    info!(
+        "{}",
+        t_args!("info-starting-tests",
+            "count" => swc.suite.cases.len(),
+            "mode" => &mode_txt
+        )
+    );
+}
 
-        // Track files with failures
-        if summary.failed > 0 {
-            failed_files.push(suite_name);
-        }
+fn report_suite(summary: &morph_test2::types::Summary, cli: &Cli, ignore_extra_analyses: bool) {
+    if cli.verbose && !cli.silent {
+        info!(
+            "{}",
+            t_args!("info-finished",
+                "passed" => summary.passed,
+                "failed" => summary.failed
+            )
+        );
+    }
 
-        aggregate.total += summary.total;
-        aggregate.passed += summary.passed;
-        aggregate.failed += summary.failed;
-        aggregate.total_expectations += summary.total_expectations;
-        aggregate.passed_expectations += summary.passed_expectations;
-        aggregate.failed_expectations += summary.failed_expectations;
-        aggregate.cases.extend(summary.cases);
+    if !cli.silent {
+        print_human(
+            summary,
+            ignore_extra_analyses,
+            cli.verbose,
+            cli.hide_fails,
+            cli.hide_passes,
+            cli.output.into(),
+        );
     }
-    Ok(())
 }
 
-async fn process_suites_with_pool(
-    suites: Vec<morph_test2::spec::SuiteWithConfig>,
+/// Check and report the relations whose suites have both run, returning
+/// each relation's name and summary
+fn check_relations(
+    relations: &IndexSet<Relation>,
+    suite_summaries: &[(String, morph_test2::types::Summary)],
     cli: &Cli,
-    aggregate: &mut morph_test2::types::Summary,
-    failed_files: &mut Vec<String>,
-) -> Result<()> {
-    // Group suites by backend configuration to share pools
-    let mut backend_groups: HashMap<String, Vec<morph_test2::spec::SuiteWithConfig>> =
-        HashMap::new();
-
-    for swc in suites {
-        // Create a key based on lookup command and FST files
-        let effective_gen = cli.generator.clone().unwrap_or_else(|| swc.gen_fst.clone());
-        let effective_morph = cli.analyser.clone().or(swc.morph_fst.clone());
-        let effective_lookup = if let Some(lookup) = &cli.lookup_tool {
-            lookup.trim().to_string()
-        } else {
-            // If CLI FSTs are provided but no lookup tool, determine based on FST extensions
-            if cli.generator.is_some() || cli.analyser.is_some() {
-                // Check if this is an HFST backend
-                if matches!(swc.backend, BackendChoice::Hfst) {
-                    determine_hfst_lookup_tool(&effective_gen, effective_morph.as_deref())
-                } else {
-                    swc.lookup_cmd.clone()
-                }
-            } else {
-                swc.lookup_cmd.clone()
-            }
+) -> Vec<(String, morph_test2::types::Summary)> {
+    let summary_of = |name: &str| {
+        suite_summaries
+            .iter()
+            .find(|(suite_name, _)| suite_name == name)
+            .map(|(_, summary)| summary)
+    };
+    let mut out = Vec::new();
+    for relation in relations {
+        let (Some(superset), Some(subset)) = (summary_of(&relation.superset), summary_of(&relation.subset)) else {
+            continue;
         };
+        let summary = check_relation(relation, superset, subset);
+        if summary.cases.is_empty() {
+            continue;
+        }
+        if cli.verbose && !cli.silent {
+            info!("{}", t_args!("info-suite", "name" => &relation.name));
+        }
+        report_suite(&summary, cli, true);
+        out.push((relation.name.clone(), summary));
+    }
+    out
+}
 
-        let key = format!(
-            "{}__{}__{}",
-            effective_lookup,
-            effective_gen,
-            effective_morph.unwrap_or_default()
-        );
-
-        backend_groups.entry(key).or_default().push(swc);
-    }
-
-    // Process each backend group in parallel
-    let group_futures: Vec<_> = backend_groups
-        .into_values()
-        .map(|group_suites| {
-            let cli = cli.clone();
-            async move {
-                // Create pooled backend for this group
-                let first_suite = &group_suites[0];
-                let effective_gen = cli
-                    .generator
-                    .clone()
-                    .unwrap_or_else(|| first_suite.gen_fst.clone());
-                let effective_morph = cli.analyser.clone().or(first_suite.morph_fst.clone());
-                let effective_lookup = if let Some(lookup) = &cli.lookup_tool {
-                    lookup.trim().to_string()
-                } else {
-                    // If CLI FSTs are provided but no lookup tool, determine based on FST extensions
-                    if cli.generator.is_some() || cli.analyser.is_some() {
-                        // Check if this is an HFST backend
-                        if matches!(first_suite.backend, BackendChoice::Hfst) {
-                            determine_hfst_lookup_tool(&effective_gen, effective_morph.as_deref())
-                        } else {
-                            first_suite.lookup_cmd.clone()
-                        }
-                    } else {
-                        first_suite.lookup_cmd.clone()
-                    }
-                };
-
-                let pooled_backend = PooledBackend::new(
-                    effective_lookup,
-                    effective_morph,
-                    Some(effective_gen),
-                    cli.silent,
-                )
-                .await?;
-
-                // Validate backend
-                pooled_backend.validate().await?;
-
-                // Process all suites in this group sequentially (they share the same backend)
-                let mut group_summaries = Vec::new();
-                for swc in group_suites {
-                    if cli.verbose && !cli.silent {
-                        let effective_gen = cli.generator.clone().unwrap_or_else(|| swc.gen_fst.clone());
-                        let effective_morph = cli.analyser.clone().or(swc.morph_fst.clone());
-                        let effective_lookup = if let Some(lookup) = &cli.lookup_tool {
-                            lookup.trim().to_string()
-                        } else {
-                            // If CLI FSTs are provided but no lookup tool, determine based on FST extensions
-                            if cli.generator.is_some() || cli.analyser.is_some() {
-                                // Check if this is an HFST backend
-                                if matches!(swc.backend, BackendChoice::Hfst) {
-                                    determine_hfst_lookup_tool(&effective_gen, effective_morph.as_deref())
-                                } else {
-                                    swc.lookup_cmd.clone()
-                                }
-                            } else {
-                                swc.lookup_cmd.clone()
-                            }
-                        };
-
-                        let lookup_full = resolve_lookup_path(&effective_lookup);
-                        let gen_full = display_path(&effective_gen);
-                        let morph_full = effective_morph
-                            .as_deref()
-                            .map(display_path)
-                            .unwrap_or_else(|| "-".to_string());
-                        let mode_txt = if cli.surface {
-                            t!("mode-analyze-only")
-                        } else if cli.lexical {
-                            t!("mode-generate-only")
-                        } else {
-                            t!("mode-all")
-                        };
-                        info!("{}", t_args!("info-suite", "name" => &swc.suite.name));
-                        info!("{}", t_args!("info-lookup-tool", "path" => &lookup_full));
-                        info!("{}", t_args!("info-generator", "path" => &gen_full));
-                        info!("{}", t_args!("info-analyzer", "path" => &morph_full));
-                        info!(
-                            "{}",
-                            t_args!("info-starting-tests",
-                                "count" => swc.suite.cases.len(),
-                                "mode" => &mode_txt
-                            )
-                        );
-                    }
-
-                    let suite_name = swc.suite.name.clone();
-                    let summary =
-                        run_suites_async(&pooled_backend, &[swc.suite.clone()], cli.ignore_extra_analyses)
-                            .await?;
-
-                    if cli.verbose && !cli.silent {
-                        info!(
-                            "{}",
-                            t_args!("info-finished",
-                                "passed" => summary.passed,
-                                "failed" => summary.failed
-                            )
-                        );
-                    }
+fn add_to_aggregate(aggregate: &mut morph_test2::types::Summary, summary: morph_test2::types::Summary) {
+    aggregate.total += summary.total;
+    aggregate.passed += summary.passed;
+    aggregate.failed += summary.failed;
+    aggregate.total_expectations += summary.total_expectations;
+    aggregate.passed_expectations += summary.passed_expectations;
+    aggregate.failed_expectations += summary.failed_expectations;
+    aggregate.cases.extend(summary.cases);
+}
 
-                    if !cli.silent {
-                        print_human(
-                            &summary,
-                            cli.ignore_extra_analyses,
-                            cli.verbose,
-                            cli.hide_fails,
-                            cli.hide_passes,
-                            cli.output.into(),
-                        );
-                    }
+/// The fixture the run records into or replays from, if any
+#[derive(Clone, Copy)]
+struct Fixtures<'a> {
+    recorder: Option<&'a Recorder>,
+    replay: Option<(&'a Path, &'a Fixture)>,
+}
 
-                    group_summaries.push(summary);
-                }
+/// Build the backend for a group of suites: a recorded fixture, the built-in
+/// reader, a morphology service, a command template, an Apertium tool, a pool
+/// of lookup processes, or a lookup process per batch, with its outputs normalized
+async fn build_backend(
+    swc: &morph_test2::spec::SuiteWithConfig,
+    cli: &Cli,
+    pooled: bool,
+    fixtures: Fixtures<'_>,
+) -> Result<Box<dyn Backend>> {
+    let backend: Box<dyn Backend> = match fixtures.replay {
+        Some((path, fixture)) => Box::new(ReplayBackend::new(path, fixture, &fixture_key(swc, cli))),
+        None => recorded_backend(swc, cli, pooled, fixtures.recorder).await?,
+    };
+    // Caches and fixtures keep the raw outputs, so normalizing comes last
+    Ok(if swc.normalizer.is_identity() {
+        backend
+    } else {
+        Box::new(NormalizingBackend::new(backend, swc.normalizer.clone()))
+    })
+}
 
-                Ok::<Vec<morph_test2::types::Summary>, anyhow::Error>(group_summaries)
-            }
+/// The backend looking up the transducers, with the lookups cached and recorded
+async fn recorded_backend(
+    swc: &morph_test2::spec::SuiteWithConfig,
+    cli: &Cli,
+    pooled: bool,
+    recorder: Option<&Recorder>,
+) -> Result<Box<dyn Backend>> {
+    let (effective_gen, effective_morph, effective_lookup) = effective_setup(swc, cli);
+    let native = uses_native(swc, cli);
+    let endpoint = http_endpoint(swc, cli);
+    let backend: Box<dyn Backend> = if native {
+        Box::new(NativeBackend {
+            timeout: Some(effective_timeout(swc, cli)),
+            ..NativeBackend::new(Some(&effective_gen), effective_morph.as_deref())?
+        })
+    } else if let Some(url) = &endpoint {
+        Box::new(HttpBackend::new(url, Some(effective_timeout(swc, cli))))
+    } else if let Some(parser) = &swc.output_parser {
+        Box::new(CommandBackend {
+            program: effective_lookup.clone(),
+            args: swc.lookup_args.clone(),
+            parser: parser.clone(),
+            generator_fst: Some(effective_gen.clone()),
+            analyzer_fst: effective_morph.clone(),
+            timeout: Some(effective_timeout(swc, cli)),
+            quiet: cli.silent,
+        })
+    } else if matches!(swc.backend, BackendChoice::Apertium) {
+        Box::new(ApertiumBackend {
+            proc_cmd: effective_lookup.clone(),
+            args: swc.lookup_args.clone(),
+            generator_fst: Some(effective_gen.clone()),
+            analyzer_fst: effective_morph.clone(),
+            timeout: Some(effective_timeout(swc, cli)),
+            quiet: cli.silent,
         })
-        .collect();
+    } else if pooled {
+        Box::new(
+            PooledBackend::new(
+                effective_lookup.clone(),
+                swc.lookup_args.clone(),
+                effective_morph.clone(),
+                Some(effective_gen.clone()),
+                cli.silent,
+                effective_timeout(swc, cli),
+            )
+            .await?,
+        )
+    } else {
+        Box::new(ExternalBackend {
+            lookup_cmd: effective_lookup.clone(),
+            args: swc.lookup_args.clone(),
+            generator_fst: Some(effective_gen.clone()),
+            analyzer_fst: effective_morph.clone(),
+            timeout: Some(effective_timeout(swc, cli)),
+            quiet: cli.silent,
+        })
+    };
+    // There is no transducer file to key a service's results on
+    let backend = match cache_dir(cli).filter(|_| endpoint.is_none()) {
+        Some(dir) => {
+            // The built-in reader has no command, but its results are keyed apart all the same,
+            // and a command's results depend on how its output is read
+            let (lookup_key, args) = if native {
+                (NATIVE_CACHE_KEY.to_string(), LookupArgs::default())
+            } else if let Some(parser) = &swc.output_parser {
+                (format!("{effective_lookup}\0{parser:?}"), swc.lookup_args.clone())
+            } else {
+                (effective_lookup.clone(), swc.lookup_args.clone())
+            };
+            CachedBackend::new(backend, &dir, &lookup_key, &args, effective_morph.as_deref(), Some(&effective_gen))?
+        }
+        None => CachedBackend::uncached(backend),
+    };
+    // Recording goes around the cache, so that cached answers are recorded too
+    Ok(match recorder {
+        Some(recorder) => Box::new(RecordingBackend::new(backend, recorder.clone(), fixture_key(swc, cli))),
+        None => Box::new(backend),
+    })
+}
 
-    // TODO: For now, pool mode doesn't track failed files
-    // This is complex to implement due to the parallel processing structure
-    // Users can use --serial flag to get failed file reporting
-    
-    // Await all groups and aggregate results
-    let all_group_results = try_join_all(group_futures).await?;
-    for group_summaries in all_group_results {
-        for summary in group_summaries {
-            aggregate.total += summary.total;
-            aggregate.passed += summary.passed;
-            aggregate.failed += summary.failed;
-            aggregate.total_expectations += summary.total_expectations;
-            aggregate.passed_expectations += summary.passed_expectations;
-            aggregate.failed_expectations += summary.failed_expectations;
-            aggregate.cases.extend(summary.cases);
+/// Run a group of suites sharing a backend, returning each suite's name and summary
+async fn run_group(
+    group: Vec<morph_test2::spec::SuiteWithConfig>,
+    cli: &Cli,
+    pooled: bool,
+    fixtures: Fixtures<'_>,
+) -> Result<Vec<(String, morph_test2::types::Summary)>> {
+    // Validate backend before running tests - fail fast on configuration errors
+    let backend = match build_backend(&group[0], cli, pooled, fixtures).await {
+        Ok(backend) => backend,
+        Err(e) => {
+            error!("{}", t_args!("error-validation-failed", "error" => format!("{e:#}")));
+            std::process::exit(2);
         }
+    };
+    if let Err(e) = backend.validate().await {
+        error!("{}", t_args!("error-validation-failed", "error" => e));
+        std::process::exit(2);
     }
 
-    Ok(())
+    if cli.verbose && !cli.silent {
+        for swc in &group {
+            log_suite_start(swc, cli);
+        }
+    }
+
+    let (effective_gen, effective_morph, _) = effective_setup(&group[0], cli);
+    let suites: Vec<TestSuite> = group.into_iter().map(|swc| swc.suite).collect();
+    let summaries = run_suites_separately(&backend, &suites, cli.ignore_extra_analyses).await;
+    // Fixtures hold only the lookups of the tests themselves
+    if cli.record.is_none()
+        && cli.replay.is_none()
+        && let Some(input) = swapped_direction_hint(&backend, &summaries).await
+    {
+        warn!(
+            "{}",
+            t_args!("warn-fsts-swapped",
+                "input" => input,
+                "generator" => effective_gen,
+                "analyser" => effective_morph.unwrap_or_default()
+            )
+        );
+    }
+    for summary in &summaries {
+        report_suite(summary, cli, cli.ignore_extra_analyses);
+    }
+    Ok(suites.into_iter().map(|suite| suite.name).zip(summaries).collect())
 }
@@ -0,0 +1,246 @@
+use crate::att::parse_att;
+use crate::backend::{Backend, DEFAULT_TIMEOUT};
+use crate::foma::{is_gzip, read_foma};
+use crate::format::{FstFormat, sniff_format};
+use crate::fst::{Apply, DirectedFst};
+use crate::hfstol::HfstolTransducer;
+use crate::types::{LookupOutcome, LookupOutput};
+use crate::{t, t_args};
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// A transducer loaded into memory that can be applied to an input string
+pub trait Transducer: Send + Sync {
+    /// Look up an input, or give up with `None` once `deadline` has passed
+    fn lookup_until(&self, input: &str, deadline: Option<Instant>) -> Option<Vec<LookupOutput>>;
+
+    fn lookup(&self, input: &str) -> Vec<LookupOutput> {
+        self.lookup_until(input, None).unwrap_or_default()
+    }
+
+    /// The same transducer applied in the opposite direction, if the format allows it
+    fn inverted(&self) -> Option<Arc<dyn Transducer>> {
+        None
+    }
+}
+
+/// Outputs with the lowest weight over all paths, best first like the lookup tools list them
+pub(crate) fn ranked(results: HashMap<String, f32>, weighted: bool) -> Vec<LookupOutput> {
+    let mut results: Vec<(String, f32)> = results.into_iter().collect();
+    results.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    results
+        .into_iter()
+        .map(|(output, weight)| LookupOutput::new(output, weighted.then_some(weight as f64)))
+        .collect()
+}
+
+/// Steps of a lookup between readings of the clock
+const CLOCK_EVERY: u32 = 1024;
+
+/// The time a lookup may take. Paths are only cut off at a certain length, so
+/// an infinitely ambiguous compound can still have more of them than can be
+/// walked; the walk checks in with its deadline on every step.
+#[derive(Debug)]
+pub(crate) struct Deadline {
+    at: Option<Instant>,
+    steps: u32,
+    passed: bool,
+}
+
+impl Deadline {
+    pub(crate) fn new(at: Option<Instant>) -> Self {
+        Self {
+            at,
+            steps: 0,
+            passed: false,
+        }
+    }
+
+    /// Count a step, returning true once the deadline has passed
+    pub(crate) fn tick(&mut self) -> bool {
+        self.steps = self.steps.wrapping_add(1);
+        if !self.passed
+            && self.steps.is_multiple_of(CLOCK_EVERY)
+            && let Some(at) = self.at
+        {
+            self.passed = Instant::now() >= at;
+        }
+        self.passed
+    }
+
+    pub(crate) fn passed(&self) -> bool {
+        self.passed
+    }
+}
+
+/// Keep the lowest weight seen for an output
+pub(crate) fn note_result(results: &mut HashMap<String, f32>, output: String, weight: f32) {
+    results
+        .entry(output)
+        .and_modify(|w| *w = w.min(weight))
+        .or_insert(weight);
+}
+
+/// Loaded transducers by path, so suites sharing an FST only read it once
+static LOADED: OnceLock<Mutex<HashMap<String, Arc<dyn Transducer>>>> = OnceLock::new();
+
+/// Load a transducer file, reusing an already loaded copy when possible
+pub fn load_transducer(path: &str) -> Result<Arc<dyn Transducer>> {
+    let cache = LOADED.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(fst) = cache.lock().unwrap().get(path) {
+        return Ok(fst.clone());
+    }
+    debug!("{}", t_args!("debug-native-load", "fst" => path));
+    let data = std::fs::read(path)
+        .with_context(|| t_args!("native-failed-to-read", "file" => path))?;
+    let fst = parse_transducer(path, &data)
+        .with_context(|| t_args!("native-failed-to-load", "file" => path))?;
+    cache
+        .lock()
+        .unwrap()
+        .insert(path.to_string(), fst.clone());
+    Ok(fst)
+}
+
+fn parse_transducer(path: &str, data: &[u8]) -> Result<Arc<dyn Transducer>> {
+    if sniff_format(path, data) == FstFormat::Att {
+        Ok(Arc::new(DirectedFst {
+            fst: Arc::new(parse_att(&String::from_utf8_lossy(data))?),
+            apply: Apply::Down,
+        }))
+    } else if is_gzip(data) {
+        // Apply up, like `flookup` does by default
+        Ok(Arc::new(DirectedFst {
+            fst: Arc::new(read_foma(data)?),
+            apply: Apply::Up,
+        }))
+    } else {
+        Ok(Arc::new(HfstolTransducer::from_bytes(data)?))
+    }
+}
+
+/// Backend that performs lookups in-process instead of spawning a lookup tool
+pub struct NativeBackend {
+    pub analyzer: Option<Arc<dyn Transducer>>,
+    pub generator: Option<Arc<dyn Transducer>>,
+    pub timeout: Option<Duration>, // per input
+}
+
+impl NativeBackend {
+    /// Load the given transducers. When only one is given and its format can be
+    /// applied in both directions, it is used for the other direction as well.
+    pub fn new(generator_fst: Option<&str>, analyzer_fst: Option<&str>) -> Result<Self> {
+        let generator = generator_fst.map(load_transducer).transpose()?;
+        let mut analyzer = analyzer_fst.map(load_transducer).transpose()?;
+        if analyzer.is_none() {
+            analyzer = generator.as_ref().and_then(|g| g.inverted());
+        }
+        let generator = generator.or_else(|| analyzer.as_ref().and_then(|a| a.inverted()));
+        Ok(Self {
+            analyzer,
+            generator,
+            timeout: None,
+        })
+    }
+
+    /// Look up inputs in parallel, off the async runtime
+    async fn lookup_batch(&self, fst: Arc<dyn Transducer>, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        let inputs = inputs.to_vec();
+        let results = tokio::task::spawn_blocking(move || {
+            inputs
+                .par_iter()
+                .map(|input| {
+                    let input = input.trim();
+                    fst.lookup_until(input, Some(Instant::now() + timeout)).ok_or_else(|| {
+                        t_args!("backend-input-timeout",
+                            "seconds" => timeout.as_secs_f64(),
+                            "input" => input
+                        )
+                    })
+                })
+                .collect()
+        })
+        .await?;
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl Backend for NativeBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let fst = self
+            .analyzer
+            .clone()
+            .ok_or_else(|| anyhow!(t!("backend-analyzer-not-set")))?;
+        self.lookup_batch(fst, inputs).await
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let fst = self
+            .generator
+            .clone()
+            .ok_or_else(|| anyhow!(t!("backend-generator-not-set")))?;
+        self.lookup_batch(fst, inputs).await
+    }
+
+    async fn validate(&self) -> Result<()> {
+        // The transducers were parsed when the backend was created
+        Ok(())
+    }
+}
+
+/// Splits input strings into symbol numbers by longest match against an alphabet
+#[derive(Debug, Default)]
+pub struct Tokenizer {
+    // Candidate symbols per first character, longest first
+    by_first_char: HashMap<char, Vec<(String, usize)>>,
+}
+
+impl Tokenizer {
+    pub fn new<'a>(symbols: impl IntoIterator<Item = (&'a str, usize)>) -> Self {
+        let mut by_first_char: HashMap<char, Vec<(String, usize)>> = HashMap::new();
+        for (symbol, number) in symbols {
+            if let Some(first) = symbol.chars().next() {
+                by_first_char
+                    .entry(first)
+                    .or_default()
+                    .push((symbol.to_string(), number));
+            }
+        }
+        for candidates in by_first_char.values_mut() {
+            candidates.sort_by_key(|(symbol, _)| std::cmp::Reverse(symbol.len()));
+        }
+        Self { by_first_char }
+    }
+
+    /// Returns `None` if some part of the input is not in the alphabet
+    pub fn tokenize(&self, input: &str) -> Option<Vec<usize>> {
+        self.segment(input).into_iter().map(|(_, n)| n).collect()
+    }
+
+    /// Split the input into symbols, with characters outside the alphabet as `None`
+    pub fn segment<'a>(&self, input: &'a str) -> Vec<(&'a str, Option<usize>)> {
+        let mut out = Vec::new();
+        let mut rest = input;
+        while let Some(first) = rest.chars().next() {
+            let known = self.by_first_char.get(&first).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .find(|(symbol, _)| rest.starts_with(symbol.as_str()))
+            });
+            let (len, number) = match known {
+                Some((symbol, number)) => (symbol.len(), Some(*number)),
+                None => (first.len_utf8(), None),
+            };
+            out.push((&rest[..len], number));
+            rest = &rest[len..];
+        }
+        out
+    }
+}
@@ -0,0 +1,105 @@
+use crate::backend::Backend;
+use crate::flags::is_flag_diacritic;
+use crate::types::{LookupOutcome, LookupOutput};
+use anyhow::Result;
+use async_trait::async_trait;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Anything that may be a flag diacritic, checked with `is_flag_diacritic`
+static FLAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"@[PNRDCU]\.[^@\s]+@").unwrap());
+
+/// How lookup outputs are rewritten before they are compared with the
+/// expectations: first the replacements, in order, then flag diacritics are
+/// stripped, then the symbols to remove are removed. The default leaves
+/// every output as the backend gave it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Normalizer {
+    pub strip_flags: bool,
+    pub replace: Vec<(String, String)>,
+    pub remove: Vec<String>,
+}
+
+impl Normalizer {
+    /// Whether normalizing leaves every output as it is
+    pub fn is_identity(&self) -> bool {
+        !self.strip_flags && self.replace.is_empty() && self.remove.is_empty()
+    }
+
+    pub fn normalize(&self, output: &str) -> String {
+        let mut output = output.to_string();
+        for (from, to) in &self.replace {
+            output = output.replace(from.as_str(), to);
+        }
+        if self.strip_flags && output.contains('@') {
+            output = FLAG
+                .replace_all(&output, |caps: &regex::Captures| {
+                    if is_flag_diacritic(&caps[0]) {
+                        String::new()
+                    } else {
+                        caps[0].to_string()
+                    }
+                })
+                .into_owned();
+        }
+        for symbol in &self.remove {
+            output = output.replace(symbol.as_str(), "");
+        }
+        output
+    }
+
+    /// Normalize the outputs of a lookup, keeping the raw form of those that change
+    pub fn apply(&self, outcome: LookupOutcome) -> LookupOutcome {
+        outcome.map(|outputs| {
+            outputs
+                .into_iter()
+                .map(|output| {
+                    let normalized = self.normalize(&output.output);
+                    if normalized == output.output {
+                        return output;
+                    }
+                    LookupOutput {
+                        raw: Some(output.output),
+                        output: normalized,
+                        ..output
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+/// Wraps a backend and normalizes the outputs of every lookup
+pub struct NormalizingBackend<B> {
+    pub inner: B,
+    normalizer: Normalizer,
+}
+
+impl<B> NormalizingBackend<B> {
+    pub fn new(inner: B, normalizer: Normalizer) -> Self {
+        Self { inner, normalizer }
+    }
+}
+
+#[async_trait]
+impl<B: Backend> Backend for NormalizingBackend<B> {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let outcomes = self.inner.analyze_batch(inputs).await?;
+        Ok(outcomes
+            .into_iter()
+            .map(|outcome| self.normalizer.apply(outcome))
+            .collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let outcomes = self.inner.generate_batch(inputs).await?;
+        Ok(outcomes
+            .into_iter()
+            .map(|outcome| self.normalizer.apply(outcome))
+            .collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        self.inner.validate().await
+    }
+}
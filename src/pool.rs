@@ -1,8 +1,11 @@
+use crate::backend::{Backend, LookupArgs, Record};
+use crate::format::check_fsts;
+use crate::types::{LookupOutcome, LookupOutput};
 use crate::{t, t_args};
 use anyhow::{Context, Result, anyhow};
-use deadpool::managed::{Manager, Metrics, Pool, RecycleError, RecycleResult};
-use futures::future::try_join_all;
-use indexmap::IndexMap;
+use async_trait::async_trait;
+use deadpool::managed::{Manager, Metrics, Object, Pool, RecycleError, RecycleResult};
+use futures::future::{BoxFuture, try_join_all};
 use std::borrow::Cow;
 use std::process::Stdio;
 use std::time::Duration;
@@ -10,101 +13,73 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
 use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 use tracing::debug;
 
-/// 30 seconds per lookup
-pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
-
 /// A persistent FST process that can handle multiple queries
 pub struct FstProcess {
     pub child: Child,
     pub stdin: BufWriter<ChildStdin>,
     pub stdout: BufReader<ChildStdout>,
+    pub timeout: Duration, // per input
 }
 
 impl FstProcess {
-    /// Send a batch of inputs and read results
-    pub async fn process_batch(&mut self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
+    /// Send a batch of inputs and read results. Lookup tools answer every input
+    /// line with a record of one or more `input\toutput` lines followed by a blank
+    /// line, so the batch is complete once a record has been read for each input.
+    pub async fn process_batch(&mut self, inputs: &[String]) -> Result<Vec<Vec<LookupOutput>>> {
         debug!("{}", t_args!("debug-pool-batch", "count" => inputs.len()));
-        // Send all inputs
-        for input in inputs {
-            let input_trimmed = input.trim();
-            self.stdin.write_all(input_trimmed.as_bytes()).await?;
-            self.stdin.write_all(b"\n").await?;
-        }
-        self.stdin.flush().await?;
-
-        // Read results - FST tools output input\toutput format
-        let mut results_map: IndexMap<String, std::collections::BTreeSet<String>> = IndexMap::new();
-        let mut lines_read = 0;
-        let _expected_inputs: std::collections::HashSet<&str> =
-            inputs.iter().map(|s| s.trim()).collect();
-
-        // Initialize all inputs in the map to preserve order and handle no-result cases
-        for input in inputs {
-            results_map.insert(input.trim().to_string(), std::collections::BTreeSet::new());
-        }
-
-        // Read all available output until timeout or reasonable limit
-        let mut line = String::new();
-        let max_lines = inputs.len() * 250; // Much more generous limit for multiple results per input
-
-        while lines_read < max_lines {
-            line.clear();
-            match tokio::time::timeout(Duration::from_millis(6000), self.stdout.read_line(&mut line))
-                .await
-            {
-                Ok(Ok(0)) => break, // EOF
-                Ok(Ok(_)) => {
-                    lines_read += 1;
-                    let trimmed = line.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
+        // Empty lines get no record from some tools, so they are not sent
+        let sent: Vec<usize> = (0..inputs.len())
+            .filter(|&i| !inputs[i].trim().is_empty())
+            .collect();
+        let FstProcess { stdin, stdout, timeout, .. } = self;
+        let timeout = *timeout;
+        let timed_out = |input: &str| {
+            anyhow!(t_args!("pool-timeout",
+                "seconds" => timeout.as_secs_f64(),
+                "input" => input
+            ))
+        };
 
-                    // Skip comments and warnings (only if the line doesn't contain a tab, meaning it's not FST output)
-                    if (trimmed.starts_with('!') || trimmed.starts_with('#')) && !trimmed.contains('\t') {
-                        continue;
+        // Write while reading, so that neither pipe can fill up and block. A
+        // write only waits while the tool is busy with earlier inputs, so it
+        // gets the same time as a lookup.
+        let write = async {
+            for &i in &sent {
+                let input = inputs[i].trim();
+                let line = format!("{input}\n");
+                tokio::time::timeout(timeout, stdin.write_all(line.as_bytes()))
+                    .await
+                    .map_err(|_| timed_out(input))??;
+            }
+            let last = sent.last().map_or("", |&i| inputs[i].trim());
+            tokio::time::timeout(timeout, stdin.flush()).await.map_err(|_| timed_out(last))??;
+            Ok::<_, anyhow::Error>(())
+        };
+        let read = async {
+            let mut all_results: Vec<Vec<LookupOutput>> = vec![Vec::new(); inputs.len()];
+            let mut line = String::new();
+            for &i in &sent {
+                let input = inputs[i].trim();
+                let deadline = tokio::time::Instant::now() + timeout;
+                let mut record = Record::default();
+                loop {
+                    line.clear();
+                    let read = tokio::time::timeout_at(deadline, stdout.read_line(&mut line))
+                        .await
+                        .map_err(|_| timed_out(input))?
+                        .map_err(|e| anyhow!(t_args!("pool-io-error", "error" => &e)))?;
+                    if read == 0 {
+                        return Err(anyhow!(t_args!("pool-process-ended", "input" => input)));
                     }
-
-                    let cols: Vec<&str> = trimmed.split('\t').collect();
-                    if cols.len() >= 2 {
-                        let input = cols[0].trim().to_string();
-                        let output = cols[1].trim().to_string();
-
-                        // Handle +inf (no result) marker
-                        if output == "+inf" {
-                            // Input with no results - already initialized as empty Set
-                            continue;
-                        }
-
-                        // Handle cases where FST couldn't process: input==output with +? in third column
-                        if cols.len() >= 3 && input == output && cols[2].trim().contains("+?") {
-                            // FST failed to generate/analyze - treat as no result
-                            continue;
-                        }
-
-                        if !output.is_empty() && output != "@" {
-                            if let Some(results) = results_map.get_mut(&input) {
-                                results.insert(output);
-                            }
-                        }
+                    if record.push_line(&line) {
+                        break;
                     }
                 }
-                Ok(Err(e)) => return Err(anyhow!(t_args!("pool-io-error", "error" => &e))),
-                Err(_) => {
-                    // Timeout is expected when no more output is available
-                    break;
-                }
+                all_results[i] = record.into_outputs();
             }
-        }
-
-        // Build ordered results matching input order
-        let mut all_results = Vec::new();
-        for input in inputs {
-            let input_key = input.trim().to_string();
-            let results_set = results_map.shift_remove(&input_key).unwrap_or_default();
-            let results: Vec<String> = results_set.into_iter().collect();
-            all_results.push(results);
-        }
+            Ok(all_results)
+        };
+        let ((), all_results) = tokio::try_join!(write, read)?;
 
         debug!(
             "{}",
@@ -120,8 +95,10 @@ impl FstProcess {
 /// Manager for creating and recycling FST processes
 pub struct FstProcessManager {
     pub lookup_cmd: String,
+    pub args: Vec<String>,
     pub fst_path: String,
     pub quiet: bool,
+    pub timeout: Duration,
 }
 
 impl Manager for FstProcessManager {
@@ -130,13 +107,15 @@ impl Manager for FstProcessManager {
 
     async fn create(&self) -> Result<FstProcess> {
         let mut cmd = Command::new(&self.lookup_cmd);
-        cmd.arg(&self.fst_path)
+        cmd.args(&self.args)
+            .arg(&self.fst_path)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
+            // Not piped, as nothing would read it and a chatty tool could block
             .stderr(if self.quiet {
                 Stdio::null()
             } else {
-                Stdio::piped()
+                Stdio::inherit()
             });
 
         let mut child = cmd
@@ -156,6 +135,7 @@ impl Manager for FstProcessManager {
             child,
             stdin: BufWriter::new(stdin),
             stdout: BufReader::new(stdout),
+            timeout: self.timeout,
         })
     }
 
@@ -190,17 +170,21 @@ pub struct PooledBackend {
 impl PooledBackend {
     pub async fn new(
         lookup_cmd: String,
+        args: LookupArgs,
         analyzer_fst: Option<String>,
         generator_fst: Option<String>,
         quiet: bool,
+        timeout: Duration,
     ) -> Result<Self> {
         let pool_size = num_cpus::get().max(1);
 
         let analyze_pool = if let Some(fst_path) = analyzer_fst {
             let manager = FstProcessManager {
                 lookup_cmd: lookup_cmd.clone(),
+                args: args.analyze,
                 fst_path,
                 quiet,
+                timeout,
             };
             Some(
                 Pool::builder(manager)
@@ -215,8 +199,10 @@ impl PooledBackend {
         let generate_pool = if let Some(fst_path) = generator_fst {
             let manager = FstProcessManager {
                 lookup_cmd,
+                args: args.generate,
                 fst_path,
                 quiet,
+                timeout,
             };
             Some(
                 Pool::builder(manager)
@@ -234,12 +220,11 @@ impl PooledBackend {
         })
     }
 
-    pub async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
-        let pool = self
-            .analyze_pool
-            .as_ref()
-            .ok_or_else(|| anyhow!(t!("backend-analyzer-not-set")))?;
-
+    async fn lookup_batch(
+        pool: &Pool<FstProcessManager>,
+        inputs: &[String],
+        get_failed_key: &'static str,
+    ) -> Result<Vec<LookupOutcome>> {
         if inputs.is_empty() {
             return Ok(vec![]);
         }
@@ -250,57 +235,96 @@ impl PooledBackend {
         let chunks: Vec<_> = inputs.chunks(chunk_size).collect();
 
         // Process chunks in parallel
-        let futures = chunks.iter().map(|chunk| {
-            let pool = pool.clone();
-            async move {
-                let mut process = pool
-                    .get()
-                    .await
-                    .map_err(|e| anyhow!(t_args!("pool-get-analyze-failed", "error" => &e)))?;
-                process.process_batch(chunk).await
+        let futures = chunks.iter().map(|chunk| async move {
+            match Self::try_chunk(pool, chunk, get_failed_key).await? {
+                Ok(results) => Ok(results.into_iter().map(Ok).collect()),
+                Err(e) => {
+                    // The process died or hung: try once more with a fresh one
+                    // before looking for the input responsible
+                    debug!("{}", t_args!("debug-pool-retry", "count" => chunk.len(), "error" => &e));
+                    match Self::try_chunk(pool, chunk, get_failed_key).await? {
+                        Ok(results) => Ok(results.into_iter().map(Ok).collect()),
+                        Err(e) => Self::bisect(pool, chunk, get_failed_key, e).await,
+                    }
+                }
             }
         });
 
-        let chunk_results = try_join_all(futures).await?;
+        let chunk_results: Vec<Vec<LookupOutcome>> = try_join_all(futures).await?;
 
         // Flatten results maintaining order
         Ok(chunk_results.into_iter().flatten().collect())
     }
 
-    pub async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
-        let pool = self
-            .generate_pool
-            .as_ref()
-            .ok_or_else(|| anyhow!(t!("backend-generator-not-set")))?;
-
-        if inputs.is_empty() {
-            return Ok(vec![]);
+    /// Run a chunk on one process. The outer error means no process could be had,
+    /// the inner one that the process failed, in which case it is not reused.
+    async fn try_chunk(
+        pool: &Pool<FstProcessManager>,
+        inputs: &[String],
+        get_failed_key: &'static str,
+    ) -> Result<Result<Vec<Vec<LookupOutput>>>> {
+        let mut process = pool
+            .get()
+            .await
+            .map_err(|e| anyhow!(t_args!(get_failed_key, "error" => &e)))?;
+        let result = process.process_batch(inputs).await;
+        if result.is_err() {
+            let _ = Object::take(process);
         }
+        Ok(result)
+    }
 
-        // Split into chunks based on pool size
-        let pool_status = pool.status();
-        let chunk_size = (inputs.len() / pool_status.max_size).max(1);
-        let chunks: Vec<_> = inputs.chunks(chunk_size).collect();
-
-        // Process chunks in parallel
-        let futures = chunks.iter().map(|chunk| {
-            let pool = pool.clone();
-            async move {
-                let mut process = pool
-                    .get()
-                    .await
-                    .map_err(|e| anyhow!(t_args!("pool-get-generate-failed", "error" => &e)))?;
-                process.process_batch(chunk).await
+    /// Narrow a failing chunk down to the inputs that crash or hang the process
+    fn bisect<'a>(
+        pool: &'a Pool<FstProcessManager>,
+        inputs: &'a [String],
+        get_failed_key: &'static str,
+        error: anyhow::Error,
+    ) -> BoxFuture<'a, Result<Vec<LookupOutcome>>> {
+        Box::pin(async move {
+            if inputs.len() == 1 {
+                return Ok(vec![Err(error.to_string())]);
             }
-        });
+            debug!("{}", t_args!("debug-pool-bisect", "count" => inputs.len()));
+            let (left, right) = inputs.split_at(inputs.len() / 2);
+            let mut outcomes = Vec::with_capacity(inputs.len());
+            for half in [left, right] {
+                match Self::try_chunk(pool, half, get_failed_key).await? {
+                    Ok(results) => outcomes.extend(results.into_iter().map(Ok)),
+                    Err(e) => outcomes.extend(Self::bisect(pool, half, get_failed_key, e).await?),
+                }
+            }
+            Ok(outcomes)
+        })
+    }
+}
 
-        let chunk_results = try_join_all(futures).await?;
+#[async_trait]
+impl Backend for PooledBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let pool = self
+            .analyze_pool
+            .as_ref()
+            .ok_or_else(|| anyhow!(t!("backend-analyzer-not-set")))?;
+        Self::lookup_batch(pool, inputs, "pool-get-analyze-failed").await
+    }
 
-        // Flatten results maintaining order
-        Ok(chunk_results.into_iter().flatten().collect())
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        let pool = self
+            .generate_pool
+            .as_ref()
+            .ok_or_else(|| anyhow!(t!("backend-generator-not-set")))?;
+        Self::lookup_batch(pool, inputs, "pool-get-generate-failed").await
     }
 
-    pub async fn validate(&self) -> Result<()> {
+    async fn validate(&self) -> Result<()> {
+        // Check the transducers first, a process reading the wrong one may
+        // start fine and only fail on the first lookup
+        for pool in [&self.analyze_pool, &self.generate_pool].into_iter().flatten() {
+            let manager = pool.manager();
+            check_fsts(&manager.lookup_cmd, [manager.fst_path.as_str()])?;
+        }
+
         // Test that we can spawn and use a process from each pool
         if let Some(pool) = &self.analyze_pool {
             let _process = pool
@@ -0,0 +1,97 @@
+use crate::spec::{RawConfig, resolve_path_relative_to_yaml};
+use crate::t_args;
+use crate::vars::{KEEP_FSTTYPE, expand_config};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Names of the project config file, looked for in a test file's directory
+/// and each directory above it. They are never read as test files.
+pub const PROJECT_FILES: [&str; 2] = ["morph-test.yaml", ".morph-test.yaml"];
+
+/// Defaults shared by all test files below the project config: a `Config`
+/// that each file's own `Config` overrides, and default command line options
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub config: Option<RawConfig>,
+    #[serde(default)]
+    pub options: ProjectOptions,
+}
+
+/// Command line options given in the project config, used unless given on
+/// the command line
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct ProjectOptions {
+    pub backend: Option<String>,
+    #[serde(default)]
+    pub ignore_extra_analyses: bool,
+    pub output: Option<String>,
+    #[serde(default)]
+    pub serial: bool,
+    #[serde(default)]
+    pub native: bool,
+    #[serde(default)]
+    pub cache: bool,
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// The nearest project config at or above `start`, a test file or directory
+pub fn find_project_config(start: &Path) -> Option<PathBuf> {
+    let start = std::fs::canonicalize(start).ok()?;
+    let dir = if start.is_dir() { start.as_path() } else { start.parent()? };
+    dir.ancestors()
+        .flat_map(|dir| PROJECT_FILES.iter().map(move |name| dir.join(name)))
+        .find(|path| path.is_file())
+}
+
+impl ProjectConfig {
+    /// Read a project config. Its variables are expanded, and its transducers
+    /// and cache directory are relative to the file itself, so they are made
+    /// so here. `${fsttype}` is left for each test file.
+    pub fn load(path: &Path) -> Result<Self> {
+        debug!("{}", t_args!("debug-project-config", "file" => path.display()));
+        let content = std::fs::read_to_string(path)
+            .with_context(|| t_args!("project-failed-to-read", "file" => path.display()))?;
+        let mut project: ProjectConfig = serde_yaml::from_str(&content)
+            .with_context(|| t_args!("project-yaml-error", "file" => path.display()))?;
+        if let Some(cfg) = &mut project.config {
+            expand_config(cfg, path, Some(KEEP_FSTTYPE))
+                .with_context(|| t_args!("spec-incomplete-config", "file" => path.display()))?;
+        }
+        let resolve = |fst: &mut Option<String>| {
+            if let Some(fst) = fst {
+                *fst = resolve_path_relative_to_yaml(fst.trim(), path);
+            }
+        };
+        if let Some(cfg) = &mut project.config {
+            if let Some(h) = &mut cfg.hfst {
+                resolve(&mut h.r#gen);
+                resolve(&mut h.morph);
+            }
+            if let Some(x) = &mut cfg.foma {
+                resolve(&mut x.r#gen);
+                resolve(&mut x.morph);
+            }
+            if let Some(a) = &mut cfg.apertium {
+                resolve(&mut a.r#gen);
+                resolve(&mut a.morph);
+            }
+            if let Some(c) = &mut cfg.command {
+                resolve(&mut c.r#gen);
+                resolve(&mut c.morph);
+            }
+            for fst in cfg.fsts.iter_mut().flat_map(|fsts| fsts.values_mut()) {
+                resolve(&mut fst.r#gen);
+                resolve(&mut fst.morph);
+            }
+        }
+        if let Some(dir) = &project.options.cache_dir {
+            let dir = resolve_path_relative_to_yaml(&dir.to_string_lossy(), path);
+            project.options.cache_dir = Some(PathBuf::from(dir));
+        }
+        Ok(project)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::{CaseResult, Direction, Summary};
+use crate::types::{CaseResult, Direction, Summary, WeightCheck};
 use crate::{t, t_args};
 use colored::Colorize;
 use indexmap::IndexMap;
@@ -31,6 +31,15 @@ fn dash_line(width: usize) -> String {
 }
 
 fn print_failure_detailed(case: &CaseResult, i: usize, n_cases: usize, expected_item: &str) {
+    let actual_str = if case.actual.is_empty() {
+        "<none>".dimmed().to_string()
+    } else {
+        case.actual.join(", ")
+    };
+    print_failure(case, i, n_cases, expected_item, &actual_str);
+}
+
+fn print_failure(case: &CaseResult, i: usize, n_cases: usize, expected_item: &str, actual_str: &str) {
     let width = n_cases.to_string().len();
     println!(
         "[{:>width$}/{:>width$}][{}] {} => {}",
@@ -45,18 +54,91 @@ fn print_failure_detailed(case: &CaseResult, i: usize, n_cases: usize, expected_
     if let Some(error) = &case.error {
         println!("         {}: {}", t!("report-error"), error.red());
     } else {
-        let actual_str = if case.actual.is_empty() {
-            "<none>".dimmed().to_string()
-        } else {
-            case.actual.join(", ")
-        };
         println!(
             "         {}: {}",
             t!("report-expected"),
             expected_item.green()
         );
         println!("         {}: {}", t!("report-got"), actual_str.yellow());
+        if let Some(raw) = raw_outputs(case) {
+            println!("         {}: {}", t!("report-raw"), raw.dimmed());
+        }
+    }
+}
+
+/// The outputs as the lookup gave them, when normalizing changed any
+fn raw_outputs(case: &CaseResult) -> Option<String> {
+    if case.outputs.iter().all(|o| o.raw.is_none()) {
+        return None;
+    }
+    let raw: Vec<&str> = case.outputs.iter().map(|o| o.raw.as_deref().unwrap_or(&o.output)).collect();
+    Some(raw.join(", "))
+}
+
+fn weight_check_label(check: &WeightCheck) -> String {
+    match check {
+        WeightCheck::Best(output) => t_args!("report-weight-best", "output" => output),
+        WeightCheck::Below(output, limit) => t_args!("report-weight-below",
+            "output" => output,
+            "limit" => limit
+        ),
+    }
+}
+
+fn weight_check_passed(case: &CaseResult, check: &WeightCheck) -> bool {
+    case.error.is_none() && check.holds(&case.outputs)
+}
+
+/// The outputs in the backend's order, each followed by its weight
+fn weighted_outputs(case: &CaseResult) -> String {
+    if case.outputs.is_empty() {
+        return "<none>".dimmed().to_string();
     }
+    case.outputs
+        .iter()
+        .map(|o| match o.weight {
+            Some(weight) => format!("{} ({weight})", o.output),
+            None => o.output.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// One line per weight check, returning the number of passes and fails
+fn print_weight_checks(
+    case: &CaseResult,
+    i: usize,
+    n_cases: usize,
+    hide_fails: bool,
+    hide_passes: bool,
+) -> (usize, usize) {
+    let mut passes = 0;
+    let mut fails = 0;
+    for check in &case.weight_checks {
+        let ok = weight_check_passed(case, check);
+        let label = weight_check_label(check);
+        if ok {
+            passes += 1;
+            if !hide_passes {
+                let width = n_cases.to_string().len();
+                println!(
+                    "[{:>width$}/{:>width$}][{}] {} => {}",
+                    i,
+                    n_cases,
+                    "PASS".green().bold(),
+                    case.input,
+                    label,
+                    width = width
+                );
+            }
+        } else {
+            fails += 1;
+            if !hide_fails {
+                print_failure(case, i, n_cases, &label, &weighted_outputs(case));
+            }
+        }
+    }
+    (passes, fails)
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -130,7 +212,11 @@ fn print_human_normal(
             let act_set: BTreeSet<&str> = case.actual.iter().map(|s| s.as_str()).collect();
             // When expected is empty, create a placeholder line
             if case.expected.is_empty() {
+                // Outputs that must not come out are written as in YAML
                 let placeholder = match case.direction {
+                    _ if !case.unwanted.is_empty() => {
+                        case.unwanted.iter().map(|u| format!("~{u}")).collect::<Vec<_>>().join(", ")
+                    }
                     Direction::Generate => t!("report-no-lexical"),
                     Direction::Analyze => t!("report-no-surface"),
                 };
@@ -182,8 +268,8 @@ fn print_human_normal(
                         }
                     }
                 }
-                // Extra analyses as FAIL when -i is NOT active
-                if !ignore_extra_analyses && matches!(case.direction, Direction::Analyze) {
+                // Extra analyses as FAIL when -i is NOT active, unless only unwanted ones are checked
+                if !ignore_extra_analyses && case.unwanted.is_empty() && matches!(case.direction, Direction::Analyze) {
                     let extras: Vec<&str> = act_set.difference(&exp_set).cloned().collect();
                     if !extras.is_empty() && !hide_fails {
                         let extras_str = extras.join(", ");
@@ -197,6 +283,11 @@ fn print_human_normal(
                         fails += 1;
                     }
                 }
+                let (weight_passes, weight_fails) =
+                    print_weight_checks(case, i, n_cases, hide_fails, hide_passes);
+                passes += weight_passes;
+                fails += weight_fails;
+                total_checks += weight_passes + weight_fails;
                 continue;
             }
             // One line per expected value (PASS/FAIL)
@@ -259,6 +350,11 @@ fn print_human_normal(
                     fails += 1;
                 }
             }
+            let (weight_passes, weight_fails) =
+                print_weight_checks(case, i, n_cases, hide_fails, hide_passes);
+            passes += weight_passes;
+            fails += weight_fails;
+            total_checks += weight_passes + weight_fails;
         }
         println!();
         println!(
@@ -365,7 +461,6 @@ fn print_human_terse(summary: &Summary, ignore_extra_analyses: bool) {
                 if !is_pass {
                     any_fail = true;
                 }
-                continue;
             }
             for exp in &case.expected {
                 let ok = act_set.contains(exp.as_str());
@@ -374,6 +469,13 @@ fn print_human_terse(summary: &Summary, ignore_extra_analyses: bool) {
                     any_fail = true;
                 }
             }
+            for check in &case.weight_checks {
+                let ok = weight_check_passed(case, check);
+                line.push(if ok { '.' } else { '!' });
+                if !ok {
+                    any_fail = true;
+                }
+            }
         }
         println!("{line}");
     }
@@ -404,6 +506,9 @@ fn print_human_final(summary: &Summary, ignore_extra_analyses: bool) {
 }
 
 fn is_pass_empty_expected(case: &CaseResult, ignore_extra_analyses: bool) -> bool {
+    if !case.unwanted.is_empty() {
+        return !case.actual.iter().any(|output| case.unwanted.contains(output));
+    }
     match case.direction {
         Direction::Analyze if ignore_extra_analyses => true,
         _ => case.actual.is_empty(),
@@ -434,8 +539,9 @@ pub fn calculate_counts(
                 total_fails += 1;
             }
 
-            // For analyze direction when not ignoring extras, count extras as additional fails
-            if !ignore_extra_analyses && matches!(case.direction, Direction::Analyze) {
+            // For analyze direction when not ignoring extras, count extras as additional fails,
+            // unless only unwanted ones are checked
+            if !ignore_extra_analyses && case.unwanted.is_empty() && matches!(case.direction, Direction::Analyze) {
                 let extras: Vec<&str> = act_set.difference(&exp_set).cloned().collect();
                 if !extras.is_empty() {
                     total_checks += 1;
@@ -463,6 +569,15 @@ pub fn calculate_counts(
                 }
             }
         }
+
+        for check in &case.weight_checks {
+            total_checks += 1;
+            if weight_check_passed(case, check) {
+                total_passes += 1;
+            } else {
+                total_fails += 1;
+            }
+        }
     }
 
     (total_passes, total_fails, total_checks)
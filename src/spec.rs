@@ -1,11 +1,21 @@
-use crate::types::{Direction, TestCase, TestSuite};
+use crate::apertium::default_proc_tool;
+use crate::backend::LookupArgs;
+use crate::command::OutputParser;
+use crate::format::{FstFormat, detect_format, is_att_transducer, named_like_hfst, read_format};
+use crate::normalize::Normalizer;
+use crate::project::{PROJECT_FILES, ProjectConfig, find_project_config};
+use crate::table::{read_table, table_delimiter};
+use crate::types::{Direction, Relation, TestCase, TestSuite, WeightCheck};
+use crate::vars::{expand_config, fsttype_of};
 use crate::{t, t_args};
 use anyhow::{Context, Result, anyhow};
 use indexmap::IndexMap;
 use serde::Deserialize;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::warn;
 use walkdir::WalkDir;
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -15,6 +25,23 @@ pub enum BackendChoice {
     Auto,
     Hfst,
     Foma,
+    Apertium,
+    Http,
+    Command,
+}
+
+impl BackendChoice {
+    /// The name of the backend, as given to `--backend`
+    pub fn name(&self) -> &'static str {
+        match self {
+            BackendChoice::Auto => "auto",
+            BackendChoice::Hfst => "hfst",
+            BackendChoice::Foma => "foma",
+            BackendChoice::Apertium => "apertium",
+            BackendChoice::Http => "http",
+            BackendChoice::Command => "command",
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -22,6 +49,8 @@ pub enum BackendChoice {
 pub struct HfstCfg {
     pub r#gen: Option<String>,
     pub morph: Option<String>,
+    pub app: Option<String>, // default: chosen by the transducer's extension
+    pub args: Option<RawArgs>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -29,7 +58,137 @@ pub struct HfstCfg {
 pub struct FomaCfg {
     pub r#gen: Option<String>,
     pub morph: Option<String>,
-    pub app: Option<String>, // default: flookup
+    pub app: Option<String>, // default: built-in reader
+    pub args: Option<RawArgs>,
+}
+
+/// Transducers for `lt-proc` or `hfst-proc`, looked up in the Apertium stream format
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ApertiumCfg {
+    pub r#gen: Option<String>,
+    pub morph: Option<String>,
+    pub app: Option<String>, // default: hfst-proc for HFST transducers, else lt-proc
+    pub args: Option<RawArgs>,
+}
+
+/// A morphology service that answers lookups as JSON over HTTP
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct HttpCfg {
+    pub url: Option<String>,
+}
+
+/// Any command, run from a template with `{fst}` and `{direction}`
+/// placeholders, whose output is read with the parser named in `Output`
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct CommandCfg {
+    pub r#gen: Option<String>,
+    pub morph: Option<String>,
+    pub run: Option<OneOrMany>,
+    pub output: Option<String>, // default: hfst
+    pub pattern: Option<String>, // for the regex parser
+}
+
+/// How lookup outputs are normalized before they are compared
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct NormalizeCfg {
+    /// Strip flag diacritics such as `@P.x.y@`; off unless set to true
+    pub flags: Option<bool>,
+    /// Strings rewritten in the outputs, before anything else
+    #[serde(default)]
+    pub replace: IndexMap<String, String>,
+    /// Symbols removed from the outputs, such as boundaries
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+impl NormalizeCfg {
+    pub fn normalizer(&self) -> Normalizer {
+        Normalizer {
+            strip_flags: self.flags.unwrap_or(false),
+            replace: self.replace.iter()
+                .filter(|(from, _)| !from.is_empty())
+                .map(|(from, to)| (from.clone(), to.clone()))
+                .collect(),
+            remove: self.remove.iter().filter(|symbol| !symbol.is_empty()).cloned().collect(),
+        }
+    }
+}
+
+/// How the rows of a TSV or CSV test file are laid out
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TableLayout {
+    /// One row per form, with the lemma, tags and surface form in columns
+    Long,
+    /// One row per lemma, with a column of forms for each tag string
+    Wide,
+}
+
+/// The columns of a TSV or CSV test file, by their names in its header.
+/// Left out, they are `lemma`, `tags`, `surface`, `negative` and `group`.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct TableCfg {
+    /// By default long when the header has a surface column, else wide
+    pub layout: Option<TableLayout>,
+    pub lemma: Option<String>,
+    pub tags: Option<String>,
+    pub surface: Option<String>,
+    /// A column that marks forms the lexical key must not give
+    pub negative: Option<String>,
+    /// A column naming the group of each row, instead of the file name
+    pub group: Option<String>,
+}
+
+/// One of the transducers named in `Fsts`
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct FstCfg {
+    pub r#gen: Option<String>,
+    pub morph: Option<String>,
+}
+
+/// Extra arguments for the lookup tool: the same for both directions, or
+/// given separately for the generator and the analyser
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum RawArgs {
+    Both(OneOrMany),
+    PerDirection(DirectionArgs),
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct DirectionArgs {
+    pub r#gen: Option<OneOrMany>,
+    pub morph: Option<OneOrMany>,
+}
+
+/// A single string is split on whitespace, a list is taken one argument per item
+fn split_args(args: &OneOrMany) -> Vec<String> {
+    match args {
+        OneOrMany::One(s) => s.split_whitespace().map(str::to_string).collect(),
+        OneOrMany::Many(v) => v.iter().map(|s| trim_owned(s)).collect(),
+    }
+}
+
+impl RawArgs {
+    pub fn resolve(&self) -> LookupArgs {
+        match self {
+            RawArgs::Both(args) => LookupArgs {
+                analyze: split_args(args),
+                generate: split_args(args),
+            },
+            RawArgs::PerDirection(d) => LookupArgs {
+                analyze: d.morph.as_ref().map(split_args).unwrap_or_default(),
+                generate: d.r#gen.as_ref().map(split_args).unwrap_or_default(),
+            },
+        }
+    }
 }
 
 // Accept alias for backward compatibility
@@ -39,6 +198,77 @@ pub struct RawConfig {
     pub hfst: Option<HfstCfg>,
     #[serde(alias = "Foma", alias = "xerox", alias = "Xerox")]
     pub foma: Option<FomaCfg>,
+    #[serde(alias = "Apertium")]
+    pub apertium: Option<ApertiumCfg>,
+    #[serde(alias = "Http", alias = "HTTP")]
+    pub http: Option<HttpCfg>,
+    #[serde(alias = "Command")]
+    pub command: Option<CommandCfg>,
+    /// Named transducers, such as `norm` and `desc`, each run as a suite of
+    /// its own with the chosen section's lookup tool
+    #[serde(alias = "Fsts", alias = "FSTs")]
+    pub fsts: Option<IndexMap<String, FstCfg>>,
+    /// Relations between the analyses of the named transducers, e.g. `desc ⊇ norm`
+    #[serde(alias = "Relations")]
+    pub relations: Option<Vec<String>>,
+    #[serde(alias = "Normalize")]
+    pub normalize: Option<NormalizeCfg>,
+    /// The columns of TSV and CSV test files
+    #[serde(alias = "Table")]
+    pub table: Option<TableCfg>,
+    /// Seconds to wait for the lookup of a single input
+    #[serde(alias = "Timeout")]
+    pub timeout: Option<f64>,
+}
+
+/// Settings of `own`, or of `defaults` where `own` has none
+fn merge<T>(own: Option<T>, defaults: Option<T>, fields: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (own, defaults) {
+        (Some(own), Some(defaults)) => Some(fields(own, defaults)),
+        (own, defaults) => own.or(defaults),
+    }
+}
+
+impl RawConfig {
+    /// This config, with what it leaves out taken from `defaults`, setting
+    /// by setting within each section. `Fsts` and `Relations` are taken whole.
+    pub fn or(self, defaults: RawConfig) -> RawConfig {
+        RawConfig {
+            hfst: merge(self.hfst, defaults.hfst, |own, d| HfstCfg {
+                r#gen: own.r#gen.or(d.r#gen),
+                morph: own.morph.or(d.morph),
+                app: own.app.or(d.app),
+                args: own.args.or(d.args),
+            }),
+            foma: merge(self.foma, defaults.foma, |own, d| FomaCfg {
+                r#gen: own.r#gen.or(d.r#gen),
+                morph: own.morph.or(d.morph),
+                app: own.app.or(d.app),
+                args: own.args.or(d.args),
+            }),
+            apertium: merge(self.apertium, defaults.apertium, |own, d| ApertiumCfg {
+                r#gen: own.r#gen.or(d.r#gen),
+                morph: own.morph.or(d.morph),
+                app: own.app.or(d.app),
+                args: own.args.or(d.args),
+            }),
+            http: merge(self.http, defaults.http, |own, d| HttpCfg {
+                url: own.url.or(d.url),
+            }),
+            command: merge(self.command, defaults.command, |own, d| CommandCfg {
+                r#gen: own.r#gen.or(d.r#gen),
+                morph: own.morph.or(d.morph),
+                run: own.run.or(d.run),
+                output: own.output.or(d.output),
+                pattern: own.pattern.or(d.pattern),
+            }),
+            fsts: self.fsts.or(defaults.fsts),
+            relations: self.relations.or(defaults.relations),
+            normalize: self.normalize.or(defaults.normalize),
+            table: self.table.or(defaults.table),
+            timeout: self.timeout.or(defaults.timeout),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -48,11 +278,202 @@ pub enum OneOrMany {
     Many(Vec<String>),
 }
 
+impl OneOrMany {
+    pub fn to_vec(&self) -> Vec<String> {
+        match self {
+            OneOrMany::One(s) => vec![trim_owned(s)],
+            OneOrMany::Many(v) => v.iter().map(|s| trim_owned(s)).collect(),
+        }
+    }
+}
+
+/// The expected forms of a lexical key, either plain or with weight assertions
 #[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Expected {
+    Forms(OneOrMany),
+    Weighted(WeightedForms),
+    /// Different expectations for each of the transducers named in `Fsts`
+    PerFst(IndexMap<String, Expected>),
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct WeightedForms {
+    pub forms: OneOrMany,
+    /// Form that must come out of generation with the lowest weight
+    pub best: Option<String>,
+    /// Forms whose generation weight must stay below the given limit
+    #[serde(default)]
+    pub weight_below: IndexMap<String, f64>,
+    /// The lexical key must be the best weighted analysis of each form
+    #[serde(default)]
+    pub best_analysis: bool,
+    /// Weight the lexical key's analysis of each form must stay below
+    pub analysis_weight_below: Option<f64>,
+}
+
+impl Expected {
+    fn forms(&self) -> Vec<String> {
+        match self {
+            Expected::Forms(forms) => forms.to_vec(),
+            Expected::Weighted(weighted) => weighted.forms.to_vec(),
+            Expected::PerFst(_) => vec![],
+        }
+    }
+
+    /// The expectations for one of the named transducers, if the lexical key
+    /// is tested against it at all
+    fn for_fst(&self, fst: Option<&str>) -> Option<&Expected> {
+        match self {
+            Expected::PerFst(per_fst) => per_fst
+                .iter()
+                .find(|(name, _)| Some(name.trim()) == fst)
+                .and_then(|(_, expected)| expected.for_fst(fst)),
+            _ => Some(self),
+        }
+    }
+
+    /// Weight checks for generating from the lexical key
+    fn generation_checks(&self) -> Vec<WeightCheck> {
+        let Expected::Weighted(weighted) = self else {
+            return vec![];
+        };
+        weighted
+            .best
+            .iter()
+            .map(|form| WeightCheck::Best(trim_owned(form)))
+            .chain(
+                weighted
+                    .weight_below
+                    .iter()
+                    .map(|(form, limit)| WeightCheck::Below(trim_owned(form), *limit)),
+            )
+            .collect()
+    }
+
+    /// Weight checks for analysing the forms back to `lexical`
+    fn analysis_checks(&self, lexical: &str) -> Vec<WeightCheck> {
+        let Expected::Weighted(weighted) = self else {
+            return vec![];
+        };
+        let mut checks = Vec::new();
+        if weighted.best_analysis {
+            checks.push(WeightCheck::Best(lexical.to_string()));
+        }
+        if let Some(limit) = weighted.analysis_weight_below {
+            checks.push(WeightCheck::Below(lexical.to_string(), limit));
+        }
+        checks
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
 #[serde(rename_all = "PascalCase")]
 pub struct RawSpec {
     pub config: Option<RawConfig>,
-    pub tests: IndexMap<String, IndexMap<String, OneOrMany>>,
+    #[serde(default)]
+    pub tests: IndexMap<String, IndexMap<String, Expected>>,
+    /// Groups written as lemmas inflected with tag strings, added to the
+    /// groups of the same name in `tests`
+    #[serde(default)]
+    pub paradigms: IndexMap<String, RawParadigm>,
+}
+
+/// A lemma, or several, with the same tag strings, such as `+N+Sg+Nom`
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct RawParadigm {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// A single lemma, with its `Forms`
+    pub lemma: Option<String>,
+    pub forms: Option<ParadigmForms>,
+    /// Several lemmas, each with its forms
+    #[serde(default)]
+    pub lemmas: IndexMap<String, ParadigmForms>,
+}
+
+/// The forms of a lemma in a paradigm
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum ParadigmForms {
+    /// One entry for each tag string, in order; null leaves a cell untested
+    Positional(Vec<Option<Expected>>),
+    /// Entries for some of the tag strings
+    PerTag(IndexMap<String, Expected>),
+}
+
+/// The lexical key of a lemma and a tag string: `+N+Sg` and `<n><sg>` are
+/// added to the lemma as they are, other tags after a `+`
+pub(crate) fn lexical_key(lemma: &str, tags: &str) -> String {
+    if lemma.is_empty() || tags.is_empty() || tags.starts_with(['+', '<']) {
+        format!("{lemma}{tags}")
+    } else {
+        format!("{lemma}+{tags}")
+    }
+}
+
+impl RawParadigm {
+    /// The lexical keys of the paradigm, with their expected forms
+    fn expand(&self, name: &str) -> Result<Vec<(String, Expected)>> {
+        let single = match (&self.lemma, &self.forms) {
+            (Some(lemma), Some(forms)) => Some((lemma, forms)),
+            (None, None) => None,
+            _ => return Err(anyhow!(t_args!("spec-paradigm-lemma-forms", "name" => name))),
+        };
+        let mut keys = Vec::new();
+        for (lemma, forms) in single.into_iter().chain(&self.lemmas) {
+            let lemma = lemma.trim();
+            match forms {
+                ParadigmForms::Positional(cells) => {
+                    if cells.len() != self.tags.len() {
+                        return Err(anyhow!(t_args!("spec-paradigm-length",
+                            "name" => name,
+                            "lemma" => lemma,
+                            "forms" => cells.len(),
+                            "tags" => self.tags.len()
+                        )));
+                    }
+                    for (tags, cell) in self.tags.iter().zip(cells) {
+                        if let Some(expected) = cell {
+                            keys.push((lexical_key(lemma, tags.trim()), expected.clone()));
+                        }
+                    }
+                }
+                ParadigmForms::PerTag(cells) => {
+                    for (tags, expected) in cells {
+                        let tags = tags.trim();
+                        if !self.tags.is_empty() && !self.tags.iter().any(|t| t.trim() == tags) {
+                            return Err(anyhow!(t_args!("spec-paradigm-unknown-tag",
+                                "name" => name,
+                                "lemma" => lemma,
+                                "tags" => tags
+                            )));
+                        }
+                        keys.push((lexical_key(lemma, tags), expected.clone()));
+                    }
+                }
+            }
+        }
+        if keys.is_empty() {
+            return Err(anyhow!(t_args!("spec-paradigm-lemma-forms", "name" => name)));
+        }
+        Ok(keys)
+    }
+}
+
+/// Add the lexical keys of the paradigms to the groups of the same name.
+/// A key that is also written out in `Tests` keeps the expectations given there.
+pub fn expand_paradigms(raw: &mut RawSpec) -> Result<()> {
+    for (name, paradigm) in std::mem::take(&mut raw.paradigms) {
+        let keys = paradigm.expand(name.trim())?;
+        let group = raw.tests.entry(name).or_default();
+        for (key, expected) in keys {
+            group.entry(key).or_insert(expected);
+        }
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
@@ -62,12 +483,42 @@ pub struct SuiteWithConfig {
     pub lookup_cmd: String,
     pub gen_fst: String,
     pub morph_fst: Option<String>,
+    /// Look up in-process instead of running `lookup_cmd`
+    pub native: bool,
+    /// Per-input lookup timeout from the spec's `Config`
+    pub timeout: Option<Duration>,
+    /// Lookup tool set with `App`, which `lookup_cmd` then holds
+    pub app: Option<String>,
+    /// Extra arguments for `lookup_cmd`, from `Args`
+    pub lookup_args: LookupArgs,
+    /// How the output of a command backend is read
+    pub output_parser: Option<OutputParser>,
+    /// Relations between this suite's analyses and those of the other
+    /// transducers named in its spec
+    pub relations: Vec<Relation>,
+    /// How the outputs are normalized before they are compared
+    pub normalizer: Normalizer,
 }
 
 fn trim_owned(s: &str) -> String {
     s.trim().to_string()
 }
 
+/// Extensions of the test files found in a directory
+const SPEC_EXTENSIONS: [&str; 7] = ["yaml", "yml", "json", "toml", "tsv", "csv", "lexc"];
+
+/// Read a spec as JSON or TOML by the file's extension, or else as YAML.
+/// All three give the same structure, so the rest does not care which it was.
+pub fn parse_spec(content: &str, f: &Path) -> Result<RawSpec> {
+    match f.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => {
+            serde_json::from_str(content).with_context(|| t_args!("spec-json-error", "file" => f.display()))
+        }
+        Some("toml") => toml::from_str(content).with_context(|| t_args!("spec-toml-error", "file" => f.display())),
+        _ => serde_yaml::from_str(content).with_context(|| t_args!("spec-yaml-error", "file" => f.display())),
+    }
+}
+
 pub fn load_specs(paths: &[PathBuf], prefer: BackendChoice) -> Result<Vec<SuiteWithConfig>> {
     let mut files = Vec::new();
     for p in paths {
@@ -76,10 +527,14 @@ pub fn load_specs(paths: &[PathBuf], prefer: BackendChoice) -> Result<Vec<SuiteW
                 let entry = entry?;
                 if entry.file_type().is_file() {
                     let path = entry.path();
-                    if let Some(ext) = path.extension() {
-                        if ext == "yaml" || ext == "yml" || ext == "lexc" {
-                            files.push(path.to_path_buf());
-                        }
+                    let is_project = path
+                        .file_name()
+                        .is_some_and(|name| PROJECT_FILES.iter().any(|p| name == *p));
+                    if let Some(ext) = path.extension()
+                        && SPEC_EXTENSIONS.iter().any(|e| ext == *e)
+                        && !is_project
+                    {
+                        files.push(path.to_path_buf());
                     }
                 }
             }
@@ -88,12 +543,14 @@ pub fn load_specs(paths: &[PathBuf], prefer: BackendChoice) -> Result<Vec<SuiteW
         }
     }
     let mut out = Vec::new();
+    // Project configs by path, as many files share one
+    let mut projects: HashMap<PathBuf, Option<RawConfig>> = HashMap::new();
     for f in files {
         let content = fs::read_to_string(&f)
             .with_context(|| t_args!("spec-failed-to-read", "file" => f.display()))?;
         
         // Check if this is a lexc file
-        if f.extension().map_or(false, |ext| ext == "lexc") {
+        if f.extension().is_some_and(|ext| ext == "lexc") {
             // Parse as lexc test data
             let lexc_test_sets = parse_lexc_test_data(&content)
                 .with_context(|| format!("Failed to parse lexc test data from {}", f.display()))?;
@@ -106,103 +563,292 @@ pub fn load_specs(paths: &[PathBuf], prefer: BackendChoice) -> Result<Vec<SuiteW
             continue;
         }
         
-        // Parse as YAML
-        let raw: RawSpec = serde_yaml::from_str(&content)
-            .with_context(|| t_args!("spec-yaml-error", "file" => f.display()))?;
-        let (backend, lookup_cmd, gen_fst, morph_fst) = resolve_backend(&raw, &prefer, &f)
-            .with_context(|| t_args!("spec-incomplete-config", "file" => f.display()))?;
-        let mut cases: Vec<TestCase> = Vec::new();
-        // Global accumulator for analyze: (surface, group) -> set of analyses (lexical-key)
-        let mut surface_to_analyses: IndexMap<(String, String), BTreeSet<String>> = IndexMap::new();
-        
-        // For each group: build generate-cases and collect surface forms
-        for (group, map) in &raw.tests {
-            let group_name = group.trim();
-            for (lexical, expected) in map {
-                let lexical_trim = lexical.trim().to_string();
-                let expect_vec: Vec<String> = match expected {
-                    OneOrMany::One(s) => vec![trim_owned(s)],
-                    OneOrMany::Many(v) => v.iter().map(|s| s.trim().to_string()).collect(),
-                };
-                // Separate positive and negative expectations
-                let mut positive_forms = Vec::new();
-                let mut negative_forms = Vec::new();
-                
-                for surf in &expect_vec {
-                    if surf.starts_with('~') {
-                        // Negative test: remove ~ prefix for the actual form
-                        let actual_form = surf[1..].to_string();
-                        negative_forms.push(actual_form);
-                    } else {
-                        // Positive test
-                        positive_forms.push(surf.clone());
-                    }
-                }
-                
-                // 1) Generate-case: input=lexical, expect=positive surface forms, expect_not=negative forms
-                let name = format!("{}: {}", group_name, &lexical_trim);
-                cases.push(TestCase {
-                    name,
-                    direction: Direction::Generate,
-                    input: lexical_trim.clone(),
-                    expect: positive_forms.clone(),
-                    expect_not: negative_forms.clone(),
-                });
-                
-                // 2) Invert to analyze: only positive surface forms should analyze to lexical
-                for surf in positive_forms {
-                    let entry = surface_to_analyses.entry((surf, group_name.to_string())).or_default();
-                    entry.insert(lexical_trim.clone());
+        // A table has no Config of its own, so it is read once the project's is known
+        let delimiter = table_delimiter(&f);
+        let mut raw = match delimiter {
+            Some(_) => RawSpec::default(),
+            None => parse_spec(&content, &f)?,
+        };
+        if let Some(path) = find_project_config(&f) {
+            let defaults = match projects.get(&path) {
+                Some(cfg) => cfg.clone(),
+                None => {
+                    let cfg = ProjectConfig::load(&path)?.config;
+                    projects.insert(path, cfg.clone());
+                    cfg
                 }
-                
-                // 2b) Negative analyze-cases: negative forms should not analyze to anything
-                for neg_form in negative_forms {
-                    // Create a separate negative analysis test
-                    let name = format!("Analysis (negative): {}", neg_form);
-                    cases.push(TestCase {
-                        name,
-                        direction: Direction::Analyze,
-                        input: neg_form,
-                        expect: vec![], // Expect no result
-                        expect_not: vec![], // No negative expectations needed for these
-                    });
+            };
+            raw.config = merge(raw.config, defaults, RawConfig::or);
+        }
+        expand_paradigms(&mut raw).with_context(|| t_args!("spec-paradigm-error", "file" => f.display()))?;
+        if let Some(delimiter) = delimiter {
+            let columns = raw.config.as_ref().and_then(|c| c.table.clone()).unwrap_or_default();
+            raw.tests = read_table(&content, delimiter, &columns, &f)
+                .with_context(|| t_args!("spec-table-error", "file" => f.display()))?;
+        }
+        if let Some(cfg) = &mut raw.config {
+            expand_config(cfg, &f, fsttype_of(&f).as_deref())
+                .with_context(|| t_args!("spec-incomplete-config", "file" => f.display()))?;
+        }
+        out.extend(yaml_suites(&raw, &prefer, &f)?);
+    }
+    Ok(out)
+}
+
+/// The suites of a YAML spec: one, or one for each transducer named in `Fsts`
+fn yaml_suites(raw: &RawSpec, prefer: &BackendChoice, f: &Path) -> Result<Vec<SuiteWithConfig>> {
+    let setups = resolve_backend(raw, prefer, f)
+        .with_context(|| t_args!("spec-incomplete-config", "file" => f.display()))?;
+    let file_name = f
+        .file_name()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "suite".to_string());
+    let suite_name = |fst: Option<&str>| match fst {
+        Some(fst) => format!("{file_name} ({fst})"),
+        None => file_name.clone(),
+    };
+    let named: Vec<&str> = setups.iter().filter_map(|(fst, _)| fst.as_deref()).collect();
+    let relations = check_fst_names(raw, &named)
+        .with_context(|| t_args!("spec-incomplete-config", "file" => f.display()))?
+        .into_iter()
+        .map(|(label, superset, subset)| Relation {
+            name: suite_name(Some(&label)),
+            superset: suite_name(Some(&superset)),
+            subset: suite_name(Some(&subset)),
+            label,
+        })
+        .collect::<Vec<_>>();
+    let output_parser = output_parser(raw, &setups[0].1.backend)
+        .with_context(|| t_args!("spec-incomplete-config", "file" => f.display()))?;
+    let timeout = match raw.config.as_ref().and_then(|c| c.timeout) {
+        Some(secs) => Some(
+            Duration::try_from_secs_f64(secs)
+                .ok()
+                .filter(|d| !d.is_zero())
+                .ok_or_else(|| anyhow!(t_args!("spec-invalid-timeout", "value" => secs)))
+                .with_context(|| t_args!("spec-incomplete-config", "file" => f.display()))?,
+        ),
+        None => None,
+    };
+    let normalizer = raw.config.as_ref().and_then(|c| c.normalize.as_ref())
+        .map(NormalizeCfg::normalizer)
+        .unwrap_or_default();
+    Ok(setups
+        .into_iter()
+        .map(|(fst, setup)| {
+            let native = reads_natively(raw, &setup);
+            let (app, lookup_args) = lookup_tool_settings(raw, &setup.backend);
+            SuiteWithConfig {
+                suite: TestSuite {
+                    name: suite_name(fst.as_deref()),
+                    cases: yaml_cases(raw, fst.as_deref()),
+                },
+                backend: setup.backend,
+                lookup_cmd: setup.lookup_cmd,
+                gen_fst: setup.gen_fst,
+                morph_fst: setup.morph_fst,
+                native,
+                timeout,
+                app,
+                lookup_args,
+                output_parser: output_parser.clone(),
+                relations: relations.clone(),
+                normalizer: normalizer.clone(),
+            }
+        })
+        .collect())
+}
+
+/// Foma networks are read natively unless a lookup tool is configured,
+/// and no external tool reads AT&T text files, unless a command says otherwise
+fn reads_natively(raw: &RawSpec, setup: &FstSetup) -> bool {
+    !matches!(setup.backend, BackendChoice::Command)
+        && ((matches!(setup.backend, BackendChoice::Foma)
+            && raw
+                .config
+                .as_ref()
+                .and_then(|c| c.foma.as_ref())
+                .is_none_or(|x| x.app.is_none()))
+            || is_att_transducer(&setup.gen_fst)
+            || setup.morph_fst.as_deref().is_some_and(is_att_transducer))
+}
+
+/// A group's name, and the transducers it runs against when the name ends
+/// in a list of them, as in `Nouns [norm, desc]`
+fn split_group(group: &str) -> (&str, Option<Vec<&str>>) {
+    if let Some(rest) = group.strip_suffix(']')
+        && let Some((name, fsts)) = rest.rsplit_once('[')
+    {
+        let fsts = fsts.split(',').map(str::trim).filter(|fst| !fst.is_empty()).collect();
+        return (name.trim(), Some(fsts));
+    }
+    (group, None)
+}
+
+/// A relation between two named transducers, as its label, superset and subset
+fn parse_relation(relation: &str) -> Result<(String, String, String)> {
+    let relation = relation.trim();
+    for (op, superset_first) in [("⊇", true), (">=", true), ("⊆", false), ("<=", false)] {
+        if let Some((left, right)) = relation.split_once(op) {
+            let (left, right) = (left.trim().to_string(), right.trim().to_string());
+            if left.is_empty() || right.is_empty() {
+                break;
+            }
+            let (superset, subset) = if superset_first { (left, right) } else { (right, left) };
+            return Ok((relation.to_string(), superset, subset));
+        }
+    }
+    Err(anyhow!(t_args!("spec-invalid-relation", "relation" => relation)))
+}
+
+/// Check that the transducers the tests and relations name are all in
+/// `Fsts`, and return the relations
+fn check_fst_names(raw: &RawSpec, named: &[&str]) -> Result<Vec<(String, String, String)>> {
+    let relations = raw
+        .config
+        .as_ref()
+        .and_then(|c| c.relations.as_ref())
+        .map(|relations| relations.iter().map(|r| parse_relation(r)).collect::<Result<Vec<_>>>())
+        .transpose()?
+        .unwrap_or_default();
+    let per_fst = raw
+        .tests
+        .values()
+        .flat_map(|map| map.values())
+        .any(|expected| matches!(expected, Expected::PerFst(_)));
+    if named.is_empty() {
+        if per_fst || !relations.is_empty() {
+            return Err(anyhow!(t!("spec-fsts-missing")));
+        }
+        return Ok(relations);
+    }
+    let known = |fst: &str| -> Result<()> {
+        if named.contains(&fst) {
+            Ok(())
+        } else {
+            Err(anyhow!(t_args!("spec-unknown-fst", "fst" => fst, "known" => named.join(", "))))
+        }
+    };
+    for (group, map) in &raw.tests {
+        for fst in split_group(group.trim()).1.unwrap_or_default() {
+            known(fst)?;
+        }
+        for expected in map.values() {
+            if let Expected::PerFst(per_fst) = expected {
+                for fst in per_fst.keys() {
+                    known(fst.trim())?;
                 }
             }
         }
-        
-        // Create Analyze-cases from the global accumulator
-        for ((surface, group_name), analyses_set) in surface_to_analyses {
-            let mut analyses: Vec<String> = analyses_set.into_iter().collect();
-            // Stable, deterministic order
-            analyses.sort();
-            let name = format!("{}: {}", group_name, surface);
+    }
+    for (_, superset, subset) in &relations {
+        known(superset)?;
+        known(subset)?;
+    }
+    Ok(relations)
+}
+
+/// The test cases of a YAML spec, for one of its named transducers or, when
+/// it names none, for its only one
+fn yaml_cases(raw: &RawSpec, fst: Option<&str>) -> Vec<TestCase> {
+    let mut cases: Vec<TestCase> = Vec::new();
+    // Global accumulator for analyze: (surface, group) -> set of analyses (lexical-key)
+    let mut surface_to_analyses: IndexMap<(String, String), BTreeSet<String>> = IndexMap::new();
+    let mut analysis_checks: IndexMap<(String, String), Vec<WeightCheck>> = IndexMap::new();
+
+    // For each group: build generate-cases and collect surface forms
+    for (group, map) in &raw.tests {
+        let group_name = match fst {
+            Some(fst) => {
+                let (name, selected) = split_group(group.trim());
+                if selected.is_some_and(|selected| !selected.contains(&fst)) {
+                    continue;
+                }
+                format!("{name} ({fst})")
+            }
+            None => group.trim().to_string(),
+        };
+        let group_name = group_name.as_str();
+        for (lexical, expected) in map {
+            let Some(expected) = expected.for_fst(fst) else {
+                continue;
+            };
+            let lexical_trim = lexical.trim().to_string();
+            let expect_vec = expected.forms();
+            // Separate positive and negative expectations
+            let mut positive_forms = Vec::new();
+            let mut negative_forms = Vec::new();
+
+            for surf in &expect_vec {
+                if let Some(actual_form) = surf.strip_prefix('~') {
+                    // Negative test: remove ~ prefix for the actual form
+                    negative_forms.push(actual_form.to_string());
+                } else {
+                    // Positive test
+                    positive_forms.push(surf.clone());
+                }
+            }
+
+            // 1) Generate-case: input=lexical, expect=positive surface forms, expect_not=negative forms
+            let name = format!("{}: {}", group_name, &lexical_trim);
             cases.push(TestCase {
                 name,
-                direction: Direction::Analyze,
-                input: surface,
-                expect: analyses,
-                expect_not: vec![], // No negative expectations for regular analysis tests
+                direction: Direction::Generate,
+                input: lexical_trim.clone(),
+                expect: positive_forms.clone(),
+                expect_not: negative_forms.clone(),
+                weight_checks: expected.generation_checks(),
             });
+
+            // 2) Invert to analyze: only positive surface forms should analyze to lexical
+            for surf in positive_forms {
+                let key = (surf, group_name.to_string());
+                analysis_checks
+                    .entry(key.clone())
+                    .or_default()
+                    .extend(expected.analysis_checks(&lexical_trim));
+                let entry = surface_to_analyses.entry(key).or_default();
+                entry.insert(lexical_trim.clone());
+            }
+
+            // 2b) Negative analyze-cases: negative forms should not analyze to anything
+            for neg_form in negative_forms {
+                // Create a separate negative analysis test
+                let name = format!("Analysis (negative): {}", neg_form);
+                cases.push(TestCase {
+                    name,
+                    direction: Direction::Analyze,
+                    input: neg_form,
+                    expect: vec![], // Expect no result
+                    expect_not: vec![], // No negative expectations needed for these
+                    weight_checks: vec![],
+                });
+            }
         }
-        let suite = TestSuite {
-            name: f
-                .file_name()
-                .map(|s| s.to_string_lossy().into_owned())
-                .unwrap_or_else(|| "suite".to_string()),
-            cases,
-        };
-        out.push(SuiteWithConfig {
-            suite,
-            backend,
-            lookup_cmd,
-            gen_fst,
-            morph_fst,
+    }
+
+    // Create Analyze-cases from the global accumulator
+    for ((surface, group_name), analyses_set) in surface_to_analyses {
+        let mut analyses: Vec<String> = analyses_set.into_iter().collect();
+        // Stable, deterministic order
+        analyses.sort();
+        let weight_checks = analysis_checks
+            .swap_remove(&(surface.clone(), group_name.clone()))
+            .unwrap_or_default();
+        let name = format!("{}: {}", group_name, surface);
+        cases.push(TestCase {
+            name,
+            direction: Direction::Analyze,
+            input: surface,
+            expect: analyses,
+            expect_not: vec![], // No negative expectations for regular analysis tests
+            weight_checks,
         });
     }
-    Ok(out)
+    cases
 }
 
-fn resolve_path_relative_to_yaml(path: &str, yaml_file_path: &PathBuf) -> String {
+pub(crate) fn resolve_path_relative_to_yaml(path: &str, yaml_file_path: &Path) -> String {
     let path_buf = std::path::Path::new(path);
     if path_buf.is_absolute() {
         path.to_string()
@@ -217,6 +863,28 @@ fn resolve_path_relative_to_yaml(path: &str, yaml_file_path: &PathBuf) -> String
 }
 
 pub fn determine_hfst_lookup_tool(gen_path: &str, morph_path: Option<&str>) -> String {
+    // The header tells best, whatever the files are called
+    let read = std::iter::once(gen_path)
+        .chain(morph_path)
+        .find_map(|path| read_format(path).ok().map(|format| (path, format)));
+    match read {
+        Some((_, FstFormat::HfstOptimized)) => return "hfst-optimised-lookup".to_string(),
+        Some((_, FstFormat::Hfst(_))) => return "hfst-lookup".to_string(),
+        // Only old headerless HFST files are told by their names alone
+        Some((path, format)) if !named_like_hfst(gen_path) && !morph_path.is_some_and(named_like_hfst) => {
+            // The label is localized too, so it cannot be made while the message is
+            let format = format.to_string();
+            warn!(
+                "{}",
+                t_args!("spec-hfst-tool-guessed",
+                    "file" => path,
+                    "format" => format
+                )
+            );
+        }
+        _ => {}
+    }
+
     // Check the generator FST extension first
     let gen_path_obj = std::path::Path::new(gen_path);
     if let Some(ext) = gen_path_obj.extension() {
@@ -243,46 +911,69 @@ pub fn determine_hfst_lookup_tool(gen_path: &str, morph_path: Option<&str>) -> S
     "hfst-optimised-lookup".to_string()
 }
 
+/// One test line of a lexc test block
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexcTest {
+    pub surface: String,
+    pub analysis: String,
+    /// From a `!!$` line: the surface form must not go with the analysis
+    pub negative: bool,
+    /// Line number in the lexc file
+    pub line: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct LexcTestSet {
     pub fst_type: String,    // e.g., "gt-norm"
     pub test_name: String,   // e.g., "gierehtse (*\"pulk\"*)"
-    pub tests: IndexMap<String, String>,  // surface_form -> analysis
+    pub tests: Vec<LexcTest>, // every test line, duplicates included
+}
+
+/// A test line's " surface_form: analysis ! comment"
+fn parse_lexc_test_line(test_line: &str) -> Option<(String, String)> {
+    let test_line = test_line.trim();
+    let colon_pos = test_line.find(':')?;
+    let surface_form = test_line[..colon_pos].trim().to_string();
+    let analysis_part = &test_line[colon_pos + 1..];
+    // Filter out comments starting with '!'
+    let analysis = if let Some(comment_pos) = analysis_part.find('!') {
+        analysis_part[..comment_pos].trim().to_string()
+    } else {
+        analysis_part.trim().to_string()
+    };
+    Some((surface_form, analysis))
 }
 
 pub fn parse_lexc_test_data(content: &str) -> Result<Vec<LexcTestSet>> {
     let mut test_sets = Vec::new();
     let mut current_set: Option<LexcTestSet> = None;
     
-    for line in content.lines() {
+    for (index, line) in content.lines().enumerate() {
         let line = line.trim();
         
-        if line.starts_with("!!€") {
-            let test_line = &line[5..]; // Remove !!€ prefix (€ is 3 bytes in UTF-8)
+        // A negative test line, in the GiellaLT convention: " surface_form: analysis"
+        if let Some(test_line) = line.strip_prefix("!!$") {
+            if let Some(ref mut test_set) = current_set
+                && let Some((surface, analysis)) = parse_lexc_test_line(test_line)
+            {
+                test_set.tests.push(LexcTest { surface, analysis, negative: true, line: index + 1 });
+            }
+        } else if let Some(test_line) = line.strip_prefix("!!€") {
             
             if test_line.starts_with(' ') {
                 // This is a test data line: " surface_form: analysis"
-                if let Some(ref mut test_set) = current_set {
-                    let test_line = test_line.trim();
-                    if let Some(colon_pos) = test_line.find(':') {
-                        let surface_form = test_line[..colon_pos].trim().to_string();
-                        let analysis_part = &test_line[colon_pos + 1..];
-                        // Filter out comments starting with '!'
-                        let analysis = if let Some(comment_pos) = analysis_part.find('!') {
-                            analysis_part[..comment_pos].trim().to_string()
-                        } else {
-                            analysis_part.trim().to_string()
-                        };
-                        test_set.tests.insert(surface_form, analysis);
-                    }
+                if let Some(ref mut test_set) = current_set
+                    && let Some((surface, analysis)) = parse_lexc_test_line(test_line)
+                {
+                    test_set.tests.push(LexcTest { surface, analysis, negative: false, line: index + 1 });
                 }
             } else {
                 // This is a header line: "fst_type: test_name # comment"
                 // Save previous test set if exists
-                if let Some(test_set) = current_set.take() {
-                    if !test_set.tests.is_empty() {
-                        test_sets.push(test_set);
-                    }
+                if let Some(test_set) = current_set.take()
+                    && !test_set.tests.is_empty()
+                {
+                    test_sets.push(test_set);
                 }
                 
                 // Parse header line
@@ -299,7 +990,7 @@ pub fn parse_lexc_test_data(content: &str) -> Result<Vec<LexcTestSet>> {
                     current_set = Some(LexcTestSet {
                         fst_type,
                         test_name,
-                        tests: IndexMap::new(),
+                        tests: Vec::new(),
                     });
                 }
             }
@@ -307,10 +998,10 @@ pub fn parse_lexc_test_data(content: &str) -> Result<Vec<LexcTestSet>> {
     }
     
     // Don't forget the last test set
-    if let Some(test_set) = current_set {
-        if !test_set.tests.is_empty() {
-            test_sets.push(test_set);
-        }
+    if let Some(test_set) = current_set
+        && !test_set.tests.is_empty()
+    {
+        test_sets.push(test_set);
     }
     
     Ok(test_sets)
@@ -323,14 +1014,14 @@ fn search_in_directory(dir: &std::path::Path, patterns: &[String]) -> Vec<PathBu
         for entry in entries.flatten() {
             let path = entry.path();
             
-            if path.is_file() {
-                if let Some(filename) = path.file_name() {
-                    let filename_str = filename.to_string_lossy();
-                    for pattern in patterns {
-                        if filename_str == *pattern {
-                            found_files.push(path);
-                            break;
-                        }
+            if path.is_file()
+                && let Some(filename) = path.file_name()
+            {
+                let filename_str = filename.to_string_lossy();
+                for pattern in patterns {
+                    if filename_str == *pattern {
+                        found_files.push(path);
+                        break;
                     }
                 }
             }
@@ -361,7 +1052,7 @@ fn search_upward_from_directory(start_dir: &std::path::Path, patterns: &[String]
     Vec::new()
 }
 
-pub fn find_fst_files(lexc_file_path: &PathBuf, fst_type: &str) -> Result<(String, Option<String>)> {
+pub fn find_fst_files(lexc_file_path: &Path, fst_type: &str) -> Result<(String, Option<String>)> {
     let analyzer_patterns = [
         format!("analyser-{}.hfstol", fst_type),
         format!("analyser-{}.hfst", fst_type),
@@ -407,7 +1098,7 @@ pub fn find_fst_files(lexc_file_path: &PathBuf, fst_type: &str) -> Result<(Strin
     Err(anyhow!("Could not find FST files for type: {} by searching upward from current directory or lexc file location", fst_type))
 }
 
-pub fn convert_lexc_to_suites(lexc_test_sets: Vec<LexcTestSet>, lexc_file_path: &PathBuf, _prefer: BackendChoice) -> Result<Vec<SuiteWithConfig>> {
+pub fn convert_lexc_to_suites(lexc_test_sets: Vec<LexcTestSet>, lexc_file_path: &Path, _prefer: BackendChoice) -> Result<Vec<SuiteWithConfig>> {
     // Group test sets by FST type
     let mut fst_groups: IndexMap<String, Vec<LexcTestSet>> = IndexMap::new();
     
@@ -430,33 +1121,70 @@ pub fn convert_lexc_to_suites(lexc_test_sets: Vec<LexcTestSet>, lexc_file_path:
         for test_set in test_sets {
             let group_name = format!("{} ({})", test_set.test_name, fst_type);
             
-            // Group surface forms by analysis for generation tests
-            let mut analysis_to_surfaces: IndexMap<String, Vec<String>> = IndexMap::new();
-            for (surface_form, analysis) in &test_set.tests {
-                analysis_to_surfaces.entry(analysis.clone()).or_default().push(surface_form.clone());
+            // Expected and unwanted forms: surface forms by analysis for
+            // generation tests, analyses by surface form for analysis tests
+            let mut analysis_to_surfaces: IndexMap<&str, (Vec<String>, Vec<String>)> = IndexMap::new();
+            let mut surface_to_analyses: IndexMap<&str, (Vec<String>, Vec<String>)> = IndexMap::new();
+            // Whether each pair was given as right or wrong, and on which line
+            let mut seen: HashMap<(&str, &str), (bool, usize)> = HashMap::new();
+            for test in &test_set.tests {
+                let test_text = format!("{}: {}", test.surface, test.analysis);
+                let (negative, line) = *seen
+                    .entry((&test.surface, &test.analysis))
+                    .or_insert((test.negative, test.line));
+                if negative != test.negative {
+                    return Err(anyhow!(t_args!("spec-lexc-contradiction",
+                        "file" => lexc_file_path.display(),
+                        "line" => test.line,
+                        "first" => line,
+                        "test" => test_text
+                    )));
+                }
+                if line != test.line {
+                    warn!(
+                        "{}",
+                        t_args!("warn-lexc-duplicate",
+                            "file" => lexc_file_path.display(),
+                            "line" => test.line,
+                            "test" => test_text
+                        )
+                    );
+                    continue;
+                }
+                let (generated, not_generated) = analysis_to_surfaces.entry(&test.analysis).or_default();
+                let (analyses, not_analyses) = surface_to_analyses.entry(&test.surface).or_default();
+                if test.negative {
+                    not_generated.push(test.surface.clone());
+                    not_analyses.push(test.analysis.clone());
+                } else {
+                    generated.push(test.surface.clone());
+                    analyses.push(test.analysis.clone());
+                }
             }
             
             // Generate test cases: one test per analysis, expecting all surface forms
-            for (analysis, surface_forms) in &analysis_to_surfaces {
+            for (analysis, (surface_forms, not_surface_forms)) in analysis_to_surfaces {
                 let name = format!("{}: {}", group_name, analysis);
                 all_cases.push(TestCase {
                     name,
                     direction: Direction::Generate,
-                    input: analysis.clone(),
-                    expect: surface_forms.clone(),
-                    expect_not: vec![],
+                    input: analysis.to_string(),
+                    expect: surface_forms,
+                    expect_not: not_surface_forms,
+                    weight_checks: vec![],
                 });
             }
             
-            // Analysis test cases: one per surface form
-            for (surface_form, analysis) in &test_set.tests {
-                let name = format!("{}: {}", group_name, &surface_form);
+            // Analysis test cases: one per surface form, expecting all its analyses
+            for (surface_form, (analyses, not_analyses)) in surface_to_analyses {
+                let name = format!("{}: {}", group_name, surface_form);
                 all_cases.push(TestCase {
                     name,
                     direction: Direction::Analyze,
-                    input: surface_form.clone(),
-                    expect: vec![analysis.clone()],
-                    expect_not: vec![],
+                    input: surface_form.to_string(),
+                    expect: analyses,
+                    expect_not: not_analyses,
+                    weight_checks: vec![],
                 });
             }
         }
@@ -477,35 +1205,162 @@ pub fn convert_lexc_to_suites(lexc_test_sets: Vec<LexcTestSet>, lexc_file_path:
             lookup_cmd,
             gen_fst,
             morph_fst,
+            native: false,
+            timeout: None,
+            app: None,
+            lookup_args: LookupArgs::default(),
+            output_parser: None,
+            relations: vec![],
+            normalizer: Normalizer::default(),
         });
     }
     
     Ok(suites)
 }
 
+/// `App` and `Args` from the config section of the chosen backend
+fn lookup_tool_settings(raw: &RawSpec, backend: &BackendChoice) -> (Option<String>, LookupArgs) {
+    let cfg = raw.config.as_ref();
+    let (app, args) = match backend {
+        BackendChoice::Hfst => cfg
+            .and_then(|c| c.hfst.as_ref())
+            .map(|h| (h.app.clone(), h.args.clone()))
+            .unwrap_or_default(),
+        BackendChoice::Foma => cfg
+            .and_then(|c| c.foma.as_ref())
+            .map(|x| (x.app.clone(), x.args.clone()))
+            .unwrap_or_default(),
+        BackendChoice::Apertium => cfg
+            .and_then(|c| c.apertium.as_ref())
+            .map(|a| (a.app.clone(), a.args.clone()))
+            .unwrap_or_default(),
+        // The command template after the program takes the place of `Args`
+        BackendChoice::Command => {
+            let rest: Vec<String> = cfg
+                .and_then(|c| c.command.as_ref())
+                .and_then(|c| c.run.as_ref())
+                .map(split_args)
+                .unwrap_or_default()
+                .into_iter()
+                .skip(1)
+                .collect();
+            let args = LookupArgs {
+                analyze: rest.clone(),
+                generate: rest,
+            };
+            return (None, args);
+        }
+        BackendChoice::Http | BackendChoice::Auto => (None, None),
+    };
+    (
+        app.map(|a| trim_owned(&a)),
+        args.map(|a| a.resolve()).unwrap_or_default(),
+    )
+}
+
+/// The parser for a command backend's output, from `Output` and `Pattern`
+fn output_parser(raw: &RawSpec, backend: &BackendChoice) -> Result<Option<OutputParser>> {
+    if !matches!(backend, BackendChoice::Command) {
+        return Ok(None);
+    }
+    let cfg = raw.config.as_ref().and_then(|c| c.command.as_ref());
+    let output = cfg.and_then(|c| c.output.as_deref());
+    let pattern = cfg.and_then(|c| c.pattern.as_deref());
+    OutputParser::from_config(output, pattern).map(Some)
+}
+
+/// The backend, lookup command and transducers a suite is looked up with
+struct FstSetup {
+    backend: BackendChoice,
+    lookup_cmd: String,
+    gen_fst: String,
+    morph_fst: Option<String>,
+}
+
+/// The setup for each of the transducers named in `Fsts`, or else for the
+/// transducers in the chosen section
 fn resolve_backend(
     raw: &RawSpec,
     prefer: &BackendChoice,
-    yaml_file_path: &PathBuf,
-) -> Result<(BackendChoice, String, String, Option<String>)> {
+    yaml_file_path: &Path,
+) -> Result<Vec<(Option<String>, FstSetup)>> {
     let cfg = raw
         .config
         .as_ref()
         .ok_or_else(|| anyhow!(t!("spec-missing-config")))?;
-    let chosen = match prefer {
+    let chosen = choose_backend(cfg, prefer)?;
+    let Some(fsts) = cfg.fsts.as_ref().filter(|fsts| !fsts.is_empty()) else {
+        let (gen_, morph) = section_fsts(cfg, &chosen, yaml_file_path)?;
+        return Ok(vec![(None, lookup_setup(cfg, chosen, gen_, morph)?)]);
+    };
+    if matches!(chosen, BackendChoice::Http) {
+        return Err(anyhow!(t!("spec-fsts-http")));
+    }
+    fsts.iter()
+        .map(|(name, fst)| {
+            let name = name.trim();
+            let gen_ = fst
+                .r#gen
+                .as_deref()
+                .ok_or_else(|| anyhow!(t_args!("spec-missing-fst-gen", "fst" => name)))?;
+            let gen_ = resolve_path_relative_to_yaml(gen_.trim(), yaml_file_path);
+            let morph = fst.morph.as_deref().map(|m| resolve_path_relative_to_yaml(m.trim(), yaml_file_path));
+            Ok((Some(name.to_string()), lookup_setup(cfg, chosen.clone(), gen_, morph)?))
+        })
+        .collect()
+}
+
+fn choose_backend(cfg: &RawConfig, prefer: &BackendChoice) -> Result<BackendChoice> {
+    // A suite written only for a service is run against it whichever
+    // section was asked for
+    let only_http = cfg.http.is_some()
+        && cfg.hfst.is_none()
+        && cfg.foma.is_none()
+        && cfg.apertium.is_none()
+        && cfg.command.is_none();
+    Ok(match prefer {
+        _ if only_http => BackendChoice::Http,
         BackendChoice::Hfst => BackendChoice::Hfst,
         BackendChoice::Foma => BackendChoice::Foma,
+        BackendChoice::Apertium => BackendChoice::Apertium,
+        BackendChoice::Http => BackendChoice::Http,
+        BackendChoice::Command => BackendChoice::Command,
         BackendChoice::Auto => {
             if cfg.hfst.as_ref().and_then(|h| h.r#gen.clone()).is_some() {
                 BackendChoice::Hfst
             } else if cfg.foma.as_ref().and_then(|x| x.r#gen.clone()).is_some() {
                 BackendChoice::Foma
+            } else if cfg.apertium.as_ref().and_then(|a| a.r#gen.clone()).is_some() {
+                BackendChoice::Apertium
+            } else if cfg.command.as_ref().and_then(|c| c.r#gen.clone()).is_some() {
+                BackendChoice::Command
+            } else if cfg.fsts.is_some() {
+                // Named transducers go with the first section there is
+                if cfg.hfst.is_some() {
+                    BackendChoice::Hfst
+                } else if cfg.foma.is_some() {
+                    BackendChoice::Foma
+                } else if cfg.apertium.is_some() {
+                    BackendChoice::Apertium
+                } else if cfg.command.is_some() {
+                    BackendChoice::Command
+                } else {
+                    BackendChoice::Hfst
+                }
             } else {
                 return Err(anyhow!(t!("spec-missing-gen")));
             }
         }
-    };
-    match chosen {
+    })
+}
+
+/// `Gen` and `Morph` of the chosen section, relative to the YAML file
+fn section_fsts(
+    cfg: &RawConfig,
+    chosen: &BackendChoice,
+    yaml_file_path: &Path,
+) -> Result<(String, Option<String>)> {
+    let (gen_, morph) = match chosen {
         BackendChoice::Hfst => {
             let h = cfg
                 .hfst
@@ -515,10 +1370,7 @@ fn resolve_backend(
                 .r#gen
                 .clone()
                 .ok_or_else(|| anyhow!(t!("spec-missing-hfst-gen")))?;
-            let gen_ = resolve_path_relative_to_yaml(&gen_.trim(), yaml_file_path);
-            let morph = h.morph.clone().map(|m| resolve_path_relative_to_yaml(&m.trim(), yaml_file_path));
-            let cmd = determine_hfst_lookup_tool(&gen_, morph.as_deref());
-            Ok((BackendChoice::Hfst, cmd, gen_, morph))
+            (gen_, h.morph.clone())
         }
         BackendChoice::Foma => {
             let x = cfg
@@ -529,16 +1381,93 @@ fn resolve_backend(
                 .r#gen
                 .clone()
                 .ok_or_else(|| anyhow!(t!("spec-missing-foma-gen")))?;
-            let gen_ = resolve_path_relative_to_yaml(&gen_.trim(), yaml_file_path);
-            let morph = x.morph.clone().map(|m| resolve_path_relative_to_yaml(&m.trim(), yaml_file_path));
-            let cmd = x
-                .app
+            (gen_, x.morph.clone())
+        }
+        BackendChoice::Apertium => {
+            let a = cfg
+                .apertium
+                .as_ref()
+                .ok_or_else(|| anyhow!(t!("spec-missing-apertium")))?;
+            let gen_ = a
+                .r#gen
                 .clone()
-                .unwrap_or_else(|| "flookup".to_string())
-                .trim()
+                .ok_or_else(|| anyhow!(t!("spec-missing-apertium-gen")))?;
+            (gen_, a.morph.clone())
+        }
+        BackendChoice::Command => {
+            let c = cfg
+                .command
+                .as_ref()
+                .ok_or_else(|| anyhow!(t!("spec-missing-command")))?;
+            let gen_ = c
+                .r#gen
+                .clone()
+                .ok_or_else(|| anyhow!(t!("spec-missing-command-gen")))?;
+            (gen_, c.morph.clone())
+        }
+        BackendChoice::Http => {
+            let url = cfg
+                .http
+                .as_ref()
+                .ok_or_else(|| anyhow!(t!("spec-missing-http")))?
+                .url
+                .as_deref()
+                .map(str::trim)
+                .ok_or_else(|| anyhow!(t!("spec-missing-http-url")))?
                 .to_string();
-            Ok((BackendChoice::Foma, cmd, gen_, morph))
+            // The service does both directions, so the URL stands in for both transducers
+            return Ok((url.clone(), Some(url)));
         }
         BackendChoice::Auto => unreachable!(),
-    }
+    };
+    let gen_ = resolve_path_relative_to_yaml(gen_.trim(), yaml_file_path);
+    let morph = morph.map(|m| resolve_path_relative_to_yaml(m.trim(), yaml_file_path));
+    Ok((gen_, morph))
+}
+
+/// The lookup command for a pair of transducers with the chosen section's
+/// settings. A foma network given as HFST is read as in the foma section.
+fn lookup_setup(
+    cfg: &RawConfig,
+    chosen: BackendChoice,
+    gen_: String,
+    morph: Option<String>,
+) -> Result<FstSetup> {
+    let (backend, cmd) = match chosen {
+        BackendChoice::Hfst => match cfg.hfst.as_ref().and_then(|h| h.app.as_ref()) {
+            Some(app) => (BackendChoice::Hfst, trim_owned(app)),
+            None if detect_format(&gen_, morph.as_deref()) == Some(FstFormat::Foma) => {
+                (BackendChoice::Foma, "flookup".to_string())
+            }
+            None => (BackendChoice::Hfst, determine_hfst_lookup_tool(&gen_, morph.as_deref())),
+        },
+        BackendChoice::Foma => {
+            let app = cfg.foma.as_ref().and_then(|x| x.app.as_deref());
+            (BackendChoice::Foma, trim_owned(app.unwrap_or("flookup")))
+        }
+        BackendChoice::Apertium => {
+            let cmd = match cfg.apertium.as_ref().and_then(|a| a.app.as_ref()) {
+                Some(app) => trim_owned(app),
+                None => default_proc_tool(&gen_, morph.as_deref()),
+            };
+            (BackendChoice::Apertium, cmd)
+        }
+        BackendChoice::Command => {
+            let cmd = cfg
+                .command
+                .as_ref()
+                .and_then(|c| c.run.as_ref())
+                .and_then(|run| split_args(run).into_iter().next())
+                .ok_or_else(|| anyhow!(t!("spec-missing-command-run")))?;
+            (BackendChoice::Command, cmd)
+        }
+        BackendChoice::Http => (BackendChoice::Http, gen_.clone()),
+        BackendChoice::Auto => unreachable!(),
+    };
+    Ok(FstSetup {
+        backend,
+        lookup_cmd: cmd,
+        gen_fst: gen_,
+        morph_fst: morph,
+    })
 }
@@ -0,0 +1,148 @@
+use crate::spec::{Expected, OneOrMany, TableCfg, TableLayout, lexical_key};
+use crate::t_args;
+use anyhow::{Result, anyhow};
+use indexmap::IndexMap;
+use std::path::Path;
+
+/// Groups of lexical keys with their expected forms, as in `Tests`
+pub type TableTests = IndexMap<String, IndexMap<String, Expected>>;
+
+/// The cell delimiter of a TSV or CSV test file, or None for other files
+pub fn table_delimiter(file: &Path) -> Option<u8> {
+    match file.extension()?.to_str()? {
+        "tsv" => Some(b'\t'),
+        "csv" => Some(b','),
+        _ => None,
+    }
+}
+
+/// The forms of a cell, separated by commas. A form with a comma of its own
+/// escapes it as `\,`.
+fn forms(cell: &str) -> Vec<String> {
+    let mut forms = vec![String::new()];
+    let mut chars = cell.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&',') => {
+                chars.next();
+                forms.last_mut().unwrap().push(',');
+            }
+            ',' => forms.push(String::new()),
+            _ => forms.last_mut().unwrap().push(c),
+        }
+    }
+    forms
+        .into_iter()
+        .map(|form| form.trim().to_string())
+        .filter(|form| !form.is_empty())
+        .collect()
+}
+
+/// Whether a negative column marks its row, i.e. is neither empty nor a no
+fn is_marked(cell: &str) -> bool {
+    !matches!(cell.to_lowercase().as_str(), "" | "0" | "-" | "no" | "n" | "false")
+}
+
+/// Read the tests of a TSV or CSV file. Each row gives forms for one lexical
+/// key (long layout) or for a lemma with each tag string in the header (wide
+/// layout); forms can be listed with commas in a cell, and as in YAML, a
+/// form that must not come out starts with `~`. The group is the `group`
+/// column's, or else the file name. In the wide layout, every column but the
+/// lemma and group ones is a tag string, so the columns of the long layout
+/// are errors there.
+pub fn read_table(content: &str, delimiter: u8, cfg: &TableCfg, file: &Path) -> Result<TableTests> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .comment(Some(b'#'))
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let header: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+    let column = |name: &Option<String>, default: &str| {
+        let name = name.as_deref().unwrap_or(default).trim().to_string();
+        let index = header.iter().position(|h| h.eq_ignore_ascii_case(&name));
+        (name, index)
+    };
+    let lemma = column(&cfg.lemma, "lemma");
+    let tags = column(&cfg.tags, "tags");
+    let surface = column(&cfg.surface, "surface");
+    let negative = column(&cfg.negative, "negative");
+    let group = column(&cfg.group, "group");
+    let layout = cfg.layout.unwrap_or(match surface.1 {
+        Some(_) => TableLayout::Long,
+        None => TableLayout::Wide,
+    });
+    let required = |(name, index): &(String, Option<usize>)| {
+        index.ok_or_else(|| anyhow!(t_args!("table-missing-column", "column" => name)))
+    };
+    if layout == TableLayout::Wide
+        && let Some((name, _)) = [&tags, &surface, &negative].into_iter().find(|(_, index)| index.is_some())
+    {
+        return Err(anyhow!(t_args!("table-wide-column", "column" => name)));
+    }
+    let default_group = file.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+
+    let mut tests: IndexMap<String, IndexMap<String, Vec<String>>> = IndexMap::new();
+    for record in reader.records() {
+        let record = record?;
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let line = record.position().map_or(0, |p| p.line());
+        let cell = |index: Option<usize>| index.and_then(|i| record.get(i)).unwrap_or("");
+        let incomplete = |name: &str| anyhow!(t_args!("table-incomplete-row", "line" => line, "column" => name));
+        let group_name = match cell(group.1) {
+            "" => default_group.clone(),
+            name => name.to_string(),
+        };
+        let keys = tests.entry(group_name).or_default();
+        match layout {
+            TableLayout::Long => {
+                let surface_index = required(&surface)?;
+                let key = lexical_key(cell(lemma.1), cell(Some(required(&tags)?)));
+                if key.is_empty() {
+                    return Err(incomplete(&tags.0));
+                }
+                let row_forms = forms(cell(Some(surface_index)));
+                if row_forms.is_empty() {
+                    return Err(incomplete(&surface.0));
+                }
+                let marked = is_marked(cell(negative.1));
+                let expected = keys.entry(key).or_default();
+                for form in row_forms {
+                    if marked && !form.starts_with('~') {
+                        expected.push(format!("~{form}"));
+                    } else {
+                        expected.push(form);
+                    }
+                }
+            }
+            TableLayout::Wide => {
+                let lemma_index = required(&lemma)?;
+                let lemma_cell = cell(Some(lemma_index));
+                if lemma_cell.is_empty() {
+                    return Err(incomplete(&lemma.0));
+                }
+                for (i, tag_string) in header.iter().enumerate() {
+                    if i == lemma_index || Some(i) == group.1 || tag_string.is_empty() {
+                        continue;
+                    }
+                    let cell_forms = forms(cell(Some(i)));
+                    if !cell_forms.is_empty() {
+                        keys.entry(lexical_key(lemma_cell, tag_string)).or_default().extend(cell_forms);
+                    }
+                }
+            }
+        }
+    }
+    Ok(tests
+        .into_iter()
+        .map(|(group, keys)| {
+            let keys = keys
+                .into_iter()
+                .map(|(key, forms)| (key, Expected::Forms(OneOrMany::Many(forms))))
+                .collect();
+            (group, keys)
+        })
+        .collect())
+}
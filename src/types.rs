@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "lowercase")]
@@ -14,8 +14,74 @@ pub struct TestCase {
     pub input: String,
     pub expect: Vec<String>,
     pub expect_not: Vec<String>, // Negative expectations - these should NOT appear in results
+    pub weight_checks: Vec<WeightCheck>,
 }
 
+/// An assertion about the weight of one of the outputs for a case's input
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeightCheck {
+    /// No other output has a lower weight
+    Best(String),
+    /// The output's weight is below the limit
+    Below(String, f64),
+}
+
+impl WeightCheck {
+    pub fn output(&self) -> &str {
+        match self {
+            WeightCheck::Best(output) | WeightCheck::Below(output, _) => output,
+        }
+    }
+
+    /// Outputs without a weight count as weight 0, like unweighted transducers
+    pub fn holds(&self, outputs: &[LookupOutput]) -> bool {
+        let weight_of = |o: &LookupOutput| o.weight.unwrap_or(0.0);
+        let Some(found) = outputs.iter().find(|o| o.output == self.output()) else {
+            return false;
+        };
+        match self {
+            WeightCheck::Best(_) => outputs.iter().all(|o| weight_of(found) <= weight_of(o)),
+            WeightCheck::Below(_, limit) => weight_of(found) < *limit,
+        }
+    }
+}
+
+/// One result of a lookup, with its weight if the lookup reports one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LookupOutput {
+    pub output: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    /// The output as the lookup gave it, when normalizing changed it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+}
+
+impl LookupOutput {
+    pub fn new(output: impl Into<String>, weight: Option<f64>) -> Self {
+        Self {
+            output: output.into(),
+            weight,
+            raw: None,
+        }
+    }
+}
+
+impl From<&str> for LookupOutput {
+    fn from(output: &str) -> Self {
+        Self::new(output, None)
+    }
+}
+
+impl From<String> for LookupOutput {
+    fn from(output: String) -> Self {
+        Self::new(output, None)
+    }
+}
+
+/// Outcome of looking up a single input: its outputs, or why the lookup failed
+pub type LookupOutcome = Result<Vec<LookupOutput>, String>;
+
 #[derive(Debug, Clone)]
 pub struct TestSuite {
     pub name: String,
@@ -28,7 +94,13 @@ pub struct CaseResult {
     pub direction: Direction,
     pub input: String,
     pub expected: Vec<String>,
+    /// Outputs that must not come out. When nothing is expected besides,
+    /// other outputs are not checked.
+    pub unwanted: Vec<String>,
     pub actual: Vec<String>,
+    /// The outputs as the backend returned them, in its order and with weights
+    pub outputs: Vec<LookupOutput>,
+    pub weight_checks: Vec<WeightCheck>,
     pub error: Option<String>,
     pub passed: bool,
 }
@@ -45,3 +117,17 @@ pub struct Summary {
     pub passed_expectations: usize,
     pub failed_expectations: usize,
 }
+
+/// A relation between the analyses of two suites run against different
+/// transducers, such as `desc ⊇ norm`: for every surface form both suites
+/// analyse, the first gives at least the analyses of the second
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Relation {
+    /// Name to report the relation under, e.g. `nouns.yaml (desc ⊇ norm)`
+    pub name: String,
+    /// The relation as written in the spec
+    pub label: String,
+    /// Name of the suite whose analyses must include those of `subset`
+    pub superset: String,
+    pub subset: String,
+}
@@ -0,0 +1,113 @@
+use crate::spec::RawConfig;
+use crate::{t, t_args};
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+
+/// What `${fsttype}` stands for in a project config, where it is left for
+/// each test file to fill in
+pub(crate) const KEEP_FSTTYPE: &str = "${fsttype}";
+
+/// Values of the built-in variables for one YAML file
+pub struct ConfigVars<'a> {
+    /// The directory of the YAML file, made absolute
+    pub yamldir: PathBuf,
+    /// The transducer type, such as `gt-norm`
+    pub fsttype: Option<&'a str>,
+}
+
+/// A directory from the first of the environment variables that is set,
+/// relative to the working directory as make sets them, or else the working
+/// directory itself, as for an in-tree build
+fn env_dir(names: &[&str]) -> Result<String> {
+    let cwd = std::env::current_dir()?;
+    let dir = match names.iter().find_map(std::env::var_os) {
+        Some(dir) => cwd.join(dir),
+        None => cwd,
+    };
+    Ok(dir.to_string_lossy().into_owned())
+}
+
+impl<'a> ConfigVars<'a> {
+    pub fn new(file: &Path, fsttype: Option<&'a str>) -> Result<Self> {
+        let dir = file.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        Ok(Self {
+            yamldir: std::path::absolute(dir)?,
+            fsttype,
+        })
+    }
+
+    fn lookup(&self, name: &str) -> Result<String> {
+        match name {
+            "yamldir" => Ok(self.yamldir.to_string_lossy().into_owned()),
+            "fsttype" => self
+                .fsttype
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!(t!("spec-no-fsttype"))),
+            "builddir" => env_dir(&["builddir", "abs_builddir"]),
+            "srcdir" => env_dir(&["srcdir", "abs_srcdir"]),
+            _ => std::env::var(name).map_err(|_| anyhow!(t_args!("spec-unknown-variable", "name" => name))),
+        }
+    }
+
+    /// Expand every `${name}` in a value: the built-in variables, or else
+    /// the environment
+    pub fn expand(&self, value: &str) -> Result<String> {
+        let mut out = String::new();
+        let mut rest = value;
+        while let Some(start) = rest.find("${") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after
+                .find('}')
+                .ok_or_else(|| anyhow!(t_args!("spec-unclosed-variable", "value" => value)))?;
+            out.push_str(&self.lookup(after[..end].trim())?);
+            rest = &after[end + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// The transducer type in a file name such as `nouns_gt-norm.yaml`
+pub fn fsttype_of(file: &Path) -> Option<String> {
+    let stem = file.file_stem()?.to_string_lossy();
+    let (_, fsttype) = stem.rsplit_once('_')?;
+    (!fsttype.is_empty()).then(|| fsttype.to_string())
+}
+
+/// Expand the variables in the transducers and lookup tools of a Config. In
+/// `Fsts`, `${fsttype}` is the name the transducers are given there.
+pub fn expand_config(cfg: &mut RawConfig, file: &Path, fsttype: Option<&str>) -> Result<()> {
+    let vars = ConfigVars::new(file, fsttype)?;
+    let expand = |value: &mut Option<String>, vars: &ConfigVars| -> Result<()> {
+        if let Some(value) = value {
+            *value = vars.expand(value)?;
+        }
+        Ok(())
+    };
+    if let Some(h) = &mut cfg.hfst {
+        expand(&mut h.r#gen, &vars)?;
+        expand(&mut h.morph, &vars)?;
+        expand(&mut h.app, &vars)?;
+    }
+    if let Some(x) = &mut cfg.foma {
+        expand(&mut x.r#gen, &vars)?;
+        expand(&mut x.morph, &vars)?;
+        expand(&mut x.app, &vars)?;
+    }
+    if let Some(a) = &mut cfg.apertium {
+        expand(&mut a.r#gen, &vars)?;
+        expand(&mut a.morph, &vars)?;
+        expand(&mut a.app, &vars)?;
+    }
+    if let Some(c) = &mut cfg.command {
+        expand(&mut c.r#gen, &vars)?;
+        expand(&mut c.morph, &vars)?;
+    }
+    for (name, fst) in cfg.fsts.iter_mut().flatten() {
+        let vars = ConfigVars::new(file, Some(name.trim()))?;
+        expand(&mut fst.r#gen, &vars)?;
+        expand(&mut fst.morph, &vars)?;
+    }
+    Ok(())
+}
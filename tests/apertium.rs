@@ -0,0 +1,174 @@
+use anyhow::Result;
+use morph_test2::apertium::{analyses, generations, parse_stream};
+
+#[test]
+fn parses_lexical_units_and_escapes() {
+    assert_eq!(
+        parse_stream("[<p>]^cats/cat<n><pl>/cat<vblex><pres><p3><sg>$ ^\\/\\^/\\/<sym>$[\\]]"),
+        vec![
+            vec!["cats", "cat<n><pl>", "cat<vblex><pres><p3><sg>"],
+            vec!["/^", "/<sym>"],
+        ]
+    );
+    assert!(parse_stream("no units here").is_empty());
+}
+
+#[test]
+fn reads_analyses_and_generations() {
+    assert_eq!(analyses("^cats/cat<n><pl>$\n"), ["cat<n><pl>"]);
+    // Unknown words have no analyses
+    assert!(analyses("^dogz/*dogz$").is_empty());
+    // Inputs of several lexical units get every combination of readings
+    assert_eq!(
+        analyses("^big/big<adj>$ ^cats/cat<n><pl>/cat<vblex><pres><p3><sg>$"),
+        ["big<adj> cat<n><pl>", "big<adj> cat<vblex><pres><p3><sg>"]
+    );
+
+    assert_eq!(generations("cats\n"), ["cats"]);
+    assert_eq!(generations("colour/color"), ["colour", "color"]);
+    assert!(generations("#cat<n><du>").is_empty());
+    assert!(generations("@cat<n><du>").is_empty());
+}
+
+#[cfg(unix)]
+mod with_tool {
+    use super::*;
+    use morph_test2::apertium::ApertiumBackend;
+    use morph_test2::backend::{Backend, LookupArgs};
+    use morph_test2::engine::run_suites;
+    use morph_test2::spec::{BackendChoice, load_specs};
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use std::sync::Once;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    static INIT: Once = Once::new();
+
+    fn init() {
+        INIT.call_once(morph_test2::i18n::init);
+    }
+
+    // Behaves like `lt-proc -z`: NUL ends every input and every answer.
+    // With -g it generates, otherwise it analyses; "slow" never gets an answer.
+    const FAKE_LT_PROC: &str = r#"#!/usr/bin/env bash
+gen=0
+for a in "$@"; do [ "$a" = "-g" ] && gen=1; done
+while IFS= read -r -d '' input; do
+  if [ $gen = 1 ]; then
+    case "$input" in
+      '^cat<n><pl>$') printf 'cats' ;;
+      '^cat<n><sg>$') printf 'cat' ;;
+      *) printf '#%s' "${input:1:${#input}-2}" ;;
+    esac
+  else
+    case "$input" in
+      cats) printf '^cats/cat<n><pl>/cat<vblex><pres><p3><sg>$' ;;
+      cat) printf '^cat/cat<n><sg>$' ;;
+      '1\/2') printf '^1\\/2/1\\/2<num>$' ;;
+      slow) sleep 10 ;;
+      *) printf '^%s/*%s$' "$input" "$input" ;;
+    esac
+  fi
+  printf '\0'
+done
+"#;
+
+    fn write_tool(dir: &Path) -> Result<String> {
+        let tool = dir.join("lt-proc");
+        fs::write(&tool, FAKE_LT_PROC)?;
+        fs::set_permissions(&tool, fs::Permissions::from_mode(0o755))?;
+        Ok(tool.to_string_lossy().into_owned())
+    }
+
+    fn backend(tool: String) -> ApertiumBackend {
+        ApertiumBackend {
+            proc_cmd: tool,
+            args: LookupArgs::default(),
+            generator_fst: Some("eng.autogen.bin".into()),
+            analyzer_fst: Some("eng.automorf.bin".into()),
+            timeout: Some(Duration::from_millis(500)),
+            quiet: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn looks_up_both_directions() -> Result<()> {
+        init();
+        let dir = tempdir()?;
+        let backend = backend(write_tool(dir.path())?);
+
+        let inputs: Vec<String> = vec!["cats".into(), "dogz".into(), "slow".into(), "cat".into()];
+        let outcomes = backend.analyze_batch(&inputs).await?;
+        let outputs: Vec<Vec<String>> = outcomes
+            .iter()
+            .map(|o| o.clone().unwrap_or_default().into_iter().map(|o| o.output).collect())
+            .collect();
+        assert_eq!(outputs[0], ["cat<n><pl>", "cat<vblex><pres><p3><sg>"]);
+        assert!(outputs[1].is_empty());
+        // A hanging input fails on its own, the rest go to a fresh process
+        assert!(outcomes[2].as_ref().unwrap_err().contains("slow"));
+        assert_eq!(outputs[3], ["cat<n><sg>"]);
+
+        let outcomes = backend
+            .generate_batch(&["cat<n><pl>".into(), "cat<n><du>".into()])
+            .await?;
+        assert_eq!(outcomes[0], Ok(vec!["cats".into()]));
+        assert_eq!(outcomes[1], Ok(vec![]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn escapes_stream_characters_in_analysis_inputs() -> Result<()> {
+        init();
+        let dir = tempdir()?;
+        let backend = backend(write_tool(dir.path())?);
+
+        let outcomes = backend.analyze_batch(&["1/2".into()]).await?;
+        assert_eq!(outcomes[0], Ok(vec!["1/2<num>".into()]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn runs_yaml_tests_with_apertium_config() -> Result<()> {
+        init();
+        let dir = tempdir()?;
+        let tool = write_tool(dir.path())?;
+        let file = dir.path().join("eng.yaml");
+        let yaml = format!(
+            r#"
+Config:
+  Apertium:
+    Gen: eng.autogen.bin
+    Morph: eng.automorf.bin
+    App: {tool}
+Tests:
+  Nouns:
+    cat<n><sg>: cat
+    cat<n><pl>: [cats, ~catz]
+"#
+        );
+        fs::write(&file, yaml)?;
+        let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Auto)?;
+        assert!(matches!(swc[0].backend, BackendChoice::Apertium));
+        assert_eq!(swc[0].lookup_cmd, tool);
+
+        let backend = ApertiumBackend {
+            generator_fst: Some(swc[0].gen_fst.clone()),
+            analyzer_fst: swc[0].morph_fst.clone(),
+            ..backend(tool)
+        };
+        // "cats" also analyses as a verb, which is what makes it fail
+        let summary = run_suites(&backend, std::slice::from_ref(&swc[0].suite), false).await;
+        let failed: Vec<&str> = summary
+            .cases
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.input.as_str())
+            .collect();
+        assert_eq!(failed, vec!["cats"]);
+        assert_eq!(run_suites(&backend, std::slice::from_ref(&swc[0].suite), true).await.failed, 0);
+        Ok(())
+    }
+}
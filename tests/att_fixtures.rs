@@ -0,0 +1,107 @@
+use anyhow::Result;
+use morph_test2::att::parse_att;
+use morph_test2::backend::Backend;
+use morph_test2::engine::run_suites;
+use morph_test2::fst::Apply;
+use morph_test2::native::NativeBackend;
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::LookupOutput;
+use std::fs;
+use std::sync::Once;
+use tempfile::tempdir;
+
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[tokio::test]
+async fn runs_yaml_suite_against_att_fixtures() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let file = dir.path().join("suite.yaml");
+    let yaml = format!(
+        r#"
+Config:
+  hfst:
+    Gen: {}
+    Morph: {}
+Tests:
+  Verb - sample:
+    gæljodh+V+TV+Ind+Prs+Sg1: gæljoem
+    gæljodh+V+TV+Ind+Prs+Pl2: [gæljoejidie, ~gæljoejide]
+    foo+V: [bar, baz]
+"#,
+        fixture("generator.att"),
+        fixture("analyser.att")
+    );
+    fs::write(&file, yaml)?;
+    let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Auto)?;
+    assert_eq!(swc.len(), 1);
+    assert!(swc[0].native);
+
+    let backend = NativeBackend::new(Some(&swc[0].gen_fst), swc[0].morph_fst.as_deref())?;
+    assert_eq!(
+        backend.analyze_batch(&["baz".into()]).await?,
+        vec![Ok(vec![LookupOutput::new("foo+V", Some(1.5))])]
+    );
+    let summary = run_suites(&backend, std::slice::from_ref(&swc[0].suite), false).await;
+    assert_eq!(summary.failed, 0);
+    assert_eq!(summary.passed, summary.total);
+    Ok(())
+}
+
+#[test]
+fn parses_weights_epsilons_and_escapes() -> Result<()> {
+    init();
+    let fst = parse_att(
+        "0\t1\ta\tb\t0.5\n\
+         1\t2\t@_EPSILON_SYMBOL_@\t@_SPACE_@\n\
+         2\t3\t@0@\tc\n\
+         1\t0.25\n\
+         3\n\
+         --\n\
+         0\t1\tx\ty\n",
+    )?;
+    // Path weights add up, the lowest weighted output comes first
+    assert_eq!(
+        fst.lookup("a", Apply::Down),
+        vec![LookupOutput::new("b c", Some(0.5)), LookupOutput::new("b", Some(0.75))]
+    );
+    assert_eq!(fst.lookup("b c", Apply::Up), vec![LookupOutput::new("a", Some(0.5))]);
+    // Only the first transducer in the file is read
+    assert!(fst.lookup("x", Apply::Down).is_empty());
+
+    // A file without weights gives none
+    let unweighted = parse_att("0\t1\tx\ty\n1\n")?;
+    assert_eq!(unweighted.lookup("x", Apply::Down), vec![LookupOutput::new("y", None)]);
+
+    assert!(parse_att("0\t1\ta\n").is_err());
+    let err = parse_att("0\t1\ta\tb\theavy\n").unwrap_err();
+    assert!(err.to_string().contains("heavy"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn native_lookup_gives_up_on_input_after_timeout() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    // Every `a` is either `x` or `y`, so the outputs double with each one
+    let file = dir.path().join("doubling.att");
+    fs::write(&file, "0\t0\ta\tx\n0\t0\ta\ty\n0\n")?;
+    let backend = NativeBackend {
+        timeout: Some(std::time::Duration::from_millis(200)),
+        ..NativeBackend::new(Some(&file.to_string_lossy()), None)?
+    };
+
+    let endless = "a".repeat(60);
+    let outcomes = backend.generate_batch(&["aa".into(), endless.clone()]).await?;
+    assert_eq!(outcomes[0].as_ref().map(Vec::len), Ok(4));
+    let error = outcomes[1].clone().unwrap_err();
+    assert!(error.contains(&endless), "error should name the input: {error}");
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use anyhow::Result;
+use morph_test2::command::{OutputParser, fill_template};
+use morph_test2::types::Direction;
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+#[test]
+fn templates_take_the_transducer_and_direction() {
+    let template: Vec<String> = ["--mode={direction}", "-f", "{fst}"].map(String::from).into();
+    assert_eq!(
+        fill_template(&template, "analyser.hfstol", &Direction::Analyze),
+        ["--mode=analyze", "-f", "analyser.hfstol"]
+    );
+    // Without a placeholder the transducer goes last, as for lookup tools
+    let template: Vec<String> = ["-q", "{direction}"].map(String::from).into();
+    assert_eq!(
+        fill_template(&template, "generator.hfstol", &Direction::Generate),
+        ["-q", "generate", "generator.hfstol"]
+    );
+}
+
+#[test]
+fn output_parsers_are_chosen_by_name() -> Result<()> {
+    init();
+    assert!(matches!(OutputParser::from_config(None, None)?, OutputParser::Tabular));
+    assert!(matches!(OutputParser::from_config(Some("xerox"), None)?, OutputParser::Tabular));
+    assert!(matches!(OutputParser::from_config(Some("Apertium"), None)?, OutputParser::Apertium));
+    assert!(matches!(OutputParser::from_config(Some("lines"), None)?, OutputParser::Lines));
+    assert!(matches!(
+        OutputParser::from_config(None, Some("^(?P<output>.*)$"))?,
+        OutputParser::Regex(_)
+    ));
+    assert!(OutputParser::from_config(Some("regex"), None).is_err());
+    assert!(OutputParser::from_config(Some("regex"), Some("^(.*)$")).is_err());
+    assert!(OutputParser::from_config(Some("json"), None).is_err());
+    Ok(())
+}
+
+#[cfg(unix)]
+mod with_tool {
+    use super::*;
+    use morph_test2::backend::{Backend, LookupArgs};
+    use morph_test2::command::CommandBackend;
+    use morph_test2::spec::{BackendChoice, load_specs};
+    use morph_test2::types::LookupOutput;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    // A wrapper with output no lookup tool has: a banner, then
+    // "input => output (weight)" lines and a blank line after each input
+    const WRAPPER: &str = r#"#!/bin/sh
+echo "wrapper 1.0 for $2, $1"
+while IFS= read -r line; do
+  case "$line" in
+    guolli) printf '%s => %s (0.5)\n%s => %s (1.5)\n' "$line" "$line+N+Sg+Nom" "$line" "$line+N+Sg+Gen" ;;
+    *+N+Sg+Nom) printf '%s => guolli\n' "$line" ;;
+  esac
+  echo
+done
+"#;
+
+    // Answers each input with a line of Apertium stream
+    const LINE_PROC: &str = r#"#!/bin/sh
+while IFS= read -r line; do
+  if [ "$1" = "generate" ]; then echo "cats"; else echo "^$line/cat<n><pl>\$"; fi
+done
+"#;
+
+    fn write_tool(dir: &Path, name: &str, script: &str) -> Result<String> {
+        let tool = dir.join(name);
+        fs::write(&tool, script)?;
+        fs::set_permissions(&tool, fs::Permissions::from_mode(0o755))?;
+        Ok(tool.to_string_lossy().into_owned())
+    }
+
+    fn backend(program: String, template: &[&str], parser: OutputParser) -> CommandBackend {
+        let template: Vec<String> = template.iter().map(|arg| arg.to_string()).collect();
+        CommandBackend {
+            program,
+            args: LookupArgs {
+                analyze: template.clone(),
+                generate: template,
+            },
+            parser,
+            generator_fst: Some("generator.hfstol".into()),
+            analyzer_fst: Some("analyser.hfstol".into()),
+            timeout: Some(Duration::from_secs(5)),
+            quiet: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn regex_parser_reads_wrapper_output() -> Result<()> {
+        init();
+        let dir = tempdir()?;
+        let tool = write_tool(dir.path(), "wrapper", WRAPPER)?;
+        let parser = OutputParser::from_config(None, Some(r"^\S+ => (?P<output>\S+)( \((?P<weight>[\d.]+)\))?$"))?;
+        let backend = backend(tool, &["{direction}", "{fst}"], parser);
+
+        let inputs: Vec<String> = ["guolli", "guoli"].map(String::from).into();
+        let outcomes = backend.analyze_batch(&inputs).await?;
+        assert_eq!(
+            outcomes,
+            vec![
+                Ok(vec![
+                    LookupOutput::new("guolli+N+Sg+Nom", Some(0.5)),
+                    LookupOutput::new("guolli+N+Sg+Gen", Some(1.5))
+                ]),
+                Ok(vec![]),
+            ]
+        );
+        let outcomes = backend.generate_batch(&["guolli+N+Sg+Nom".to_string()]).await?;
+        assert_eq!(outcomes, vec![Ok(vec![LookupOutput::from("guolli")])]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn apertium_parser_reads_a_line_per_input() -> Result<()> {
+        init();
+        let dir = tempdir()?;
+        let tool = write_tool(dir.path(), "line-proc", LINE_PROC)?;
+        let backend = backend(tool, &["{direction}"], OutputParser::Apertium);
+
+        let outcomes = backend.analyze_batch(&["cats".to_string()]).await?;
+        assert_eq!(outcomes, vec![Ok(vec![LookupOutput::from("cat<n><pl>")])]);
+        let outcomes = backend.generate_batch(&["cat<n><pl>".to_string()]).await?;
+        assert_eq!(outcomes, vec![Ok(vec![LookupOutput::from("cats")])]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn a_failing_exit_after_all_answers_keeps_them() -> Result<()> {
+        init();
+        let dir = tempdir()?;
+        let script = LINE_PROC.replace("done\n", "done\nexit 1\n");
+        let tool = write_tool(dir.path(), "failing-proc", &script)?;
+        let backend = backend(tool, &["{direction}"], OutputParser::Apertium);
+
+        let outcomes = backend.analyze_batch(&["cats".to_string(), "dogs".to_string()]).await?;
+        assert_eq!(
+            outcomes,
+            vec![
+                Ok(vec![LookupOutput::from("cat<n><pl>")]),
+                Ok(vec![LookupOutput::from("cat<n><pl>")])
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validation_checks_the_transducers() -> Result<()> {
+        init();
+        let dir = tempdir()?;
+        let tool = write_tool(dir.path(), "line-proc", LINE_PROC)?;
+        let mut backend = backend(tool, &["{direction}"], OutputParser::Apertium);
+        let err = backend.validate().await.unwrap_err();
+        assert!(err.to_string().contains("generator.hfstol"), "{err}");
+
+        let fst = dir.path().join("generator.att");
+        fs::write(&fst, "0\t1\ta\tb\n1\n")?;
+        backend.generator_fst = Some(fst.to_string_lossy().into_owned());
+        backend.analyzer_fst = None;
+        backend.validate().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn command_section_is_read_from_config() -> Result<()> {
+        init();
+        let dir = tempdir()?;
+        let file = dir.path().join("suite.yaml");
+        fs::write(
+            &file,
+            r#"
+Config:
+  Command:
+    Gen: generator.hfstol
+    Run: ./wrapper.sh --{direction} {fst}
+    Output: lines
+Tests:
+  Nouns:
+    guolli+N+Sg+Nom: guolli
+"#,
+        )?;
+        let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Auto)?;
+        assert!(matches!(swc[0].backend, BackendChoice::Command));
+        assert_eq!(swc[0].lookup_cmd, "./wrapper.sh");
+        assert_eq!(swc[0].lookup_args.analyze, ["--{direction}", "{fst}"]);
+        assert!(matches!(swc[0].output_parser, Some(OutputParser::Lines)));
+        Ok(())
+    }
+}
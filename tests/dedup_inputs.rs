@@ -0,0 +1,122 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use morph_test2::backend::Backend;
+use morph_test2::engine::{run_suites, run_suites_separately};
+use morph_test2::types::*;
+use std::sync::{Mutex, Once};
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+/// Generates "<input>+N" and records every batch it is asked to look up
+#[derive(Default)]
+struct RecordingBackend {
+    batches: Mutex<Vec<Vec<String>>>,
+}
+
+#[async_trait]
+impl Backend for RecordingBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        self.batches.lock().unwrap().push(inputs.to_vec());
+        Ok(inputs.iter().map(|_| Ok(vec![])).collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        self.batches.lock().unwrap().push(inputs.to_vec());
+        Ok(inputs.iter().map(|input| Ok(vec![format!("{input}+N").into()])).collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn generate(name: &str, input: &str, expect: &str) -> TestCase {
+    TestCase {
+        name: name.into(),
+        direction: Direction::Generate,
+        input: input.into(),
+        expect: vec![expect.into()],
+        expect_not: vec![],
+        weight_checks: vec![],
+    }
+}
+
+fn suites() -> Vec<TestSuite> {
+    vec![
+        TestSuite {
+            name: "nouns.yaml".into(),
+            cases: vec![
+                generate("Nouns: guolli", "guolli", "guolli+N"),
+                generate("Nouns: beana", "beana", "beana+N"),
+                generate("Plurals: guolli", "guolli", "guolli+N"),
+            ],
+        },
+        TestSuite {
+            name: "more-nouns.yaml".into(),
+            cases: vec![
+                generate("Nouns: beana", "beana", "wrong"),
+                generate("Nouns: sápmi", "sápmi", "sápmi+N"),
+            ],
+        },
+    ]
+}
+
+#[tokio::test]
+async fn shared_inputs_are_looked_up_once() {
+    init();
+    let backend = RecordingBackend::default();
+    let summary = run_suites(&backend, &suites(), false).await;
+
+    let batches = backend.batches.lock().unwrap();
+    assert_eq!(*batches, vec![vec!["guolli", "beana", "sápmi"]]);
+    assert_eq!(summary.total, 5);
+    assert_eq!(summary.failed, 1);
+    let actual: Vec<&[String]> = summary.cases.iter().map(|c| c.actual.as_slice()).collect();
+    assert_eq!(actual[2], ["guolli+N"]);
+    assert_eq!(actual[3], ["beana+N"]);
+}
+
+#[tokio::test]
+async fn results_are_reported_per_suite() {
+    init();
+    let backend = RecordingBackend::default();
+    let summaries = run_suites_separately(&backend, &suites(), false).await;
+
+    assert_eq!(backend.batches.lock().unwrap().len(), 1);
+    assert_eq!(summaries.len(), 2);
+    assert_eq!((summaries[0].total, summaries[0].failed), (3, 0));
+    assert_eq!((summaries[1].total, summaries[1].failed), (2, 1));
+    assert_eq!(summaries[1].cases[0].input, "beana");
+    assert!(!summaries[1].cases[0].passed);
+}
+
+/// Answers only the first input of every batch
+struct ShortBackend;
+
+#[async_trait]
+impl Backend for ShortBackend {
+    async fn analyze_batch(&self, _inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(vec![])
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().take(1).map(|input| Ok(vec![format!("{input}+N").into()])).collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn missing_outcomes_fail_the_batch() {
+    init();
+    let summary = run_suites(&ShortBackend, &suites(), false).await;
+    assert_eq!(summary.failed, summary.total);
+    let error = summary.cases[0].error.as_deref().unwrap();
+    assert!(error.contains("1 of 3"), "{error}");
+}
@@ -0,0 +1,208 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use morph_test2::backend::{Backend, LookupArgs};
+use morph_test2::engine::run_suites;
+use morph_test2::fixture::{Fixture, Recorder, RecordingBackend, ReplayBackend, fixture_key};
+use morph_test2::types::*;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+/// Analyses "guolli" with a weight, generates "guolli" from its nominative,
+/// and times out on "slow"
+struct FakeBackend;
+
+#[async_trait]
+impl Backend for FakeBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs
+            .iter()
+            .map(|input| match input.as_str() {
+                "guolli" => Ok(vec![LookupOutput::new("guolli+N+Sg+Nom", Some(0.5))]),
+                "slow" => Err("timed out".to_string()),
+                _ => Ok(vec![]),
+            })
+            .collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs
+            .iter()
+            .map(|input| match input.as_str() {
+                "guolli+N+Sg+Nom" => Ok(vec!["guolli".into()]),
+                _ => Ok(vec![]),
+            })
+            .collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn case(direction: Direction, input: &str, expect: &[&str]) -> TestCase {
+    TestCase {
+        name: format!("Nouns: {input}"),
+        direction,
+        input: input.into(),
+        expect: expect.iter().map(|e| e.to_string()).collect(),
+        expect_not: vec![],
+        weight_checks: vec![],
+    }
+}
+
+fn suite() -> TestSuite {
+    TestSuite {
+        name: "nouns".into(),
+        cases: vec![
+            case(Direction::Generate, "guolli+N+Sg+Nom", &["guolli"]),
+            case(Direction::Analyze, "guolli", &["guolli+N+Sg+Nom"]),
+            case(Direction::Analyze, "guoli", &["guolli+N+Sg+Gen"]),
+        ],
+    }
+}
+
+#[tokio::test]
+async fn replay_gives_the_recorded_results() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let path = dir.path().join("fixtures").join("nouns.json");
+    let suites = vec![suite()];
+
+    let recorder = Recorder::open(&path)?;
+    let recording = RecordingBackend::new(FakeBackend, recorder.clone(), "fake");
+    let recorded = run_suites(&recording, &suites, false).await;
+    recorder.save()?;
+
+    let fixture = Fixture::load(&path)?;
+    let lookups = &fixture.backends["fake"];
+    assert_eq!(lookups.analyze["guolli"], vec![LookupOutput::new("guolli+N+Sg+Nom", Some(0.5))]);
+    // Inputs without results are recorded too, so replaying them does not fail
+    assert_eq!(lookups.analyze["guoli"], vec![]);
+    assert_eq!(lookups.generate["guolli+N+Sg+Nom"], vec![LookupOutput::from("guolli")]);
+
+    let replayed = run_suites(&ReplayBackend::new(&path, &fixture, "fake"), &suites, false).await;
+    let results = |summary: &Summary| -> Vec<(String, Vec<LookupOutput>, bool)> {
+        summary
+            .cases
+            .iter()
+            .map(|c| (c.input.clone(), c.outputs.clone(), c.passed))
+            .collect()
+    };
+    assert_eq!(results(&replayed), results(&recorded));
+    assert_eq!((replayed.passed, replayed.failed), (2, 1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn inputs_missing_from_the_fixture_fail_the_batch() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let path = dir.path().join("nouns.json");
+    let recorder = Recorder::open(&path)?;
+    let recording = RecordingBackend::new(FakeBackend, recorder.clone(), "fake");
+    recording.analyze_batch(&["guolli".into(), "slow".into()]).await?;
+    recorder.save()?;
+
+    // Failed lookups are not recorded
+    let replay = ReplayBackend::new(&path, &Fixture::load(&path)?, "fake");
+    let error = replay
+        .analyze_batch(&["guolli".into(), "slow".into()])
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("slow"), "{error}");
+    assert!(replay.generate_batch(&["guolli+N+Sg+Nom".into()]).await.is_err());
+
+    // Recording again adds to the fixture
+    let recorder = Recorder::open(&path)?;
+    let recording = RecordingBackend::new(FakeBackend, recorder.clone(), "fake");
+    recording.generate_batch(&["guolli+N+Sg+Nom".into()]).await?;
+    recorder.save()?;
+    let replay = ReplayBackend::new(&path, &Fixture::load(&path)?, "fake");
+    assert_eq!(replay.analyze_batch(&["guolli".into()]).await?.len(), 1);
+    assert_eq!(
+        replay.generate_batch(&["guolli+N+Sg+Nom".into()]).await?,
+        vec![Ok(vec![LookupOutput::from("guolli")])]
+    );
+    Ok(())
+}
+
+/// Analyses every input as a verb
+struct VerbBackend;
+
+#[async_trait]
+impl Backend for VerbBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|input| Ok(vec![format!("{input}+V").into()])).collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|_| Ok(vec![])).collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn backends_are_recorded_apart() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let path = dir.path().join("nouns.json");
+    let recorder = Recorder::open(&path)?;
+    let inputs = vec!["guolli".to_string()];
+    RecordingBackend::new(FakeBackend, recorder.clone(), "nouns").analyze_batch(&inputs).await?;
+    RecordingBackend::new(VerbBackend, recorder.clone(), "verbs").analyze_batch(&inputs).await?;
+    // Nothing is written until the run is over
+    assert!(!path.exists());
+    recorder.save()?;
+
+    let fixture = Fixture::load(&path)?;
+    let nouns = ReplayBackend::new(&path, &fixture, "nouns").analyze_batch(&inputs).await?;
+    assert_eq!(nouns, vec![Ok(vec![LookupOutput::new("guolli+N+Sg+Nom", Some(0.5))])]);
+    let verbs = ReplayBackend::new(&path, &fixture, "verbs").analyze_batch(&inputs).await?;
+    assert_eq!(verbs, vec![Ok(vec![LookupOutput::from("guolli+V")])]);
+    assert!(ReplayBackend::new(&path, &fixture, "adjectives").analyze_batch(&inputs).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn fixture_recorded_in_one_directory_replays_in_another() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let path = dir.path().join("nouns.json");
+    let key = |project: &str, fst: &str| -> Result<String> {
+        let project = dir.path().join(project);
+        std::fs::create_dir_all(&project)?;
+        let gen_fst = project.join("generator.att");
+        std::fs::write(&gen_fst, fst)?;
+        let tool = project.join("bin").join("hfst-lookup");
+        Ok(fixture_key(
+            "hfst",
+            &tool.to_string_lossy(),
+            &LookupArgs::default(),
+            None,
+            &[&gen_fst.to_string_lossy()],
+        ))
+    };
+
+    let recorder = Recorder::open(&path)?;
+    let recording = RecordingBackend::new(FakeBackend, recorder.clone(), key("checkout", "0\t1\ta\tb\n1\n")?);
+    let recorded = recording.analyze_batch(&["guolli".into()]).await?;
+    recorder.save()?;
+
+    // The same transducer somewhere else has the same key, a changed one does not
+    let fixture = Fixture::load(&path)?;
+    let elsewhere = ReplayBackend::new(&path, &fixture, &key("elsewhere", "0\t1\ta\tb\n1\n")?);
+    assert_eq!(elsewhere.analyze_batch(&["guolli".into()]).await?, recorded);
+    let changed = ReplayBackend::new(&path, &fixture, &key("changed", "0\t1\ta\tc\n1\n")?);
+    assert!(changed.analyze_batch(&["guolli".into()]).await.is_err());
+    Ok(())
+}
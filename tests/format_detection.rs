@@ -0,0 +1,120 @@
+use anyhow::Result;
+use morph_test2::backend::Backend;
+use morph_test2::format::check_fsts;
+use morph_test2::native::NativeBackend;
+use morph_test2::spec::{BackendChoice, determine_hfst_lookup_tool, load_specs};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, Once};
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+/// The start of an HFST3 file of the given type
+fn hfst3(kind: &str) -> Vec<u8> {
+    let props = format!("version\u{0}3.3\u{0}type\u{0}{kind}\u{0}");
+    let mut data = b"HFST\0".to_vec();
+    data.extend((props.len() as u16).to_le_bytes());
+    data.push(0);
+    data.extend(props.as_bytes());
+    data
+}
+
+fn write(dir: &Path, name: &str, data: &[u8]) -> Result<String> {
+    let path = dir.join(name);
+    fs::write(&path, data)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn lookup_tool_follows_the_header() -> Result<()> {
+    let dir = tempdir()?;
+    let optimised = write(dir.path(), "analyser-gt-norm", &hfst3("HFST_OLW"))?;
+    let tropical = write(dir.path(), "generator.hfstol", &hfst3("TROPICAL_OPENFST_TYPE"))?;
+    assert_eq!(determine_hfst_lookup_tool(&optimised, None), "hfst-optimised-lookup");
+    // The header wins over the extension
+    assert_eq!(determine_hfst_lookup_tool(&tropical, None), "hfst-lookup");
+    // Without readable files the extension decides, as before
+    assert_eq!(determine_hfst_lookup_tool("missing.hfst", None), "hfst-lookup");
+    Ok(())
+}
+
+/// Collects what is logged while it is the subscriber
+#[derive(Clone, Default)]
+struct Log(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for Log {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn lookup_tool_for_a_transducer_without_hfst_header_is_a_warned_guess() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let lttoolbox = write(dir.path(), "eng.automorf.bin", b"LTTB\0\0\0\x01")?;
+    let headerless = write(dir.path(), "analyser.hfstol", b"\0\0\0\x01")?;
+    let log = Log::default();
+    let writer = log.clone();
+    let subscriber = tracing_subscriber::fmt().with_writer(move || writer.clone()).finish();
+    tracing::subscriber::with_default(subscriber, || {
+        assert_eq!(determine_hfst_lookup_tool(&headerless, None), "hfst-optimised-lookup");
+        assert!(log.0.lock().unwrap().is_empty());
+        assert_eq!(determine_hfst_lookup_tool(&lttoolbox, None), "hfst-optimised-lookup");
+    });
+    let logged = String::from_utf8(log.0.lock().unwrap().clone())?;
+    assert!(logged.contains("eng.automorf.bin"), "{logged}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn foma_and_att_transducers_in_the_hfst_section_are_read_natively() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let foma = write(dir.path(), "generator.bin", &[0x1f, 0x8b, 8, 0, 0, 0, 0, 0])?;
+    let file = dir.path().join("suite.yaml");
+    let yaml = "Config:\n  hfst:\n    Gen: GEN\nTests:\n  Nouns:\n    foo+V: bar\n";
+    fs::write(&file, yaml.replace("GEN", &foma))?;
+    let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst)?;
+    assert!(matches!(swc[0].backend, BackendChoice::Foma));
+    assert!(swc[0].native);
+
+    // AT&T text is told by its content as well as by its extension
+    let att = write(dir.path(), "generator", &fs::read(fixture("generator.att"))?)?;
+    fs::write(&file, yaml.replace("GEN", &att))?;
+    let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst)?;
+    assert!(swc[0].native);
+    let backend = NativeBackend::new(Some(&att), None)?;
+    let outcomes = backend.generate_batch(&["foo+V".to_string()]).await?;
+    let outputs: Vec<String> = outcomes[0].as_ref().unwrap().iter().map(|o| o.output.clone()).collect();
+    assert_eq!(outputs, ["bar", "baz"]);
+    Ok(())
+}
+
+#[test]
+fn unknown_formats_are_rejected() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let unknown = write(dir.path(), "generator", &[0x02, 0x00, 0x7f, 0x00])?;
+    let error = check_fsts("hfst-optimised-lookup", [unknown.as_str()])
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("not known") && error.contains("generator"), "{error}");
+    // Old HFST files have no header, but are named like HFST files
+    let headerless = write(dir.path(), "generator.hfstol", &[0x02, 0x00, 0x7f, 0x00])?;
+    check_fsts("hfst-optimised-lookup", [headerless.as_str()])?;
+    Ok(())
+}
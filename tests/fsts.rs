@@ -0,0 +1,139 @@
+use anyhow::Result;
+use morph_test2::engine::{check_relation, run_suites_separately};
+use morph_test2::native::NativeBackend;
+use morph_test2::spec::{BackendChoice, SuiteWithConfig, load_specs};
+use morph_test2::types::Direction;
+use std::fs;
+use std::path::Path;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+/// Like the analyser fixture for "bar" and "baz", but also analyses "bar" as a noun
+const DESC_ANALYSER: &str = "0\t1\tb\tf\t0.0\n1\t2\ta\to\t0.0\n2\t3\tr\to\t0.0\n3\t4\t@0@\t+V\t0.0\n\
+                             2\t5\tz\to\t0.0\n5\t6\t@0@\t+V\t0.0\n3\t7\t@0@\t+N\t0.0\n4\t0.0\n6\t0.0\n7\t0.0\n";
+
+const SPEC: &str = "\
+Config:
+  Fsts:
+    norm:
+      Gen: generator.att
+      Morph: analyser.att
+    desc:
+      Gen: generator.att
+      Morph: analyser-desc.att
+  Relations:
+    - desc ⊇ norm
+    - desc <= norm
+Tests:
+  Verbs:
+    foo+V: [bar, baz]
+  Nouns [desc]:
+    foo+N: bar
+  Either:
+    foo+V:
+      norm: bar
+      desc: [bar, baz]
+";
+
+fn spec_dir(spec: &str) -> Result<tempfile::TempDir> {
+    let dir = tempdir()?;
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::copy(fixtures.join("generator.att"), dir.path().join("generator.att"))?;
+    fs::copy(fixtures.join("analyser.att"), dir.path().join("analyser.att"))?;
+    fs::write(dir.path().join("analyser-desc.att"), DESC_ANALYSER)?;
+    fs::write(dir.path().join("suite.yaml"), spec)?;
+    Ok(dir)
+}
+
+fn load(dir: &Path) -> Result<Vec<SuiteWithConfig>> {
+    load_specs(&[dir.join("suite.yaml")], BackendChoice::Auto)
+}
+
+#[test]
+fn each_named_transducer_is_a_suite() -> Result<()> {
+    init();
+    let dir = spec_dir(SPEC)?;
+    let suites = load(dir.path())?;
+    let names: Vec<&str> = suites.iter().map(|s| s.suite.name.as_str()).collect();
+    assert_eq!(names, ["suite.yaml (norm)", "suite.yaml (desc)"]);
+    assert!(suites[1].morph_fst.as_deref().is_some_and(|m| m.ends_with("analyser-desc.att")));
+    assert!(suites.iter().all(|s| s.native));
+
+    let generated = |swc: &SuiteWithConfig| -> Vec<(String, Vec<String>)> {
+        swc.suite
+            .cases
+            .iter()
+            .filter(|c| c.direction == Direction::Generate)
+            .map(|c| (c.name.clone(), c.expect.clone()))
+            .collect()
+    };
+    assert_eq!(
+        generated(&suites[0]),
+        [
+            ("Verbs (norm): foo+V".to_string(), vec!["bar".to_string(), "baz".to_string()]),
+            ("Either (norm): foo+V".to_string(), vec!["bar".to_string()]),
+        ]
+    );
+    assert_eq!(generated(&suites[1]).len(), 3);
+
+    let relations: Vec<(&str, &str, &str)> = suites[0]
+        .relations
+        .iter()
+        .map(|r| (r.name.as_str(), r.superset.as_str(), r.subset.as_str()))
+        .collect();
+    assert_eq!(
+        relations,
+        [
+            ("suite.yaml (desc ⊇ norm)", "suite.yaml (desc)", "suite.yaml (norm)"),
+            ("suite.yaml (desc <= norm)", "suite.yaml (norm)", "suite.yaml (desc)"),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn relations_compare_the_analyses_of_each_surface_form() -> Result<()> {
+    init();
+    let dir = spec_dir(SPEC)?;
+    let suites = load(dir.path())?;
+    let mut summaries = Vec::new();
+    for swc in &suites {
+        let backend = NativeBackend::new(Some(&swc.gen_fst), swc.morph_fst.as_deref())?;
+        summaries.extend(run_suites_separately(&backend, std::slice::from_ref(&swc.suite), false).await);
+    }
+
+    let holds = check_relation(&suites[0].relations[0], &summaries[1], &summaries[0]);
+    assert_eq!((holds.passed, holds.failed), (2, 0));
+    // The normative analyser lacks the noun reading of "bar"
+    let fails = check_relation(&suites[0].relations[1], &summaries[0], &summaries[1]);
+    let failed: Vec<&str> = fails.cases.iter().filter(|c| !c.passed).map(|c| c.name.as_str()).collect();
+    assert_eq!(failed, ["desc <= norm: bar"]);
+    Ok(())
+}
+
+#[test]
+fn transducers_must_be_named_in_fsts() -> Result<()> {
+    init();
+    let error = |spec: &str| -> Result<String> {
+        let dir = spec_dir(spec)?;
+        Ok(format!("{:#}", load(dir.path()).unwrap_err()))
+    };
+    let unknown = error(&SPEC.replace("Nouns [desc]", "Nouns [dsec]"))?;
+    assert!(unknown.contains("dsec") && unknown.contains("norm, desc"), "{unknown}");
+    let relation = error(&SPEC.replace("desc ⊇ norm", "desc > norm"))?;
+    assert!(relation.contains("desc > norm"), "{relation}");
+
+    // Without Fsts, brackets are part of the group name as before
+    let plain = "Config:\n  hfst:\n    Gen: generator.att\nTests:\n  Nouns [plural]:\n    foo+V: bar\n";
+    let dir = spec_dir(plain)?;
+    assert_eq!(load(dir.path())?[0].suite.cases[0].name, "Nouns [plural]: foo+V");
+    let per_fst = error(&plain.replace("foo+V: bar", "foo+V:\n      norm: bar"))?;
+    assert!(per_fst.contains("Fsts"), "{per_fst}");
+    Ok(())
+}
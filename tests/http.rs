@@ -0,0 +1,130 @@
+use anyhow::Result;
+use morph_test2::backend::Backend;
+use morph_test2::engine::run_suites;
+use morph_test2::http::HttpBackend;
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::LookupOutput;
+use serde_json::{Value, json};
+use std::fs;
+use std::sync::Once;
+use std::time::Duration;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+fn answer(direction: &str, input: &str) -> Value {
+    match (direction, input) {
+        ("analyze", "guolli") => json!(["guolli+N+Sg+Nom", {"output": "guolli+N+Sg+Gen", "weight": 2.5}]),
+        ("analyze", "guoli") => json!(["guolli+N+Sg+Gen"]),
+        ("generate", "guolli+N+Sg+Nom") => json!(["guolli"]),
+        ("generate", "guolli+N+Sg+Gen") => json!(["guoli"]),
+        (_, "boom") => json!({"error": "cannot look up boom"}),
+        _ => json!([]),
+    }
+}
+
+/// Serves lookups like a morphology service on a free local port, and
+/// returns its URL. The server lives until the test process ends.
+fn start_service() -> String {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/lookup", server.server_addr().to_ip().unwrap());
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let body: Value = serde_json::from_reader(request.as_reader()).unwrap();
+            let direction = body["direction"].as_str().unwrap_or_default().to_string();
+            let results: Vec<Value> = body["inputs"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|input| answer(&direction, input.as_str().unwrap()))
+                .collect();
+            let response = tiny_http::Response::from_string(json!({ "results": results }).to_string())
+                .with_header("Content-Type: application/json".parse::<tiny_http::Header>().unwrap());
+            let _ = request.respond(response);
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn service_answers_both_directions() -> Result<()> {
+    init();
+    let mut backend = HttpBackend::new(&start_service(), Some(Duration::from_secs(5)));
+    backend.validate().await?;
+    // Batches are split over several requests, and the answers kept in order
+    backend.batch_size = 2;
+
+    let inputs: Vec<String> = ["guolli", "boom", "guoli"].map(String::from).into();
+    let outcomes = backend.analyze_batch(&inputs).await?;
+    assert_eq!(
+        outcomes[0],
+        Ok(vec![
+            LookupOutput::from("guolli+N+Sg+Nom"),
+            LookupOutput::new("guolli+N+Sg+Gen", Some(2.5))
+        ])
+    );
+    assert_eq!(outcomes[1], Err("cannot look up boom".to_string()));
+    assert_eq!(outcomes[2], Ok(vec![LookupOutput::from("guolli+N+Sg+Gen")]));
+
+    let outcomes = backend.generate_batch(&["guolli+N+Sg+Nom".to_string()]).await?;
+    assert_eq!(outcomes, vec![Ok(vec![LookupOutput::from("guolli")])]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unreachable_service_fails_validation() {
+    init();
+    // Nothing listens on the port of a server that has been dropped
+    let addr = tiny_http::Server::http("127.0.0.1:0")
+        .unwrap()
+        .server_addr()
+        .to_ip()
+        .unwrap();
+    let backend = HttpBackend::new(&format!("http://{addr}/lookup"), Some(Duration::from_secs(5)));
+    assert!(backend.validate().await.is_err());
+    let outcomes = backend.analyze_batch(&["guolli".to_string()]).await.unwrap();
+    assert!(outcomes[0].is_err());
+}
+
+#[tokio::test]
+async fn yaml_suites_run_against_the_service() -> Result<()> {
+    init();
+    let url = start_service();
+    let dir = tempdir()?;
+    let file = dir.path().join("service.yaml");
+    fs::write(
+        &file,
+        format!(
+            r#"
+Config:
+  Http:
+    Url: {url}
+Tests:
+  Nouns:
+    guolli+N+Sg+Nom: guolli
+    guolli+N+Sg+Gen: [guoli, guolli]
+"#
+        ),
+    )?;
+    // The default section on the command line is hfst, which the suite does not have
+    let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst)?;
+    assert!(matches!(swc[0].backend, BackendChoice::Http));
+    assert_eq!(swc[0].lookup_cmd, url);
+
+    let backend = HttpBackend::new(&swc[0].lookup_cmd, swc[0].timeout);
+    let summary = run_suites(&backend, std::slice::from_ref(&swc[0].suite), false).await;
+    let failed: Vec<&str> = summary
+        .cases
+        .iter()
+        .filter(|c| !c.passed)
+        .map(|c| c.input.as_str())
+        .collect();
+    // The service does not generate "guolli" for the genitive
+    assert_eq!(failed, vec!["guolli+N+Sg+Gen"]);
+    assert_eq!(summary.cases.len(), 4);
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use morph_test2::backend::Backend;
+use morph_test2::engine::run_suites;
+use morph_test2::spec::{BackendChoice, convert_lexc_to_suites, parse_lexc_test_data};
+use morph_test2::types::{Direction, LookupOutcome, TestCase};
+use std::fs;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+const LEXC: &str = "\
+LEXICON Nouns
+!!€gt-norm: guolli # Test data
+!!€ guolli: guolli+N+Sg+Nom
+!!€ guolli: guolli+N+Sg+Gen ! homonymous
+!!€ guoli: guolli+N+Sg+Gen
+!!$ guolii: guolli+N+Sg+Gen
+!!$ guolli: guolli+N+Ess
+!!€ guolli: guolli+N+Sg+Nom
+guolli:guolli N ;
+";
+
+fn case<'a>(cases: &'a [TestCase], direction: Direction, input: &str) -> &'a TestCase {
+    cases
+        .iter()
+        .find(|case| case.direction == direction && case.input == input)
+        .unwrap_or_else(|| panic!("no {direction:?} case for {input} in {cases:?}"))
+}
+
+#[test]
+fn every_test_line_is_kept() -> Result<()> {
+    let sets = parse_lexc_test_data(LEXC)?;
+    assert_eq!(sets.len(), 1);
+    let tests = &sets[0].tests;
+    assert_eq!(tests.len(), 6);
+    assert_eq!((tests[1].surface.as_str(), tests[1].analysis.as_str()), ("guolli", "guolli+N+Sg+Gen"));
+    assert!(tests[3].negative && !tests[2].negative);
+    assert_eq!(tests[5].line, 8);
+    Ok(())
+}
+
+#[test]
+fn analyses_are_combined_and_negatives_checked() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    fs::write(dir.path().join("generator-gt-norm.hfst"), "")?;
+    let file = dir.path().join("nouns.lexc");
+    let suites = convert_lexc_to_suites(parse_lexc_test_data(LEXC)?, &file, BackendChoice::Hfst)?;
+    let cases = &suites[0].suite.cases;
+
+    // Both analyses of guolli, and the duplicate line only once
+    let guolli = case(cases, Direction::Analyze, "guolli");
+    assert_eq!(guolli.expect, ["guolli+N+Sg+Nom", "guolli+N+Sg+Gen"]);
+    assert_eq!(guolli.expect_not, ["guolli+N+Ess"]);
+    assert_eq!(case(cases, Direction::Generate, "guolli+N+Sg+Nom").expect, ["guolli"]);
+
+    let genitive = case(cases, Direction::Generate, "guolli+N+Sg+Gen");
+    assert_eq!(genitive.expect, ["guolli", "guoli"]);
+    assert_eq!(genitive.expect_not, ["guolii"]);
+    // A form only given as wrong must not get the analyses it was given with
+    let wrong = case(cases, Direction::Analyze, "guolii");
+    assert!(wrong.expect.is_empty());
+    assert_eq!(wrong.expect_not, ["guolli+N+Sg+Gen"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn forms_only_given_as_wrong_are_checked_for_nothing_else() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    fs::write(dir.path().join("generator-gt-norm.hfst"), "")?;
+    let file = dir.path().join("nouns.lexc");
+    let lexc = "!!€gt-norm: Nouns\n!!$ guolii: guolli+N+Sg+Gen\n";
+    let suites = convert_lexc_to_suites(parse_lexc_test_data(lexc)?, &file, BackendChoice::Hfst)?;
+
+    // Outputs other than the wrong ones, such as a typo tag, are left alone
+    let right = FixedBackend("guolli+Err/Orth", "guolli");
+    let summary = run_suites(&right, std::slice::from_ref(&suites[0].suite), false).await;
+    assert_eq!((summary.passed, summary.failed), (2, 0));
+    let wrong = FixedBackend("guolli+N+Sg+Gen", "guolii");
+    let summary = run_suites(&wrong, std::slice::from_ref(&suites[0].suite), false).await;
+    assert_eq!((summary.passed, summary.failed), (0, 2));
+    Ok(())
+}
+
+/// Analyses every input as the first form and generates the second
+struct FixedBackend(&'static str, &'static str);
+
+#[async_trait]
+impl Backend for FixedBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|_| Ok(vec![self.0.into()])).collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|_| Ok(vec![self.1.into()])).collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn a_pair_both_right_and_wrong_is_an_error() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    fs::write(dir.path().join("generator-gt-norm.hfst"), "")?;
+    let file = dir.path().join("nouns.lexc");
+    let lexc = format!("{LEXC}!!$ guoli: guolli+N+Sg+Gen\n");
+    let error = convert_lexc_to_suites(parse_lexc_test_data(&lexc)?, &file, BackendChoice::Hfst).unwrap_err();
+    let error = error.to_string();
+    assert!(error.contains("line 10") && error.contains("line 5"), "{error}");
+    assert!(error.contains("guoli: guolli+N+Sg+Gen"), "{error}");
+    Ok(())
+}
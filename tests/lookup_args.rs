@@ -0,0 +1,126 @@
+#![cfg(unix)]
+
+use anyhow::Result;
+use morph_test2::backend::{Backend, DEFAULT_TIMEOUT, ExternalBackend, LookupArgs};
+use morph_test2::pool::PooledBackend;
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::LookupOutput;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+// Answers every input with the arguments it was started with
+const ECHO_ARGS: &str = r#"#!/bin/sh
+while IFS= read -r line; do
+  printf '%s\t%s\n\n' "$line" "$*"
+done
+"#;
+
+fn write_tool(dir: &Path) -> Result<String> {
+    let tool = dir.join("echo-args");
+    fs::write(&tool, ECHO_ARGS)?;
+    fs::set_permissions(&tool, fs::Permissions::from_mode(0o755))?;
+    Ok(tool.to_string_lossy().into_owned())
+}
+
+fn outputs(outcomes: Vec<Result<Vec<LookupOutput>, String>>) -> Vec<String> {
+    outcomes
+        .into_iter()
+        .flat_map(|outcome| outcome.unwrap())
+        .map(|o| o.output)
+        .collect()
+}
+
+#[test]
+fn app_and_args_are_read_from_config() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let file = dir.path().join("suite.yaml");
+    let yaml = r#"
+Config:
+  hfst:
+    Gen: generator.hfstol
+    Morph: analyser.hfstol
+    App: /opt/hfst/bin/hfst-lookup
+    Args:
+      Gen: -q --time-cutoff=2
+      Morph: [-q, -b, "0.5"]
+Tests:
+  Nouns:
+    guolli+N: guolli
+"#;
+    fs::write(&file, yaml)?;
+    let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Auto)?;
+    assert_eq!(swc[0].lookup_cmd, "/opt/hfst/bin/hfst-lookup");
+    assert_eq!(swc[0].app.as_deref(), Some("/opt/hfst/bin/hfst-lookup"));
+    assert_eq!(
+        swc[0].lookup_args,
+        LookupArgs {
+            analyze: vec!["-q".into(), "-b".into(), "0.5".into()],
+            generate: vec!["-q".into(), "--time-cutoff=2".into()],
+        }
+    );
+
+    // The same arguments for both directions
+    fs::write(
+        &file,
+        yaml.replace(
+            "    Args:\n      Gen: -q --time-cutoff=2\n      Morph: [-q, -b, \"0.5\"]\n",
+            "    Args: -x\n",
+        ),
+    )?;
+    let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Auto)?;
+    assert_eq!(swc[0].lookup_args.analyze, vec!["-x"]);
+    assert_eq!(swc[0].lookup_args.generate, vec!["-x"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn args_are_passed_before_the_transducer() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let tool = write_tool(dir.path())?;
+    let args = LookupArgs {
+        analyze: vec!["-q".into(), "-x".into()],
+        generate: vec!["--time-cutoff=2".into()],
+    };
+    let inputs = vec!["guolli".to_string()];
+
+    let external = ExternalBackend {
+        lookup_cmd: tool.clone(),
+        args: args.clone(),
+        generator_fst: Some("generator.hfstol".into()),
+        analyzer_fst: Some("analyser.hfstol".into()),
+        timeout: Some(DEFAULT_TIMEOUT),
+        quiet: true,
+    };
+    assert_eq!(outputs(external.analyze_batch(&inputs).await?), ["-q -x analyser.hfstol"]);
+    assert_eq!(
+        outputs(external.generate_batch(&inputs).await?),
+        ["--time-cutoff=2 generator.hfstol"]
+    );
+
+    let pooled = PooledBackend::new(
+        tool,
+        args,
+        Some("analyser.hfstol".into()),
+        Some("generator.hfstol".into()),
+        true,
+        DEFAULT_TIMEOUT,
+    )
+    .await?;
+    assert_eq!(outputs(pooled.analyze_batch(&inputs).await?), ["-q -x analyser.hfstol"]);
+    assert_eq!(
+        outputs(pooled.generate_batch(&inputs).await?),
+        ["--time-cutoff=2 generator.hfstol"]
+    );
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use morph_test2::backend::{Backend, LookupArgs};
+use morph_test2::cache::CachedBackend;
+use morph_test2::types::LookupOutcome;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+/// Generates "<input>+N" and remembers which inputs it was asked for; "hang"
+/// fails as if it had timed out
+#[derive(Default)]
+struct CountingBackend {
+    seen: Mutex<Vec<String>>,
+}
+
+impl CountingBackend {
+    fn seen(&self) -> Vec<String> {
+        self.seen.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Backend for CountingBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|_| Ok(vec![])).collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        self.seen.lock().unwrap().extend(inputs.iter().cloned());
+        Ok(inputs
+            .iter()
+            .map(|input| match input.as_str() {
+                "hang" => Err(format!("no answer for '{input}'")),
+                _ => Ok(vec![format!("{input}+N").into()]),
+            })
+            .collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn cached(dir: &Path, fst: &Path) -> Result<CachedBackend<CountingBackend>> {
+    CachedBackend::new(
+        CountingBackend::default(),
+        dir,
+        "fake-lookup",
+        &LookupArgs::default(),
+        None,
+        Some(fst.to_str().unwrap()),
+    )
+}
+
+fn inputs(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+#[tokio::test]
+async fn results_are_reused_across_runs() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let cache_dir = dir.path().join("cache");
+    let fst = dir.path().join("generator.hfstol");
+    std::fs::write(&fst, b"first build")?;
+
+    let first = cached(&cache_dir, &fst)?;
+    let outcomes = first.generate_batch(&inputs(&["guolli", "hang", "beana"])).await?;
+    assert_eq!(outcomes[0], Ok(vec!["guolli+N".into()]));
+    assert!(outcomes[1].is_err());
+    assert_eq!(first.inner.seen(), inputs(&["guolli", "hang", "beana"]));
+
+    // A new run only looks up what is new, and what failed last time
+    let second = cached(&cache_dir, &fst)?;
+    let outcomes = second.generate_batch(&inputs(&["beana", "guolli", "hang", "nuvttat"])).await?;
+    assert_eq!(outcomes[0], Ok(vec!["beana+N".into()]));
+    assert_eq!(outcomes[1], Ok(vec!["guolli+N".into()]));
+    assert!(outcomes[2].is_err());
+    assert_eq!(outcomes[3], Ok(vec!["nuvttat+N".into()]));
+    assert_eq!(second.inner.seen(), inputs(&["hang", "nuvttat"]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn rebuilt_fst_is_looked_up_again() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let fst = dir.path().join("generator.hfstol");
+    std::fs::write(&fst, b"first build")?;
+    cached(dir.path(), &fst)?.generate_batch(&inputs(&["guolli"])).await?;
+
+    std::fs::write(&fst, b"second build")?;
+    let rebuilt = cached(dir.path(), &fst)?;
+    rebuilt.generate_batch(&inputs(&["guolli"])).await?;
+    assert_eq!(rebuilt.inner.seen(), inputs(&["guolli"]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn directions_and_commands_are_kept_apart() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let fst = dir.path().join("both.hfstol");
+    std::fs::write(&fst, b"network")?;
+    let fst = fst.to_str().unwrap();
+
+    let backend = CachedBackend::new(CountingBackend::default(), dir.path(), "fake-lookup", &LookupArgs::default(), Some(fst), Some(fst))?;
+    backend.generate_batch(&inputs(&["guolli"])).await?;
+    assert_eq!(backend.analyze_batch(&inputs(&["guolli"])).await?, vec![Ok(vec![])]);
+
+    let other_tool = CachedBackend::new(CountingBackend::default(), dir.path(), "other-lookup", &LookupArgs::default(), None, Some(fst))?;
+    other_tool.generate_batch(&inputs(&["guolli"])).await?;
+    assert_eq!(other_tool.inner.seen(), inputs(&["guolli"]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn lookup_arguments_are_kept_apart() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let fst = dir.path().join("generator.hfstol");
+    std::fs::write(&fst, b"network")?;
+    let fst = fst.to_str().unwrap();
+    cached(dir.path(), Path::new(fst))?.generate_batch(&inputs(&["guolli"])).await?;
+
+    let args = LookupArgs {
+        analyze: vec![],
+        generate: vec!["-b".into(), "0.5".into()],
+    };
+    let with_args = CachedBackend::new(CountingBackend::default(), dir.path(), "fake-lookup", &args, None, Some(fst))?;
+    with_args.generate_batch(&inputs(&["guolli"])).await?;
+    assert_eq!(with_args.inner.seen(), inputs(&["guolli"]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn groups_sharing_a_cache_file_keep_each_others_results() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let fst = dir.path().join("generator.hfstol");
+    std::fs::write(&fst, b"network")?;
+
+    // Both are opened before either saves, as groups of one run are
+    let first = cached(dir.path(), &fst)?;
+    let second = cached(dir.path(), &fst)?;
+    let (guolli, beana) = (inputs(&["guolli"]), inputs(&["beana"]));
+    let (a, b) = tokio::join!(first.generate_batch(&guolli), second.generate_batch(&beana));
+    a?;
+    b?;
+
+    let next_run = cached(dir.path(), &fst)?;
+    next_run.generate_batch(&inputs(&["guolli", "beana"])).await?;
+    assert!(next_run.inner.seen().is_empty());
+    Ok(())
+}
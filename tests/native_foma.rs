@@ -0,0 +1,116 @@
+use anyhow::Result;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use morph_test2::backend::Backend;
+use morph_test2::engine::run_suites;
+use morph_test2::foma::read_foma;
+use morph_test2::fst::Apply;
+use morph_test2::native::NativeBackend;
+use morph_test2::types::*;
+use std::io::Write;
+use std::sync::Once;
+
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+fn outputs(results: Vec<LookupOutput>) -> Vec<String> {
+    results.into_iter().map(|o| o.output).collect()
+}
+
+// Upper side cat+N+Sg / cat+N+Pl, lower side cat / cats,
+// plus an identity arc passing any single unknown symbol through
+const NET: &str = "##foma-net 1.0##
+##props##
+2 8 7 8 2 1 0 0 0 0 0 2 test
+##sigma##
+0 @_EPSILON_SYMBOL_@
+2 @_IDENTITY_SYMBOL_@
+3 +N
+4 +Pl
+5 +Sg
+6 a
+7 c
+8 s
+9 t
+##states##
+0 7 1 0
+2 6
+1 6 2 0
+2 9 3 0
+3 3 0 4 0
+4 5 0 5 0
+4 8 5
+5 -1 -1 1
+6 -1 -1 1
+-1 -1 -1 -1 -1
+##end##
+";
+
+fn gzip(text: &str) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(text.as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn applies_foma_networks_up_and_down() -> Result<()> {
+    init();
+    let fst = read_foma(&gzip(NET))?;
+    assert_eq!(outputs(fst.lookup("cats", Apply::Up)), ["cat+N+Pl"]);
+    assert_eq!(outputs(fst.lookup("cat", Apply::Up)), ["cat+N+Sg"]);
+    assert_eq!(outputs(fst.lookup("cat+N+Pl", Apply::Down)), ["cats"]);
+    // Identity only matches symbols outside the alphabet
+    assert_eq!(outputs(fst.lookup("x", Apply::Up)), ["x"]);
+    assert!(fst.lookup("a", Apply::Up).is_empty());
+    Ok(())
+}
+
+#[test]
+fn rejects_files_that_are_not_foma_networks() {
+    init();
+    assert!(read_foma(b"not gzip").is_err());
+    assert!(read_foma(&gzip("##something-else##\n")).is_err());
+    let bad = NET.replace("4 8 5\n", "4 8 x\n");
+    let err = read_foma(&gzip(&bad)).unwrap_err();
+    assert!(err.to_string().contains("4 8 x"));
+}
+
+#[tokio::test]
+async fn one_network_serves_both_directions() -> Result<()> {
+    init();
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("analyser.foma");
+    std::fs::write(&path, gzip(NET))?;
+
+    // Analysis applies the network up like flookup, generation applies it down
+    let backend = NativeBackend::new(None, Some(path.to_str().unwrap()))?;
+    assert_eq!(backend.analyze_batch(&["cats".into()]).await?, vec![Ok(vec!["cat+N+Pl".into()])]);
+    assert_eq!(backend.generate_batch(&["cat+N+Sg".into()]).await?, vec![Ok(vec!["cat".into()])]);
+
+    let suite = TestSuite {
+        name: "foma".into(),
+        cases: vec![
+            TestCase {
+                name: "Nouns: cat+N+Pl".into(),
+                direction: Direction::Generate,
+                input: "cat+N+Pl".into(),
+                expect: vec!["cats".into()],
+                expect_not: vec!["cat".into()],
+                weight_checks: vec![],
+            },
+            TestCase {
+                name: "Nouns: cat".into(),
+                direction: Direction::Analyze,
+                input: "cat".into(),
+                expect: vec!["cat+N+Sg".into()],
+                expect_not: vec![],
+                weight_checks: vec![],
+            },
+        ],
+    };
+    let summary = run_suites(&backend, &[suite], false).await;
+    assert_eq!(summary.passed, 2);
+    Ok(())
+}
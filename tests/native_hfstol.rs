@@ -0,0 +1,216 @@
+use anyhow::Result;
+use morph_test2::backend::Backend;
+use morph_test2::engine::run_suites;
+use morph_test2::hfstol::HfstolTransducer;
+use morph_test2::native::{NativeBackend, Transducer};
+use morph_test2::types::*;
+use std::sync::{Arc, Once};
+
+const TARGET_TABLE_START: u32 = 1 << 31;
+const NO_SYMBOL: u16 = u16::MAX;
+const NO_TABLE_INDEX: u32 = u32::MAX;
+
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+fn outputs(results: Vec<LookupOutput>) -> Vec<String> {
+    results.into_iter().map(|o| o.output).collect()
+}
+
+struct State {
+    is_final: bool,
+    arcs: Vec<(u16, u16, usize)>, // input, output, target state
+}
+
+/// Write an unweighted optimized-lookup transducer. Every state gets its own
+/// row in the index table, which is valid (if not compact) hfstol.
+fn build_hfstol(symbols: &[&str], input_symbols: u16, states: &[State]) -> Vec<u8> {
+    let is_flag =
+        |s: u16| symbols[s as usize].starts_with("@P.") || symbols[s as usize].starts_with("@R.");
+    let row = input_symbols as usize + 1;
+    let mut indices = vec![(NO_SYMBOL, NO_TABLE_INDEX); row * states.len()];
+    let mut transitions: Vec<(u16, u16, u32)> = Vec::new();
+    for (n, state) in states.iter().enumerate() {
+        let base = n * row;
+        if state.is_final {
+            indices[base] = (NO_SYMBOL, 1);
+        }
+        // Epsilons and flags first, then one group per input symbol
+        let mut arcs = state.arcs.clone();
+        arcs.sort_by_key(|(input, _, _)| {
+            if *input == 0 || is_flag(*input) {
+                0
+            } else {
+                *input
+            }
+        });
+        let mut previous_group = None;
+        for (input, output, target) in arcs {
+            let group = if input == 0 || is_flag(input) {
+                0
+            } else {
+                input
+            };
+            if previous_group != Some(group) {
+                indices[base + 1 + group as usize] =
+                    (group, TARGET_TABLE_START + transitions.len() as u32);
+                previous_group = Some(group);
+            }
+            transitions.push((input, output, (target * row) as u32));
+        }
+        transitions.push((NO_SYMBOL, NO_SYMBOL, NO_TABLE_INDEX));
+    }
+
+    let mut body = Vec::new();
+    body.extend(input_symbols.to_le_bytes());
+    body.extend((symbols.len() as u16).to_le_bytes());
+    body.extend((indices.len() as u32).to_le_bytes());
+    body.extend((transitions.len() as u32).to_le_bytes());
+    body.extend((states.len() as u32).to_le_bytes());
+    body.extend((transitions.len() as u32).to_le_bytes());
+    for _ in 0..9 {
+        body.extend(0u32.to_le_bytes());
+    }
+    for s in symbols {
+        body.extend(s.as_bytes());
+        body.push(0);
+    }
+    for (input, target) in indices {
+        body.extend(input.to_le_bytes());
+        body.extend(target.to_le_bytes());
+    }
+    for (input, output, target) in transitions {
+        body.extend(input.to_le_bytes());
+        body.extend(output.to_le_bytes());
+        body.extend(target.to_le_bytes());
+    }
+
+    let props = b"version\x003.3\x00type\x00HFST_OL\x00name\x00test\x00";
+    let mut out = b"HFST\x00".to_vec();
+    out.extend((props.len() as u16).to_le_bytes());
+    out.push(0);
+    out.extend(props);
+    out.extend(body);
+    out
+}
+
+// cat -> cat+N+Sg, cats -> cat+N+Pl, plus a flag-guarded branch where only +Flag survives
+fn analyser() -> Vec<u8> {
+    let symbols = [
+        "@_EPSILON_SYMBOL_@",
+        "c",
+        "a",
+        "t",
+        "s",
+        "@P.Num.Pl@",
+        "@R.Num.Pl@",
+        "@R.Num.Sg@",
+        "+N",
+        "+Sg",
+        "+Pl",
+        "+Flag",
+        "+Blocked",
+    ];
+    let arc = |i, o, t| (i, o, t);
+    let states = vec![
+        State {
+            is_final: false,
+            arcs: vec![arc(1, 1, 1)],
+        },
+        State {
+            is_final: false,
+            arcs: vec![arc(2, 2, 2)],
+        },
+        State {
+            is_final: false,
+            arcs: vec![arc(3, 3, 3)],
+        },
+        State {
+            is_final: false,
+            arcs: vec![arc(0, 8, 4), arc(5, 5, 7)],
+        },
+        State {
+            is_final: false,
+            arcs: vec![arc(0, 9, 5), arc(4, 10, 6)],
+        },
+        State {
+            is_final: true,
+            arcs: vec![],
+        },
+        State {
+            is_final: true,
+            arcs: vec![],
+        },
+        State {
+            is_final: false,
+            arcs: vec![arc(6, 6, 8), arc(7, 7, 9)],
+        },
+        State {
+            is_final: false,
+            arcs: vec![arc(0, 11, 5)],
+        },
+        State {
+            is_final: false,
+            arcs: vec![arc(0, 12, 5)],
+        },
+    ];
+    build_hfstol(&symbols, 8, &states)
+}
+
+#[test]
+fn looks_up_multichar_outputs_and_flags() -> Result<()> {
+    init();
+    let fst = HfstolTransducer::from_bytes(&analyser())?;
+    assert_eq!(outputs(fst.lookup("cat")), ["cat+Flag", "cat+N+Sg"]);
+    assert_eq!(outputs(fst.lookup("cats")), ["cat+N+Pl"]);
+    assert!(fst.lookup("dog").is_empty());
+    assert!(fst.lookup("ca").is_empty());
+    Ok(())
+}
+
+#[test]
+fn rejects_non_optimized_lookup_transducers() {
+    init();
+    let mut data = analyser();
+    let pos = data.windows(7).position(|w| w == b"HFST_OL").unwrap();
+    data[pos..pos + 7].copy_from_slice(b"FOMA\0\0\0");
+    let err = HfstolTransducer::from_bytes(&data).unwrap_err();
+    assert!(err.to_string().contains("FOMA"));
+    assert!(HfstolTransducer::from_bytes(b"HFST\0").is_err());
+}
+
+#[tokio::test]
+async fn native_backend_runs_suites() -> Result<()> {
+    init();
+    let fst: Arc<dyn Transducer> = Arc::new(HfstolTransducer::from_bytes(&analyser())?);
+    let backend = NativeBackend {
+        analyzer: Some(fst),
+        generator: None,
+        timeout: None,
+    };
+    assert_eq!(
+        backend.analyze_batch(&["cats".into(), " cat ".into()]).await?,
+        vec![
+            Ok(vec!["cat+N+Pl".into()]),
+            Ok(vec!["cat+Flag".into(), "cat+N+Sg".into()])
+        ]
+    );
+    assert!(backend.generate_batch(&["cat+N+Sg".into()]).await.is_err());
+
+    let suite = TestSuite {
+        name: "native".into(),
+        cases: vec![TestCase {
+            name: "Nouns: cats".into(),
+            direction: Direction::Analyze,
+            input: "cats".into(),
+            expect: vec!["cat+N+Pl".into()],
+            expect_not: vec![],
+            weight_checks: vec![],
+        }],
+    };
+    let summary = run_suites(&backend, &[suite], false).await;
+    assert_eq!(summary.passed, 1);
+    Ok(())
+}
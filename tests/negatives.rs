@@ -0,0 +1,79 @@
+#![cfg(unix)]
+
+use anyhow::Result;
+use morph_test2::backend::{Backend, DEFAULT_TIMEOUT, ExternalBackend, LookupArgs};
+use morph_test2::engine::run_suites;
+use morph_test2::pool::PooledBackend;
+use morph_test2::types::*;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::tempdir;
+
+// Every input is both a singular and a plural noun
+const FAKE_LOOKUP: &str = r#"#!/bin/sh
+while IFS= read -r line; do
+  printf '%s\t%s+N+Sg\t0.000000\n%s\t%s+N+Pl\t0.000000\n\n' "$line" "$line" "$line" "$line"
+done
+"#;
+
+fn write_tool(dir: &Path) -> Result<String> {
+    let tool = dir.join("fake-lookup");
+    std::fs::write(&tool, FAKE_LOOKUP)?;
+    std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755))?;
+    Ok(tool.to_string_lossy().into_owned())
+}
+
+fn suite() -> TestSuite {
+    TestSuite {
+        name: "negatives".into(),
+        cases: vec![
+            TestCase {
+                name: "Nouns: guolli".into(),
+                direction: Direction::Analyze,
+                input: "guolli".into(),
+                expect: vec!["guolli+N+Sg".into()],
+                expect_not: vec![],
+                weight_checks: vec![],
+            },
+            TestCase {
+                name: "Nouns: beana".into(),
+                direction: Direction::Analyze,
+                input: "beana".into(),
+                expect: vec!["beana+N+Sg".into()],
+                expect_not: vec!["beana+N+Pl".into()],
+                weight_checks: vec![],
+            },
+        ],
+    }
+}
+
+async fn failed_inputs(backend: &dyn Backend) -> Vec<String> {
+    let summary = run_suites(backend, &[suite()], true).await;
+    summary
+        .cases
+        .into_iter()
+        .filter(|c| !c.passed)
+        .map(|c| c.input)
+        .collect()
+}
+
+#[tokio::test]
+async fn negatives_fail_the_same_with_every_backend() -> Result<()> {
+    morph_test2::i18n::init();
+    let dir = tempdir()?;
+    let tool = write_tool(dir.path())?;
+
+    let external = ExternalBackend {
+        lookup_cmd: tool.clone(),
+        args: LookupArgs::default(),
+        generator_fst: None,
+        analyzer_fst: Some("analyser.hfstol".into()),
+        timeout: Some(DEFAULT_TIMEOUT),
+        quiet: true,
+    };
+    let pooled = PooledBackend::new(tool, LookupArgs::default(), Some("analyser.hfstol".into()), None, true, DEFAULT_TIMEOUT).await?;
+
+    assert_eq!(failed_inputs(&external).await, vec!["beana"]);
+    assert_eq!(failed_inputs(&pooled).await, vec!["beana"]);
+    Ok(())
+}
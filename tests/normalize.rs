@@ -0,0 +1,111 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use morph_test2::backend::Backend;
+use morph_test2::engine::run_suites;
+use morph_test2::normalize::{Normalizer, NormalizingBackend};
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::*;
+use std::fs;
+use std::path::Path;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+#[test]
+fn flags_are_stripped_and_symbols_rewritten() {
+    // Outputs are left as they are unless asked otherwise
+    assert!(Normalizer::default().is_identity());
+    assert_eq!(Normalizer::default().normalize("@R.Cap@Guolli"), "@R.Cap@Guolli");
+
+    let flags = Normalizer { strip_flags: true, ..Normalizer::default() };
+    assert_eq!(flags.normalize("@P.Cap.Opt@guolli@U.Case.Gen@+N"), "guolli+N");
+    assert_eq!(flags.normalize("@R.Cap@Guolli"), "Guolli");
+    // Only flag diacritics go, not other symbols between at signs
+    assert_eq!(flags.normalize("e-post@x.no @0@"), "e-post@x.no @0@");
+
+    let configured = Normalizer {
+        strip_flags: false,
+        replace: vec![("»".into(), "#".into())],
+        remove: vec!["#".into(), "^".into()],
+    };
+    assert_eq!(configured.normalize("guol»li^@P.x.y@"), "guolli@P.x.y@");
+    assert!(!configured.is_identity());
+}
+
+/// Gives its outputs with flag diacritics and boundaries left in
+struct RawBackend;
+
+#[async_trait]
+impl Backend for RawBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs
+            .iter()
+            .map(|_| Ok(vec![LookupOutput::new("guolli+N+Sg+Nom@U.Case.Nom@", Some(1.0))]))
+            .collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|_| Ok(vec!["@P.Cap.Opt@guol#li".into()])).collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn case(direction: Direction, input: &str, expect: &str) -> TestCase {
+    TestCase {
+        name: format!("Nouns: {input}"),
+        direction,
+        input: input.into(),
+        expect: vec![expect.into()],
+        expect_not: vec![],
+        weight_checks: vec![],
+    }
+}
+
+#[tokio::test]
+async fn normalized_outputs_are_compared_and_raw_ones_kept() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let file = dir.path().join("nouns.yaml");
+    let gen_fst = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/generator.att");
+    fs::write(
+        &file,
+        format!(
+            "Config:\n  hfst:\n    Gen: {}\n  Normalize:\n    Flags: true\n    Remove: ['#']\nTests:\n  Nouns:\n    guolli+N+Sg+Nom: guolli\n",
+            gen_fst.display()
+        ),
+    )?;
+    let normalizer = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst)?[0].normalizer.clone();
+    assert!(normalizer.strip_flags);
+    assert_eq!(normalizer.remove, ["#"]);
+    // Flags are only stripped when asked for
+    let unflagged = dir.path().join("unflagged.yaml");
+    fs::write(&unflagged, fs::read_to_string(&file)?.replace("    Flags: true\n", ""))?;
+    let loaded = load_specs(std::slice::from_ref(&unflagged), BackendChoice::Hfst)?;
+    assert!(!loaded[0].normalizer.strip_flags);
+
+    let suites = vec![TestSuite {
+        name: "nouns".into(),
+        cases: vec![
+            case(Direction::Generate, "guolli+N+Sg+Nom", "guolli"),
+            case(Direction::Analyze, "guolli", "guolli+N+Sg+Nom"),
+        ],
+    }];
+    let summary = run_suites(&NormalizingBackend::new(RawBackend, normalizer), &suites, false).await;
+    assert_eq!((summary.passed, summary.failed), (2, 0));
+    assert_eq!(summary.cases[0].actual, ["guolli"]);
+    assert_eq!(summary.cases[0].outputs[0].raw.as_deref(), Some("@P.Cap.Opt@guol#li"));
+    // Weights stay with their outputs
+    assert_eq!(summary.cases[1].outputs[0].weight, Some(1.0));
+
+    let unnormalized = run_suites(&RawBackend, &suites, false).await;
+    assert_eq!(unnormalized.failed, 2);
+    Ok(())
+}
@@ -1,25 +1,27 @@
 // tests/order_insensitive.rs
 use anyhow::Result;
+use async_trait::async_trait;
 use morph_test2::backend::Backend;
 use morph_test2::engine::run_suites;
 use morph_test2::types::*;
 
 struct MockBackend;
 
+#[async_trait]
 impl Backend for MockBackend {
-    fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
-        Ok(inputs.iter().map(|_| vec![]).collect())
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|_| Ok(vec![])).collect())
     }
-    fn generate_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
-        Ok(inputs.iter().map(|_| vec!["a".into(), "b".into(), "c".into()]).collect())
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|_| Ok(vec!["a".into(), "b".into(), "c".into()])).collect())
     }
-    fn validate(&self) -> Result<()> {
+    async fn validate(&self) -> Result<()> {
         Ok(())
     }
 }
 
-#[test]
-fn order_does_not_matter_for_lists() {
+#[tokio::test]
+async fn order_does_not_matter_for_lists() {
     morph_test2::i18n::init();
     let suite = TestSuite {
         name: "order".into(),
@@ -29,10 +31,11 @@ fn order_does_not_matter_for_lists() {
             input: "X+V".into(),
             expect: vec!["c".into(), "a".into(), "b".into()],
             expect_not: vec![],
+            weight_checks: vec![],
         }],
     };
     let backend = MockBackend;
-    let summary = run_suites(&backend, &[suite], true);
+    let summary = run_suites(&backend, &[suite], true).await;
     assert_eq!(summary.total, 1);
     assert_eq!(summary.failed, 0);
 }
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::TestCase;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+fn config() -> String {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    format!(
+        "Config:\n  hfst:\n    Gen: {}\n    Morph: {}\n",
+        fixtures.join("generator.att").display(),
+        fixtures.join("analyser.att").display()
+    )
+}
+
+fn cases(dir: &Path, name: &str, spec: &str) -> Result<Vec<TestCase>> {
+    let file: PathBuf = dir.join(name);
+    fs::write(&file, format!("{}{spec}", config()))?;
+    let suites = load_specs(&[file], BackendChoice::Hfst)?;
+    Ok(suites[0].suite.cases.clone())
+}
+
+const WRITTEN_OUT: &str = "\
+Tests:
+  Nouns:
+    guolli+N+Sg+Nom: guolli
+    guolli+N+Sg+Gen: [guoli, ~guolli]
+    guolli+N+Pl+Nom: guolit
+    bárdni+N+Sg+Nom: bárdni
+    bárdni+N+Pl+Nom: bártnit
+";
+
+#[test]
+fn paradigms_expand_to_the_written_out_tests() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let expected = format!("{:?}", cases(dir.path(), "written.yaml", WRITTEN_OUT)?);
+
+    // Forms positionally, or per tag, with a cell left untested
+    let paradigms = "\
+Paradigms:
+  Nouns:
+    Tags: [+N+Sg+Nom, +N+Sg+Gen, +N+Pl+Nom]
+    Lemmas:
+      guolli: [guolli, [guoli, ~guolli], guolit]
+      bárdni:
+        +N+Sg+Nom: bárdni
+        +N+Pl+Nom: bártnit
+";
+    assert_eq!(format!("{:?}", cases(dir.path(), "paradigms.yaml", paradigms)?), expected);
+
+    // A single lemma, added to a group in Tests
+    let mixed = "\
+Tests:
+  Nouns:
+    guolli+N+Sg+Nom: guolli
+    guolli+N+Sg+Gen: [guoli, ~guolli]
+    guolli+N+Pl+Nom: guolit
+Paradigms:
+  Nouns:
+    Tags: [N+Sg+Nom, N+Sg+Gen, N+Pl+Nom]
+    Lemma: bárdni
+    Forms: [bárdni, null, bártnit]
+";
+    assert_eq!(format!("{:?}", cases(dir.path(), "mixed.yaml", mixed)?), expected);
+    Ok(())
+}
+
+#[test]
+fn paradigm_mistakes_are_reported() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let error = |spec: &str| cases(dir.path(), "wrong.yaml", spec).map(|_| ()).unwrap_err();
+
+    let too_few = error("Paradigms:\n  Nouns:\n    Tags: [+N+Sg+Nom, +N+Sg+Gen]\n    Lemma: guolli\n    Forms: [guolli]\n");
+    assert!(format!("{too_few:#}").contains("guolli"), "{too_few:#}");
+    let unknown = error("Paradigms:\n  Nouns:\n    Tags: [+N+Sg+Nom]\n    Lemmas:\n      guolli: {+N+Sg+Ill: guollái}\n");
+    assert!(format!("{unknown:#}").contains("+N+Sg+Ill"), "{unknown:#}");
+    let no_forms = error("Paradigms:\n  Nouns:\n    Tags: [+N+Sg+Nom]\n    Lemma: guolli\n");
+    assert!(format!("{no_forms:#}").contains("Nouns"), "{no_forms:#}");
+    Ok(())
+}
@@ -0,0 +1,64 @@
+#![cfg(unix)]
+
+use anyhow::Result;
+use morph_test2::backend::{Backend, DEFAULT_TIMEOUT, LookupArgs};
+use morph_test2::pool::PooledBackend;
+use std::os::unix::fs::PermissionsExt;
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+
+// Answers like hfst-optimized-lookup: 300 analyses per input, each record
+// ended by a blank line, and an unknown-word record for "none"
+const FAKE_LOOKUP: &str = r#"#!/bin/sh
+while IFS= read -r line; do
+  if [ "$line" = "none" ]; then
+    printf '%s\t%s\t+?\n\n' "$line" "$line"
+    continue
+  fi
+  i=0
+  while [ $i -lt 300 ]; do
+    printf '%s\t%s+%03d\t0.000000\n' "$line" "$line" "$i"
+    i=$((i+1))
+  done
+  printf '\n'
+done
+"#;
+
+#[tokio::test]
+async fn batch_returns_when_last_record_arrives() -> Result<()> {
+    morph_test2::i18n::init();
+    let dir = tempdir()?;
+    let tool = dir.path().join("fake-lookup");
+    std::fs::write(&tool, FAKE_LOOKUP)?;
+    std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755))?;
+
+    let backend = PooledBackend::new(
+        tool.to_string_lossy().into_owned(),
+        LookupArgs::default(),
+        Some("analyser.hfstol".into()),
+        None,
+        true,
+        DEFAULT_TIMEOUT,
+    )
+    .await?;
+
+    let inputs: Vec<String> = vec!["guolli".into(), "none".into(), "".into(), " beana ".into()];
+    let started = Instant::now();
+    let results: Vec<Vec<String>> = backend
+        .analyze_batch(&inputs)
+        .await?
+        .into_iter()
+        .map(|outcome| outcome.unwrap().into_iter().map(|o| o.output).collect())
+        .collect();
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].len(), 300);
+    assert_eq!(results[0][0], "guolli+000");
+    assert_eq!(results[0][299], "guolli+299");
+    assert!(results[1].is_empty());
+    assert!(results[2].is_empty());
+    assert_eq!(results[3].len(), 300);
+    assert_eq!(results[3][0], "beana+000");
+    Ok(())
+}
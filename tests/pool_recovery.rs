@@ -0,0 +1,107 @@
+#![cfg(unix)]
+
+use anyhow::Result;
+use morph_test2::backend::{Backend, DEFAULT_TIMEOUT, LookupArgs};
+use morph_test2::engine::run_suites;
+use morph_test2::pool::PooledBackend;
+use morph_test2::types::*;
+use std::os::unix::fs::PermissionsExt;
+use std::sync::Once;
+use tempfile::tempdir;
+
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+// Echoes every input as its own analysis, but dies on "boom"
+const CRASHING_LOOKUP: &str = r#"#!/bin/sh
+while IFS= read -r line; do
+  if [ "$line" = "boom" ]; then
+    exit 1
+  fi
+  printf '%s\t%s+N\t0.000000\n\n' "$line" "$line"
+done
+"#;
+
+fn case(input: &str) -> TestCase {
+    TestCase {
+        name: format!("Nouns: {input}"),
+        direction: Direction::Analyze,
+        input: input.into(),
+        expect: vec![format!("{input}+N")],
+        expect_not: vec![],
+        weight_checks: vec![],
+    }
+}
+
+#[tokio::test]
+async fn crashing_input_is_isolated_and_process_respawned() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let tool = dir.path().join("crashing-lookup");
+    std::fs::write(&tool, CRASHING_LOOKUP)?;
+    std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755))?;
+
+    let backend = PooledBackend::new(
+        tool.to_string_lossy().into_owned(),
+        LookupArgs::default(),
+        Some("analyser.hfstol".into()),
+        None,
+        true,
+        DEFAULT_TIMEOUT,
+    )
+    .await?;
+
+    let words = ["a", "b", "c", "boom", "d", "e", "f", "g", "h", "i"];
+    let inputs: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+    let outcomes = backend.analyze_batch(&inputs).await?;
+    for (word, outcome) in words.iter().zip(&outcomes) {
+        if *word == "boom" {
+            assert!(outcome.as_ref().unwrap_err().contains("boom"));
+        } else {
+            assert_eq!(outcome.as_ref().unwrap(), &vec![LookupOutput::new(format!("{word}+N"), Some(0.0))]);
+        }
+    }
+
+    let suite = TestSuite {
+        name: "crash".into(),
+        cases: words.iter().map(|w| case(w)).collect(),
+    };
+    let summary = run_suites(&backend, &[suite], false).await;
+    assert_eq!(summary.failed, 1);
+    let failed = summary.cases.iter().find(|c| !c.passed).unwrap();
+    assert_eq!(failed.input, "boom");
+    assert!(failed.error.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn batches_larger_than_the_pipes_do_not_block() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let tool = dir.path().join("echoing-lookup");
+    std::fs::write(&tool, CRASHING_LOOKUP)?;
+    std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755))?;
+
+    let backend = PooledBackend::new(
+        tool.to_string_lossy().into_owned(),
+        LookupArgs::default(),
+        Some("analyser.hfstol".into()),
+        None,
+        true,
+        DEFAULT_TIMEOUT,
+    )
+    .await?;
+
+    // Each process gets well over a pipe buffer of input and of output
+    let inputs: Vec<String> = (0..num_cpus::get().max(1) * 100)
+        .map(|i| format!("{i}{}", "a".repeat(1000)))
+        .collect();
+    let outcomes = tokio::time::timeout(DEFAULT_TIMEOUT, backend.analyze_batch(&inputs)).await??;
+    assert_eq!(outcomes.len(), inputs.len());
+    for (input, outcome) in inputs.iter().zip(&outcomes) {
+        assert_eq!(outcome.as_ref().unwrap(), &vec![LookupOutput::new(format!("{input}+N"), Some(0.0))]);
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use morph_test2::project::{ProjectConfig, find_project_config};
+use morph_test2::spec::{BackendChoice, load_specs};
+use std::fs;
+use std::path::Path;
+use std::sync::Once;
+use std::time::Duration;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+const PROJECT: &str = "\
+Config:
+  hfst:
+    Gen: src/generator.att
+    Morph: src/analyser.att
+  Timeout: 5
+Options:
+  Backend: auto
+  IgnoreExtraAnalyses: true
+  CacheDir: .cache
+";
+
+const TESTS: &str = "Tests:\n  Verbs:\n    foo+V: [bar, baz]\n";
+
+/// A language repo with the project config at the top and tests two levels down
+fn project(dir: &Path) -> Result<()> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::create_dir_all(dir.join("src"))?;
+    fs::create_dir_all(dir.join("test/nouns"))?;
+    fs::copy(fixtures.join("generator.att"), dir.join("src/generator.att"))?;
+    fs::copy(fixtures.join("analyser.att"), dir.join("src/analyser.att"))?;
+    fs::write(dir.join("morph-test.yaml"), PROJECT)?;
+    fs::write(dir.join("test/nouns/verbs.yaml"), TESTS)?;
+    Ok(())
+}
+
+#[test]
+fn files_without_config_use_the_project_config() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    project(dir.path())?;
+    // The project config itself is not a test file
+    let suites = load_specs(&[dir.path().to_path_buf()], BackendChoice::Hfst)?;
+    assert_eq!(suites.len(), 1);
+    let swc = &suites[0];
+    assert!(Path::new(&swc.gen_fst).ends_with("src/generator.att"));
+    assert!(Path::new(&swc.gen_fst).is_file());
+    assert!(swc.morph_fst.as_deref().is_some_and(|m| Path::new(m).is_file()));
+    assert!(swc.native);
+    assert_eq!(swc.timeout, Some(Duration::from_secs(5)));
+    Ok(())
+}
+
+#[test]
+fn the_file_config_overrides_the_project_config() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    project(dir.path())?;
+    let file = dir.path().join("test/nouns/verbs.yaml");
+    fs::write(&file, format!("Config:\n  hfst:\n    Gen: ../../src/analyser.att\n  Timeout: 2\n{TESTS}"))?;
+    let swc = &load_specs(std::slice::from_ref(&file), BackendChoice::Hfst)?[0];
+    assert!(swc.gen_fst.ends_with("analyser.att"));
+    assert!(Path::new(&swc.gen_fst).is_file());
+    // What the file leaves out still comes from the project
+    assert!(swc.morph_fst.as_deref().is_some_and(|m| m.ends_with("src/analyser.att")));
+    assert_eq!(swc.timeout, Some(Duration::from_secs(2)));
+    Ok(())
+}
+
+#[test]
+fn the_nearest_project_config_is_used() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    project(dir.path())?;
+    let file = dir.path().join("test/nouns/verbs.yaml");
+    let found = find_project_config(&file).unwrap();
+    assert_eq!(found, fs::canonicalize(dir.path().join("morph-test.yaml"))?);
+
+    let options = ProjectConfig::load(&found)?.options;
+    assert_eq!(options.backend.as_deref(), Some("auto"));
+    assert!(options.ignore_extra_analyses && !options.serial);
+    assert_eq!(options.cache_dir, Some(found.parent().unwrap().join(".cache")));
+
+    fs::write(dir.path().join("test/.morph-test.yaml"), "Options:\n  Serial: true\n")?;
+    let nearer = find_project_config(&file).unwrap();
+    assert!(nearer.ends_with("test/.morph-test.yaml"));
+    // A project config without Config leaves the test files to give their own
+    let error = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst).unwrap_err();
+    assert!(format!("{error:#}").contains("Config"), "{error:#}");
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anyhow::Result;
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::TestCase;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+const YAML: &str = "\
+Config:
+  hfst:
+    Gen: GEN
+    Morph: MORPH
+  Timeout: 5
+Tests:
+  Verbs:
+    foo+V: [bar, baz]
+    gæljohd+V+TV+Ind+Prs+Sg1: [gæljoem, ~gæljohdan]
+  Weights:
+    foo+V:
+      Forms: [bar, baz]
+      Best: bar
+";
+
+const JSON: &str = r#"{
+  "Config": {
+    "hfst": {"Gen": "GEN", "Morph": "MORPH"},
+    "Timeout": 5
+  },
+  "Tests": {
+    "Verbs": {
+      "foo+V": ["bar", "baz"],
+      "gæljohd+V+TV+Ind+Prs+Sg1": ["gæljoem", "~gæljohdan"]
+    },
+    "Weights": {
+      "foo+V": {"Forms": ["bar", "baz"], "Best": "bar"}
+    }
+  }
+}"#;
+
+const TOML: &str = r#"
+[Config]
+Timeout = 5
+
+[Config.hfst]
+Gen = "GEN"
+Morph = "MORPH"
+
+[Tests.Verbs]
+"foo+V" = ["bar", "baz"]
+"gæljohd+V+TV+Ind+Prs+Sg1" = ["gæljoem", "~gæljohdan"]
+
+[Tests.Weights."foo+V"]
+Forms = ["bar", "baz"]
+Best = "bar"
+"#;
+
+fn write_spec(dir: &Path, name: &str, template: &str) -> Result<PathBuf> {
+    let path = dir.join(name);
+    let content = template
+        .replace("GEN", &fixture("generator.att").display().to_string())
+        .replace("MORPH", &fixture("analyser.att").display().to_string());
+    fs::write(&path, content)?;
+    Ok(path)
+}
+
+fn cases(paths: &[PathBuf]) -> Result<Vec<TestCase>> {
+    let suites = load_specs(paths, BackendChoice::Hfst)?;
+    assert_eq!(suites.len(), 1);
+    Ok(suites[0].suite.cases.clone())
+}
+
+#[test]
+fn json_and_toml_give_the_same_tests_as_yaml() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let yaml = cases(&[write_spec(dir.path(), "verbs.yaml", YAML)?])?;
+    let json = cases(&[write_spec(dir.path(), "verbs.json", JSON)?])?;
+    let toml = cases(&[write_spec(dir.path(), "verbs.toml", TOML)?])?;
+    assert!(!yaml.is_empty());
+    assert_eq!(format!("{json:?}"), format!("{yaml:?}"));
+    assert_eq!(format!("{toml:?}"), format!("{yaml:?}"));
+    // The order of the groups and keys is kept
+    assert!(toml[0].name.starts_with("Verbs"), "{}", toml[0].name);
+    assert!(toml.iter().any(|case| !case.expect_not.is_empty()));
+    Ok(())
+}
+
+#[test]
+fn json_and_toml_files_are_found_in_directories() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    write_spec(dir.path(), "verbs.json", JSON)?;
+    write_spec(dir.path(), "verbs.toml", TOML)?;
+    fs::write(dir.path().join("notes.txt"), "not a test file")?;
+    let suites = load_specs(&[dir.path().to_path_buf()], BackendChoice::Hfst)?;
+    assert_eq!(suites.len(), 2);
+
+    fs::write(dir.path().join("broken.json"), "{\"Tests\": ")?;
+    let error = load_specs(&[dir.path().to_path_buf()], BackendChoice::Hfst).unwrap_err();
+    assert!(format!("{error:#}").contains("broken.json"), "{error:#}");
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::TestCase;
+use std::fs;
+use std::path::Path;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+/// The project config that gives the tables their transducers
+fn project(dir: &Path, table: &str) -> Result<()> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::write(
+        dir.join("morph-test.yaml"),
+        format!(
+            "Config:\n  hfst:\n    Gen: {}\n    Morph: {}\n{table}",
+            fixtures.join("generator.att").display(),
+            fixtures.join("analyser.att").display()
+        ),
+    )?;
+    Ok(())
+}
+
+const YAML: &str = "\
+Tests:
+  verbs:
+    foo+V: [bar, baz, ~bas]
+    gæljohd+V+TV+Ind+Prs+Sg1: gæljoem
+";
+
+fn cases(file: &Path) -> Result<Vec<TestCase>> {
+    let suites = load_specs(&[file.to_path_buf()], BackendChoice::Hfst)?;
+    assert_eq!(suites.len(), 1);
+    Ok(suites[0].suite.cases.clone())
+}
+
+#[test]
+fn long_and_wide_tables_give_the_same_tests_as_yaml() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    project(dir.path(), "")?;
+    let yaml_dir = dir.path().join("yaml");
+    fs::create_dir(&yaml_dir)?;
+    fs::write(yaml_dir.join("verbs.yaml"), YAML)?;
+    let yaml = cases(&yaml_dir.join("verbs.yaml"))?;
+
+    let long = dir.path().join("verbs.tsv");
+    fs::write(
+        &long,
+        "# lemma\ttags\tsurface\tnegative\n\
+         lemma\ttags\tsurface\tnegative\n\
+         foo\t+V\tbar\n\
+         foo\tV\tbaz\t\n\
+         foo\t+V\tbas\tyes\n\
+         \n\
+         gæljohd\t+V+TV+Ind+Prs+Sg1\tgæljoem\t0\n",
+    )?;
+    assert_eq!(format!("{:?}", cases(&long)?), format!("{yaml:?}"));
+
+    let wide = dir.path().join("verbs.csv");
+    fs::write(
+        &wide,
+        "lemma,+V,+V+TV+Ind+Prs+Sg1\n\
+         foo,\"bar, baz, ~bas\",\n\
+         gæljohd,,gæljoem\n",
+    )?;
+    assert_eq!(format!("{:?}", cases(&wide)?), format!("{yaml:?}"));
+    Ok(())
+}
+
+#[test]
+fn columns_are_named_in_the_config() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    project(
+        dir.path(),
+        "  Table:\n    Lemma: Lemma\n    Tags: Analysis\n    Surface: Form\n    Negative: Not\n    Group: Paradigm\n",
+    )?;
+    let file = dir.path().join("verbs.tsv");
+    fs::write(
+        &file,
+        "Paradigm\tLemma\tAnalysis\tForm\tNot\n\
+         Verbs\tfoo\t+V\tbar, baz\n\
+         Verbs\tfoo\t+V\tbas\tx\n",
+    )?;
+    let cases = cases(&file)?;
+    assert_eq!(cases[0].name, "Verbs: foo+V");
+    assert_eq!(cases[0].expect, ["bar", "baz"]);
+    assert_eq!(cases[0].expect_not, ["bas"]);
+
+    // Without the configured column, the error names it
+    fs::write(&file, "Paradigm\tLemma\tTags\tForm\nVerbs\tfoo\t+V\tbar\n")?;
+    let error = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst).unwrap_err();
+    assert!(format!("{error:#}").contains("Analysis"), "{error:#}");
+    fs::write(&file, "Paradigm\tLemma\tAnalysis\tForm\nVerbs\tfoo\t+V\t\n")?;
+    let error = load_specs(&[file], BackendChoice::Hfst).unwrap_err();
+    assert!(format!("{error:#}").contains("Form"), "{error:#}");
+    Ok(())
+}
+
+#[test]
+fn commas_can_be_escaped_and_long_columns_are_not_tag_strings() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    project(dir.path(), "")?;
+    let file = dir.path().join("punctuation.tsv");
+    fs::write(&file, "lemma\ttags\tsurface\n,\t+Punct\t\\,, ~\\,\\,\n")?;
+    let cases = cases(&file)?;
+    assert_eq!(cases[0].expect, [","]);
+    assert_eq!(cases[0].expect_not, [",,"]);
+
+    // A table without a surface column is wide, where a negative column would be a tag string
+    fs::write(&file, "lemma\t+V\tnegative\nfoo\tbar\tyes\n")?;
+    let error = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst).unwrap_err();
+    assert!(format!("{error:#}").contains("negative"), "{error:#}");
+    Ok(())
+}
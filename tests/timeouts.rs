@@ -0,0 +1,132 @@
+#![cfg(unix)]
+
+use anyhow::Result;
+use morph_test2::backend::{Backend, ExternalBackend, LookupArgs};
+use morph_test2::pool::PooledBackend;
+use morph_test2::types::LookupOutput;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::sync::Once;
+use std::time::Duration;
+use tempfile::tempdir;
+
+// Hangs on "slow", answers everything else with a single analysis
+const SLOW_LOOKUP: &str = r#"#!/bin/sh
+while IFS= read -r line; do
+  if [ "$line" = "slow" ]; then
+    sleep 30
+  fi
+  printf '%s\t%s+N\t0.000000\n\n' "$line" "$line"
+done
+"#;
+
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+fn write_tool(dir: &Path) -> Result<String> {
+    let tool = dir.join("slow-lookup");
+    std::fs::write(&tool, SLOW_LOOKUP)?;
+    std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755))?;
+    Ok(tool.to_string_lossy().into_owned())
+}
+
+fn inputs() -> Vec<String> {
+    vec!["guolli".into(), "slow".into(), "beana".into()]
+}
+
+#[tokio::test]
+async fn external_backend_reports_input_that_timed_out() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let backend = ExternalBackend {
+        lookup_cmd: write_tool(dir.path())?,
+        args: LookupArgs::default(),
+        generator_fst: None,
+        analyzer_fst: Some("analyser.hfstol".into()),
+        timeout: Some(TIMEOUT),
+        quiet: true,
+    };
+
+    let outcomes = backend.analyze_batch(&inputs()).await?;
+    assert_eq!(outcomes.len(), 3);
+    assert_eq!(outcomes[0], Ok(vec![LookupOutput::new("guolli+N", Some(0.0))]));
+    let error = outcomes[1].clone().unwrap_err();
+    assert!(error.contains("slow"), "error should name the input: {error}");
+    assert_eq!(outcomes[2], Ok(vec![LookupOutput::new("beana+N", Some(0.0))]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn pooled_backend_reports_input_that_timed_out() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let backend = PooledBackend::new(
+        write_tool(dir.path())?,
+        LookupArgs::default(),
+        Some("analyser.hfstol".into()),
+        None,
+        true,
+        TIMEOUT,
+    )
+    .await?;
+
+    let outcomes = backend.analyze_batch(&inputs()).await?;
+    assert_eq!(outcomes.len(), 3);
+    assert_eq!(outcomes[0], Ok(vec![LookupOutput::new("guolli+N", Some(0.0))]));
+    let error = outcomes[1].clone().unwrap_err();
+    assert!(error.contains("slow"), "error should name the input: {error}");
+    assert_eq!(outcomes[2], Ok(vec![LookupOutput::new("beana+N", Some(0.0))]));
+    Ok(())
+}
+
+// Counts its starts next to itself, and dies on every input starting with "boom"
+const CRASHING_LOOKUP: &str = r#"#!/bin/sh
+echo >> "$0.starts"
+while IFS= read -r line; do
+  case "$line" in boom*) exit 1 ;; esac
+  printf '%s\t%s+N\t0.000000\n\n' "$line" "$line"
+done
+"#;
+
+fn crashing_backend(dir: &Path) -> Result<(ExternalBackend, std::path::PathBuf)> {
+    let tool = dir.join("crashing-lookup");
+    std::fs::write(&tool, CRASHING_LOOKUP)?;
+    std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755))?;
+    let backend = ExternalBackend {
+        lookup_cmd: tool.to_string_lossy().into_owned(),
+        args: LookupArgs::default(),
+        generator_fst: None,
+        analyzer_fst: Some("analyser.hfstol".into()),
+        timeout: Some(TIMEOUT),
+        quiet: true,
+    };
+    Ok((backend, dir.join("crashing-lookup.starts")))
+}
+
+#[tokio::test]
+async fn process_failing_before_any_answer_is_not_restarted() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let (backend, starts) = crashing_backend(dir.path())?;
+
+    let inputs: Vec<String> = ["boom", "guolli", "beana"].map(String::from).into();
+    assert!(backend.analyze_batch(&inputs).await.is_err());
+    assert_eq!(std::fs::read_to_string(&starts)?.lines().count(), 1);
+
+    // Once something was answered, only the inputs not yet looked up fail
+    std::fs::remove_file(&starts)?;
+    let inputs: Vec<String> = ["guolli", "boom", "boom2", "beana"].map(String::from).into();
+    let outcomes = backend.analyze_batch(&inputs).await?;
+    assert_eq!(outcomes[0], Ok(vec![LookupOutput::new("guolli+N", Some(0.0))]));
+    assert!(outcomes[1].clone().unwrap_err().contains("boom"));
+    assert!(outcomes[2].clone().unwrap_err().contains("boom2"));
+    let error = outcomes[3].clone().unwrap_err();
+    assert!(error.contains("Not looked up"), "{error}");
+    assert_eq!(std::fs::read_to_string(&starts)?.lines().count(), 2);
+    Ok(())
+}
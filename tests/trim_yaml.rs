@@ -1,43 +1,46 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use morph_test2::backend::Backend;
 use morph_test2::engine::run_suites;
 use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::LookupOutcome;
 use std::fs;
 use tempfile::tempdir;
 
 struct MockGen;
 
+#[async_trait]
 impl Backend for MockGen {
-    fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
         let results = inputs.iter().map(|input| {
             // Return expected analysis results for the trimmed test inputs
-            match input.as_str() {
+            Ok(match input.as_str() {
                 "gæljoejidie" => vec!["gæljodh+V+TV+Ind+Prs+Pl2".into()],
                 "bar" => vec!["foo+V".into()],
                 "baz" => vec!["foo+V".into()],
                 _ => vec![],
-            }
+            })
         }).collect();
         Ok(results)
     }
-    fn generate_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
         let results = inputs.iter().map(|input| {
             // Returnerer eksakt, utan ekstra blank
-            match input.as_str() {
+            Ok(match input.as_str() {
                 "gæljodh+V+TV+Ind+Prs+Pl2" => vec!["gæljoejidie".into()],
                 "foo+V" => vec!["bar".into(), "baz".into()],
                 _ => vec![],
-            }
+            })
         }).collect();
         Ok(results)
     }
-    fn validate(&self) -> Result<()> {
+    async fn validate(&self) -> Result<()> {
         Ok(())
     }
 }
 
-#[test]
-fn trims_spaces_in_yaml_keys_and_values() -> Result<()> {
+#[tokio::test]
+async fn trims_spaces_in_yaml_keys_and_values() -> Result<()> {
     morph_test2::i18n::init();
     let dir = tempdir()?;
     let file = dir.path().join("suite.yaml");
@@ -51,7 +54,7 @@ Tests:
     "foo+V": [ "bar  ", "  baz" ]
 "#;
     fs::write(&file, yaml)?;
-    let swc = load_specs(&[file.clone()], BackendChoice::Hfst)?;
+    let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst)?;
     assert_eq!(swc.len(), 1);
     let suite = &swc[0].suite;
     // Sjekk at trimming skjedde ved parsing
@@ -66,7 +69,7 @@ Tests:
     assert_eq!(c2.expect, vec!["bar", "baz"]);
     // Kjør testen med mock-backend
     let backend = MockGen;
-    let summary = run_suites(&backend, &[suite.clone()], true);
+    let summary = run_suites(&backend, std::slice::from_ref(suite), true).await;
     assert_eq!(summary.failed, 0);
     Ok(())
 }
@@ -1,33 +1,35 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use morph_test2::backend::Backend;
 use morph_test2::engine::run_suites;
 use morph_test2::types::*;
 
 struct MockBackend;
 
+#[async_trait]
 impl Backend for MockBackend {
-    fn analyze_batch(&self, _inputs: &[String]) -> Result<Vec<Vec<String>>> {
-        Ok(_inputs.iter().map(|_| vec![]).collect())
+    async fn analyze_batch(&self, _inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(_inputs.iter().map(|_| Ok(vec![])).collect())
     }
 
-    fn generate_batch(&self, inputs: &[String]) -> Result<Vec<Vec<String>>> {
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
         let results = inputs.iter().map(|input| {
-            match input.as_str() {
+            Ok(match input.as_str() {
                 "gæljodh+V+TV+Ind+Prs+Sg1" => vec!["gæljoem".into()],
                 "multi" => vec!["a".into(), "b".into()],
                 _ => vec![],
-            }
+            })
         }).collect();
         Ok(results)
     }
 
-    fn validate(&self) -> Result<()> {
+    async fn validate(&self) -> Result<()> {
         Ok(())
     }
 }
 
-#[test]
-fn exact_match_and_order() {
+#[tokio::test]
+async fn exact_match_and_order() {
     morph_test2::i18n::init();
     let suite = TestSuite {
         name: "sample".into(),
@@ -38,6 +40,7 @@ fn exact_match_and_order() {
                 input: "gæljodh+V+TV+Ind+Prs+Sg1".into(),
                 expect: vec!["gæljoem".into()],
                 expect_not: vec![],
+                weight_checks: vec![],
             },
             TestCase {
                 name: "order_sensitive_fail".into(),
@@ -45,11 +48,12 @@ fn exact_match_and_order() {
                 input: "multi".into(),
                 expect: vec!["b".into(), "a".into()],
                 expect_not: vec![],
+                weight_checks: vec![],
             },
         ],
     };
     let backend = MockBackend;
-    let summary = run_suites(&backend, &[suite], true);
+    let summary = run_suites(&backend, &[suite], true).await;
     assert_eq!(summary.total, 2);
     assert_eq!(summary.passed, 2);
     assert_eq!(summary.failed, 0);
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use morph_test2::backend::{Backend, ExternalBackend, LookupArgs};
+use morph_test2::engine::{run_suites_separately, swapped_direction_hint};
+use morph_test2::format::{FstFormat, check_fsts, sniff_format};
+use morph_test2::types::*;
+use std::fs;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+/// The start of an HFST3 file of the given type
+fn hfst3(kind: &str) -> Vec<u8> {
+    let props = format!("version\u{0}3.3\u{0}type\u{0}{kind}\u{0}");
+    let mut data = b"HFST\0".to_vec();
+    data.extend((props.len() as u16).to_le_bytes());
+    data.push(0);
+    data.extend(props.as_bytes());
+    data.extend([0; 16]);
+    data
+}
+
+#[test]
+fn formats_are_told_from_the_first_bytes() {
+    assert_eq!(sniff_format("a.hfstol", &hfst3("HFST_OLW")), FstFormat::HfstOptimized);
+    assert_eq!(
+        sniff_format("a.hfst", &hfst3("TROPICAL_OPENFST_TYPE")),
+        FstFormat::Hfst("TROPICAL_OPENFST_TYPE".into())
+    );
+    assert_eq!(sniff_format("a.foma", &[0x1f, 0x8b, 8, 0]), FstFormat::Foma);
+    assert_eq!(sniff_format("a.bin", b"LTTB\x01\0"), FstFormat::Lttoolbox);
+    assert_eq!(sniff_format("a.txt", b"0\t1\ta\tb\t0.0\n1\n"), FstFormat::Att);
+    assert_eq!(sniff_format("a.hfstol", &[0x02, 0x00, 0x7f, 0x00]), FstFormat::Unknown);
+}
+
+#[test]
+fn transducers_must_exist_and_suit_the_tool() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let hfstol = dir.path().join("analyser.hfstol");
+    fs::write(&hfstol, hfst3("HFST_OL"))?;
+    let hfstol = hfstol.to_string_lossy();
+
+    check_fsts("hfst-optimised-lookup", [hfstol.as_ref()])?;
+    check_fsts("/usr/local/bin/hfst-lookup", [hfstol.as_ref()])?;
+    // Tools that are not known read anything
+    check_fsts("./my-lookup.sh", [hfstol.as_ref()])?;
+
+    let error = check_fsts("flookup", [hfstol.as_ref()]).unwrap_err().to_string();
+    assert!(error.contains("flookup") && error.contains("analyser.hfstol"), "{error}");
+    let missing = dir.path().join("generator.hfstol");
+    let error = check_fsts("hfst-optimised-lookup", [missing.to_string_lossy().as_ref()])
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("generator.hfstol"), "{error}");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn validation_fails_on_a_missing_transducer() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let backend = ExternalBackend {
+        lookup_cmd: "sh".into(),
+        args: LookupArgs::default(),
+        generator_fst: Some(dir.path().join("generator.hfstol").to_string_lossy().into_owned()),
+        analyzer_fst: None,
+        timeout: None,
+        quiet: true,
+    };
+    let error = backend.validate().await.unwrap_err().to_string();
+    assert!(error.contains("generator.hfstol"), "{error}");
+    Ok(())
+}
+
+/// Transducers the wrong way round: lexical forms are analysed, not generated
+struct SwappedBackend;
+
+#[async_trait]
+impl Backend for SwappedBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs
+            .iter()
+            .map(|input| match input.as_str() {
+                "guolli+N+Sg+Nom" => Ok(vec!["guolli".into()]),
+                _ => Ok(vec![]),
+            })
+            .collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs.iter().map(|_| Ok(vec![])).collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn suite(cases: &[(Direction, &str)]) -> TestSuite {
+    TestSuite {
+        name: "nouns".into(),
+        cases: cases
+            .iter()
+            .map(|(direction, input)| TestCase {
+                name: format!("Nouns: {input}"),
+                direction: direction.clone(),
+                input: input.to_string(),
+                expect: vec![],
+                expect_not: vec![],
+                weight_checks: vec![],
+            })
+            .collect(),
+    }
+}
+
+#[tokio::test]
+async fn swapped_transducers_are_noticed() {
+    init();
+    let suites = vec![suite(&[
+        (Direction::Generate, "guolli+N+Sg+Gen"),
+        (Direction::Generate, "guolli+N+Sg+Nom"),
+        (Direction::Analyze, "guolli"),
+    ])];
+    let summaries = run_suites_separately(&SwappedBackend, &suites, false).await;
+    assert_eq!(
+        swapped_direction_hint(&SwappedBackend, &summaries).await.as_deref(),
+        Some("guolli+N+Sg+Nom")
+    );
+    // Once the run generated anything, the analyser is not asked
+    let mut generated = summaries.clone();
+    generated[0].cases[0].outputs = vec!["guolli".into()];
+    assert_eq!(swapped_direction_hint(&SwappedBackend, &generated).await, None);
+    // Without lexical forms there is nothing to tell by
+    let suites = vec![suite(&[(Direction::Analyze, "guolli")])];
+    let summaries = run_suites_separately(&SwappedBackend, &suites, false).await;
+    assert_eq!(swapped_direction_hint(&SwappedBackend, &summaries).await, None);
+}
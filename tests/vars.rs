@@ -0,0 +1,84 @@
+use anyhow::Result;
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::vars::{ConfigVars, fsttype_of};
+use std::fs;
+use std::path::Path;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+const TESTS: &str = "Tests:\n  Verbs:\n    foo+V: [bar, baz]\n";
+
+#[test]
+fn variables_are_expanded() -> Result<()> {
+    init();
+    let vars = ConfigVars::new(Path::new("/lang/test/nouns_gt-norm.yaml"), Some("gt-norm"))?;
+    assert_eq!(
+        vars.expand("${yamldir}/../src/generator-${fsttype}.hfstol")?,
+        "/lang/test/../src/generator-gt-norm.hfstol"
+    );
+    // Cargo sets this for the tests, as make would set a variable for its checks
+    assert_eq!(vars.expand("${CARGO_MANIFEST_DIR}/x")?, format!("{}/x", env!("CARGO_MANIFEST_DIR")));
+    assert_eq!(vars.expand("plain/$HOME/path")?, "plain/$HOME/path");
+
+    let unknown = vars.expand("${NO_SUCH_VARIABLE_HERE}/x").unwrap_err().to_string();
+    assert!(unknown.contains("NO_SUCH_VARIABLE_HERE"), "{unknown}");
+    assert!(vars.expand("${yamldir/x").is_err());
+    let no_type = ConfigVars::new(Path::new("nouns.yaml"), None)?;
+    assert!(no_type.expand("generator-${fsttype}.hfstol").is_err());
+
+    assert_eq!(fsttype_of(Path::new("test/N-nouns_gt-desc.yaml")).as_deref(), Some("gt-desc"));
+    assert_eq!(fsttype_of(Path::new("test/nouns.yaml")), None);
+    Ok(())
+}
+
+#[test]
+fn config_paths_use_the_variables() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::copy(fixtures.join("generator.att"), dir.path().join("generator-gt-norm.att"))?;
+    let file = dir.path().join("verbs_gt-norm.yaml");
+    // Without builddir in the environment, it is the working directory,
+    // which is the package root when testing
+    let config = "Config:\n  hfst:\n    Gen: ${yamldir}/generator-${fsttype}.att\n    \
+                  Morph: ${builddir}/tests/fixtures/analyser.att\n";
+    fs::write(&file, format!("{config}{TESTS}"))?;
+    let swc = &load_specs(std::slice::from_ref(&file), BackendChoice::Hfst)?[0];
+    assert!(Path::new(&swc.gen_fst).is_file(), "{}", swc.gen_fst);
+    assert_eq!(Path::new(&swc.gen_fst), std::path::absolute(dir.path())?.join("generator-gt-norm.att"));
+    assert!(swc.morph_fst.as_deref().is_some_and(|m| Path::new(m).is_file()));
+    Ok(())
+}
+
+#[test]
+fn the_project_config_leaves_the_fst_type_to_each_file() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::create_dir_all(dir.path().join("src"))?;
+    fs::create_dir_all(dir.path().join("test"))?;
+    for fsttype in ["gt-norm", "gt-desc"] {
+        fs::copy(fixtures.join("generator.att"), dir.path().join(format!("src/generator-{fsttype}.att")))?;
+    }
+    fs::write(
+        dir.path().join("morph-test.yaml"),
+        "Config:\n  hfst:\n    Gen: ${yamldir}/src/generator-${fsttype}.att\n",
+    )?;
+    fs::write(dir.path().join("test/verbs_gt-norm.yaml"), TESTS)?;
+    fs::write(dir.path().join("test/verbs_gt-desc.yaml"), TESTS)?;
+    let mut gens: Vec<String> = load_specs(&[dir.path().join("test")], BackendChoice::Hfst)?
+        .into_iter()
+        .map(|swc| swc.gen_fst)
+        .collect();
+    gens.sort();
+    assert!(gens[0].ends_with("src/generator-gt-desc.att"), "{gens:?}");
+    assert!(gens[1].ends_with("src/generator-gt-norm.att"), "{gens:?}");
+    assert!(gens.iter().all(|fst| Path::new(fst).is_file()));
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use morph_test2::backend::Backend;
+use morph_test2::engine::run_suites;
+use morph_test2::report::calculate_counts;
+use morph_test2::spec::{BackendChoice, load_specs};
+use morph_test2::types::*;
+use std::fs;
+use std::sync::Once;
+use tempfile::tempdir;
+
+static INIT: Once = Once::new();
+
+fn init() {
+    INIT.call_once(morph_test2::i18n::init);
+}
+
+fn weighted(output: &str, weight: f64) -> LookupOutput {
+    LookupOutput::new(output, Some(weight))
+}
+
+/// Generates two weighted forms for "guolli+N+Sg+Gen", and analyses both of
+/// them, the rarer form with a second, better weighted analysis
+struct WeightedBackend;
+
+#[async_trait]
+impl Backend for WeightedBackend {
+    async fn analyze_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs
+            .iter()
+            .map(|input| {
+                Ok(match input.as_str() {
+                    "guoli" => vec![weighted("guolli+N+Sg+Gen", 1.0)],
+                    "guolli" => vec![
+                        weighted("guolli+N+Sg+Nom", 0.5),
+                        weighted("guolli+N+Sg+Gen", 4.0),
+                    ],
+                    _ => vec![],
+                })
+            })
+            .collect())
+    }
+
+    async fn generate_batch(&self, inputs: &[String]) -> Result<Vec<LookupOutcome>> {
+        Ok(inputs
+            .iter()
+            .map(|input| {
+                Ok(match input.as_str() {
+                    "guolli+N+Sg+Gen" => vec![weighted("guoli", 1.0), weighted("guolli", 4.0)],
+                    "guolli+N+Sg+Nom" => vec![weighted("guolli", 0.5)],
+                    _ => vec![],
+                })
+            })
+            .collect())
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn weight_checks_compare_outputs() {
+    let outputs = vec![weighted("guoli", 1.0), weighted("guolli", 4.0)];
+    assert!(WeightCheck::Best("guoli".into()).holds(&outputs));
+    assert!(!WeightCheck::Best("guolli".into()).holds(&outputs));
+    assert!(WeightCheck::Below("guolli".into(), 5.0).holds(&outputs));
+    assert!(!WeightCheck::Below("guolli".into(), 4.0).holds(&outputs));
+    // A missing output fails every check, a missing weight counts as 0
+    assert!(!WeightCheck::Below("guolliid".into(), 5.0).holds(&outputs));
+    let unweighted = vec![LookupOutput::from("guoli"), weighted("guolli", 0.0)];
+    assert!(WeightCheck::Best("guoli".into()).holds(&unweighted));
+}
+
+#[tokio::test]
+async fn yaml_weight_assertions_are_checked() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let file = dir.path().join("weights.yaml");
+    let yaml = r#"
+Config:
+  hfst:
+    Gen: /dev/null
+Tests:
+  Nouns:
+    guolli+N+Sg+Gen:
+      Forms: [guoli, guolli]
+      Best: guoli
+      WeightBelow:
+        guolli: 5
+      BestAnalysis: true
+    guolli+N+Sg+Nom: guolli
+"#;
+    fs::write(&file, yaml)?;
+    let swc = load_specs(std::slice::from_ref(&file), BackendChoice::Hfst)?;
+    let suite = &swc[0].suite;
+
+    let generate = suite.cases.iter().find(|c| c.input == "guolli+N+Sg+Gen").unwrap();
+    assert_eq!(generate.expect, vec!["guoli", "guolli"]);
+    assert_eq!(
+        generate.weight_checks,
+        vec![
+            WeightCheck::Best("guoli".into()),
+            WeightCheck::Below("guolli".into(), 5.0)
+        ]
+    );
+    let analyse = suite
+        .cases
+        .iter()
+        .find(|c| c.direction == Direction::Analyze && c.input == "guolli")
+        .unwrap();
+    assert_eq!(analyse.expect, vec!["guolli+N+Sg+Gen", "guolli+N+Sg+Nom"]);
+    assert_eq!(analyse.weight_checks, vec![WeightCheck::Best("guolli+N+Sg+Gen".into())]);
+
+    // Only the analysis of "guolli" fails: the nominative is better weighted
+    let summary = run_suites(&WeightedBackend, std::slice::from_ref(suite), false).await;
+    let failed: Vec<&str> = summary
+        .cases
+        .iter()
+        .filter(|c| !c.passed)
+        .map(|c| c.input.as_str())
+        .collect();
+    assert_eq!(failed, vec!["guolli"]);
+    let failure = summary.cases.iter().find(|c| !c.passed).unwrap();
+    assert_eq!(failure.outputs[0], weighted("guolli+N+Sg+Nom", 0.5));
+
+    // Every weight check counts as a check of its own
+    let cases: Vec<&CaseResult> = summary.cases.iter().collect();
+    let (passes, fails, checks) = calculate_counts(&cases, false);
+    assert_eq!((passes, fails, checks), (9, 1, 10));
+    Ok(())
+}
+
+#[test]
+fn unknown_weight_keys_are_rejected() -> Result<()> {
+    init();
+    let dir = tempdir()?;
+    let file = dir.path().join("typo.yaml");
+    let yaml = r#"
+Config:
+  hfst:
+    Gen: /dev/null
+Tests:
+  Nouns:
+    guolli+N+Sg+Gen:
+      Forms: guoli
+      Bets: guoli
+"#;
+    fs::write(&file, yaml)?;
+    assert!(load_specs(std::slice::from_ref(&file), BackendChoice::Hfst).is_err());
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn lookup_tool_weights_are_kept() -> Result<()> {
+    use morph_test2::backend::{ExternalBackend, LookupArgs};
+    use std::os::unix::fs::PermissionsExt;
+
+    init();
+    let dir = tempdir()?;
+    // Answers like hfst-lookup: best weighted first, the same output reached twice
+    let tool = dir.path().join("weighted-lookup");
+    fs::write(
+        &tool,
+        "#!/bin/sh\n\
+         while IFS= read -r line; do\n\
+           printf '%s\\t%s+N\\t0.500000\\n' \"$line\" \"$line\"\n\
+           printf '%s\\t%s+V\\t2.000000\\n' \"$line\" \"$line\"\n\
+           printf '%s\\t%s+N\\t1.000000\\n\\n' \"$line\" \"$line\"\n\
+         done\n",
+    )?;
+    fs::set_permissions(&tool, fs::Permissions::from_mode(0o755))?;
+    let backend = ExternalBackend {
+        lookup_cmd: tool.to_string_lossy().into_owned(),
+        args: LookupArgs::default(),
+        generator_fst: None,
+        analyzer_fst: Some("analyser.hfstol".into()),
+        timeout: None,
+        quiet: true,
+    };
+
+    let outcomes = backend.analyze_batch(&["guolli".into()]).await?;
+    assert_eq!(
+        outcomes,
+        vec![Ok(vec![weighted("guolli+N", 0.5), weighted("guolli+V", 2.0)])]
+    );
+    Ok(())
+}